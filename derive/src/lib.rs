@@ -0,0 +1,232 @@
+//! `#[derive(ApolloConfig)]` for [apollo-rust-client].
+//!
+//! Enable the `derive` feature of `apollo-rust-client` and import the macro
+//! from there; the attributes are documented in its `typed` module.
+//!
+//! [apollo-rust-client]: https://docs.rs/apollo-rust-client
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    Data, DeriveInput, Expr, ExprLit, Fields, GenericArgument, Ident, Lit, LitStr, PathArguments,
+    Type, ext::IdentExt, parse_macro_input,
+};
+
+/// Implements `apollo_rust_client::typed::ApolloConfig` for a struct with
+/// named fields.
+#[proc_macro_derive(ApolloConfig, attributes(apollo))]
+pub fn derive_apollo_config(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// How keys are derived from field names.
+#[derive(Clone, Copy)]
+enum RenameAll {
+    Snake,
+    Dot,
+    Kebab,
+    Camel,
+}
+
+/// What a field falls back to when its key is missing.
+enum Fallback {
+    /// The key is required.
+    None,
+    /// `Default::default()`.
+    Trait,
+    /// A string literal converted like a configured value.
+    Text(LitStr),
+    /// An expression of the field type.
+    Expr(Expr),
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "ApolloConfig can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "ApolloConfig can only be derived for structs with named fields",
+        ));
+    };
+
+    let (prefix, rename_all) = container_attributes(input)?;
+    let mut reads = Vec::new();
+    let mut required = Vec::new();
+    let mut initializers = Vec::new();
+    for field in &fields.named {
+        let Some(name) = &field.ident else {
+            continue;
+        };
+        let (key, fallback) = field_attributes(field)?;
+        let key = format!(
+            "{prefix}{}",
+            key.unwrap_or_else(|| rename(&name.to_string(), rename_all))
+        );
+        let ty = &field.ty;
+        let value = format_ident!("__{}", name.unraw());
+        if let (Some(inner), Fallback::None) = (option_inner(ty), &fallback) {
+            reads.push(quote! {
+                let #value = fields.optional::<#inner>(#key);
+            });
+            initializers.push(quote! { #name: #value });
+            continue;
+        }
+        let read = match fallback {
+            Fallback::None => quote! { fields.required::<#ty>(#key) },
+            Fallback::Trait => quote! {
+                fields.optional::<#ty>(#key).or_else(|| ::core::option::Option::Some(
+                    <#ty as ::core::default::Default>::default(),
+                ))
+            },
+            Fallback::Text(text) => quote! { fields.or_default_str::<#ty>(#key, #text) },
+            Fallback::Expr(expression) => quote! {
+                fields.optional::<#ty>(#key).or_else(|| {
+                    let default: #ty = #expression;
+                    ::core::option::Option::Some(default)
+                })
+            },
+        };
+        reads.push(quote! { let #value = #read; });
+        required.push(value.clone());
+        initializers.push(quote! { #name: #value });
+    }
+
+    let patterns = required
+        .iter()
+        .map(|value| quote! { ::core::option::Option::Some(#value) });
+    let ident: &Ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::apollo_rust_client::typed::ApolloConfig for #ident #type_generics #where_clause {
+            fn from_source(
+                source: &::apollo_rust_client::typed::Source,
+            ) -> ::core::result::Result<Self, ::apollo_rust_client::typed::Error> {
+                let mut fields = source.fields();
+                #(#reads)*
+                let value = match (#(#required,)*) {
+                    (#(#patterns,)*) => ::core::option::Option::Some(Self { #(#initializers),* }),
+                    #[allow(unreachable_patterns)]
+                    _ => ::core::option::Option::None,
+                };
+                fields.finish(value)
+            }
+        }
+    })
+}
+
+/// Parses `#[apollo(prefix = "...", rename_all = "...")]` on the struct.
+fn container_attributes(input: &DeriveInput) -> syn::Result<(String, RenameAll)> {
+    let mut prefix = String::new();
+    let mut rename_all = RenameAll::Snake;
+    for attribute in input
+        .attrs
+        .iter()
+        .filter(|attribute| attribute.path().is_ident("apollo"))
+    {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("prefix") {
+                prefix = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("rename_all") {
+                let rule = meta.value()?.parse::<LitStr>()?;
+                rename_all = match rule.value().as_str() {
+                    "snake_case" => RenameAll::Snake,
+                    "dot.case" => RenameAll::Dot,
+                    "kebab-case" => RenameAll::Kebab,
+                    "camelCase" => RenameAll::Camel,
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            rule,
+                            "expected \"snake_case\", \"dot.case\", \"kebab-case\" or \"camelCase\"",
+                        ));
+                    }
+                };
+            } else {
+                return Err(meta.error("expected `prefix` or `rename_all`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok((prefix, rename_all))
+}
+
+/// Parses `#[apollo(key = "...", default)]` on a field.
+fn field_attributes(field: &syn::Field) -> syn::Result<(Option<String>, Fallback)> {
+    let mut key = None;
+    let mut fallback = Fallback::None;
+    for attribute in field
+        .attrs
+        .iter()
+        .filter(|attribute| attribute.path().is_ident("apollo"))
+    {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("key") {
+                key = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("default") {
+                fallback = if meta.input.peek(syn::Token![=]) {
+                    match meta.value()?.parse::<Expr>()? {
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(text),
+                            ..
+                        }) => Fallback::Text(text),
+                        expression => Fallback::Expr(expression),
+                    }
+                } else {
+                    Fallback::Trait
+                };
+            } else {
+                return Err(meta.error("expected `key` or `default`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok((key, fallback))
+}
+
+/// Returns `T` if `ty` is written `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+/// Derives a key from a snake case field name.
+fn rename(name: &str, rule: RenameAll) -> String {
+    let name = name.strip_prefix("r#").unwrap_or(name);
+    match rule {
+        RenameAll::Snake => name.to_string(),
+        RenameAll::Dot => name.replace('_', "."),
+        RenameAll::Kebab => name.replace('_', "-"),
+        RenameAll::Camel => {
+            let mut words = name.split('_');
+            let mut key = words.next().unwrap_or_default().to_string();
+            for word in words {
+                let mut characters = word.chars();
+                if let Some(first) = characters.next() {
+                    key.extend(first.to_uppercase());
+                    key.push_str(characters.as_str());
+                }
+            }
+            key
+        }
+    }
+}
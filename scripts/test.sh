@@ -7,6 +7,9 @@ set -eu
 cargo clippy --all-targets -- -D warnings && \
 cargo clippy --no-default-features --features rustls --all-targets -- -D warnings && \
 cargo clippy --target wasm32-unknown-unknown --all-targets -- -D warnings && \
+cargo clippy --workspace --features cli,emulator,derive,override-watch,shared-cache,test-util --all-targets -- -D warnings && \
 RUST_LOG=apollo_rust_client=trace cargo test --all-targets -- --nocapture && \
 cargo test --doc && \
+cargo test --workspace --features cli,emulator,derive,override-watch,shared-cache,test-util --all-targets && \
+cargo test --workspace --features cli,emulator,derive,override-watch,shared-cache,test-util --doc && \
 RUST_BACKTRACE=1 wasm-pack test --node --lib -- --nocapture
@@ -0,0 +1,82 @@
+//! Serves a directory of namespace files as a local Apollo config service.
+//!
+//! ```text
+//! apollo-emulator ./config --app-id my-app --bind 127.0.0.1:8080
+//! ```
+//!
+//! Point clients at the printed URL as their `config_server`. Requires the
+//! `emulator` feature.
+
+use apollo_rust_client::emulator::Emulator;
+use clap::Parser;
+use std::{net::SocketAddr, path::PathBuf, process::ExitCode};
+
+/// Serves a directory of namespace files as a local Apollo config service.
+#[derive(Debug, Parser)]
+#[command(name = "apollo-emulator", version)]
+struct Arguments {
+    /// Directory holding one file per namespace, such as
+    /// `application.properties` or `settings.json`.
+    directory: PathBuf,
+
+    /// App id the namespaces are published under.
+    #[arg(long, env = "APP_ID")]
+    app_id: String,
+
+    /// Cluster the namespaces are published to.
+    #[arg(long, env = "IDC", default_value = "default")]
+    cluster: String,
+
+    /// Address to listen on.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    bind: SocketAddr,
+
+    /// Require requests to be signed with this access key secret.
+    #[arg(long, env = "APOLLO_ACCESS_KEY_SECRET", hide_env_values = true)]
+    secret: Option<String>,
+
+    /// Serve HTTPS with a self-signed certificate for `localhost`.
+    #[arg(long)]
+    https: bool,
+}
+
+fn main() -> ExitCode {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let arguments = Arguments::parse();
+
+    let mut builder = Emulator::builder(&arguments.directory, &arguments.app_id)
+        .cluster(&arguments.cluster)
+        .bind(arguments.bind)
+        .https(arguments.https);
+    if let Some(secret) = arguments.secret {
+        builder = builder.secret(secret);
+    }
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(error) => {
+            eprintln!("apollo-emulator: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let _guard = runtime.enter();
+    let emulator = match builder.start() {
+        Ok(emulator) => emulator,
+        Err(error) => {
+            eprintln!("apollo-emulator: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!(
+        "Serving {} namespace(s) of app {} in cluster {} from {} at {}",
+        emulator.namespaces().len(),
+        arguments.app_id,
+        arguments.cluster,
+        arguments.directory.display(),
+        emulator.url(),
+    );
+    runtime.block_on(std::future::pending())
+}
@@ -0,0 +1,428 @@
+//! Inspects and watches Apollo configuration from the command line.
+//!
+//! ```text
+//! apollo --app-id my-app --config-server http://apollo:8080 get application db.url
+//! apollo --label canary dump settings.json --format yaml
+//! apollo watch application
+//! apollo status application settings.json
+//! apollo exec --prefix APP_ --on-change HUP -- ./legacy-server --verbose
+//! ```
+//!
+//! Without both `--app-id` and `--config-server`, the connection is read with
+//! `ClientConfig::from_env` and the given flags override it. Requires the
+//! `cli` feature.
+
+use apollo_rust_client::{
+    Client, ConfigChangeEvent, Diagnosis, PropertyChangeType, SignatureStatus,
+    client_config::ClientConfig,
+    exec::{KeyCase, OnChange, Supervisor},
+    namespace::{Namespace, path, to_properties},
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde_json::Value;
+use std::{
+    error::Error,
+    ffi::OsString,
+    process::{ExitCode, ExitStatus},
+    sync::Arc,
+    time::Duration,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Inspects and watches Apollo configuration with the client the services
+/// use.
+#[derive(Debug, Parser)]
+#[command(name = "apollo", version)]
+struct Arguments {
+    #[command(flatten)]
+    connection: Connection,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Connection settings. Unset settings come from the environment.
+#[derive(Debug, Args)]
+struct Connection {
+    /// App id [default: `APP_ID`].
+    #[arg(long, global = true)]
+    app_id: Option<String>,
+
+    /// Apollo config service URL [default: `APOLLO_CONFIG_SERVICE`].
+    #[arg(long, global = true)]
+    config_server: Option<String>,
+
+    /// Cluster [default: `default`].
+    #[arg(long, global = true, env = "IDC")]
+    cluster: Option<String>,
+
+    /// Access key secret.
+    #[arg(
+        long,
+        global = true,
+        env = "APOLLO_ACCESS_KEY_SECRET",
+        hide_env_values = true
+    )]
+    secret: Option<String>,
+
+    /// Gray release label.
+    #[arg(long, global = true, env = "APOLLO_LABEL")]
+    label: Option<String>,
+
+    /// Gray release IP.
+    #[arg(long, global = true)]
+    ip: Option<String>,
+
+    /// Local cache directory.
+    #[arg(long, global = true, env = "APOLLO_CACHE_DIR")]
+    cache_dir: Option<String>,
+
+    /// Accept self-signed HTTPS certificates.
+    #[arg(long, global = true)]
+    allow_insecure_https: bool,
+
+    /// Seconds between polls while watching [default:
+    /// `APOLLO_REFRESH_INTERVAL`, then 30].
+    #[arg(long, global = true)]
+    refresh_interval: Option<u64>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Prints a namespace, or one value of it.
+    ///
+    /// Keys of JSON and YAML namespaces are JSON Pointers (`/db/hosts/0`) or
+    /// dotted paths (`db.hosts.0`).
+    Get {
+        namespace: String,
+        key: Option<String>,
+    },
+
+    /// Prints a namespace as Properties, JSON or YAML.
+    Dump {
+        namespace: String,
+
+        /// Output format [default: the namespace's own format].
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+    },
+
+    /// Prints a namespace, then each change until interrupted.
+    Watch { namespace: String },
+
+    /// Checks that Apollo is reachable and accepts the signature, and shows
+    /// where namespaces are cached.
+    Status {
+        #[arg(default_value = "application")]
+        namespaces: Vec<String>,
+    },
+
+    /// Runs a program with Properties namespaces as environment variables,
+    /// restarting or signalling it when they change.
+    ///
+    /// Starts from the local cache when Apollo is unreachable, and exits with
+    /// the program's exit code.
+    Exec {
+        /// Namespace to inject; repeat for several, later ones winning
+        /// [default: application].
+        #[arg(long = "namespace", short)]
+        namespaces: Vec<String>,
+
+        /// Prefix of the variable names.
+        #[arg(long, default_value = "")]
+        prefix: String,
+
+        /// Case of the variable names: upper, lower or preserve.
+        #[arg(long, default_value = "upper")]
+        case: KeyCase,
+
+        /// `restart`, or a signal to send instead, such as `HUP` or `USR1`.
+        #[arg(long, default_value = "restart")]
+        on_change: OnChange,
+
+        /// Seconds a restarted program has to exit before it is killed.
+        #[arg(long, default_value_t = 10)]
+        grace_period: u64,
+
+        /// The program and its arguments.
+        #[arg(last = true, required = true)]
+        command: Vec<OsString>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Format {
+    Properties,
+    Json,
+    Yaml,
+}
+
+fn main() -> ExitCode {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+    let arguments = Arguments::parse();
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(error) => {
+            eprintln!("apollo: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match runtime.block_on(run(arguments)) {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("apollo: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(arguments: Arguments) -> Result<ExitCode> {
+    let config = arguments.connection.config()?;
+    let client = Client::new(config.clone())?;
+    match arguments.command {
+        Command::Get { namespace, key } => {
+            let (format, value) = load(&client, &namespace).await?;
+            let Some(key) = key else {
+                print!("{}", render(&value, format)?);
+                return Ok(ExitCode::SUCCESS);
+            };
+            let found = match format {
+                Some(Format::Properties) => value.get(&key),
+                _ => path::lookup(&value, &key),
+            };
+            match found {
+                Some(Value::String(text)) => println!("{text}"),
+                Some(value) => println!("{}", serde_json::to_string_pretty(value)?),
+                None => {
+                    eprintln!("apollo: {key} is not set in {namespace}");
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+        }
+        Command::Dump { namespace, format } => {
+            let (own_format, value) = load(&client, &namespace).await?;
+            print!("{}", render(&value, format.or(own_format))?);
+        }
+        Command::Watch { namespace } => watch(&client, &namespace).await?,
+        Command::Status { namespaces } => return Ok(status(&client, &config, &namespaces).await),
+        Command::Exec {
+            namespaces,
+            prefix,
+            case,
+            on_change,
+            grace_period,
+            command,
+        } => {
+            let mut command = command.into_iter();
+            let program = command.next().ok_or("no program to run")?;
+            let mut supervisor = Supervisor::new(client, program)
+                .args(command)
+                .prefix(prefix)
+                .key_case(case)
+                .on_change(on_change)
+                .grace_period(Duration::from_secs(grace_period));
+            for namespace in namespaces {
+                supervisor = supervisor.namespace(namespace);
+            }
+            return Ok(exit_code(supervisor.run().await?));
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+impl Connection {
+    /// Builds the client configuration from the environment, with the flags
+    /// taking precedence. Without `APP_ID` or `APOLLO_CONFIG_SERVICE`, the
+    /// flags must name both, and the settings read by the flags' `env`
+    /// attributes still apply.
+    fn config(&self) -> Result<ClientConfig> {
+        let mut config = match (ClientConfig::from_env(), &self.app_id, &self.config_server) {
+            (Ok(config), _, _) => config,
+            (Err(_), Some(app_id), Some(config_server)) => {
+                ClientConfig::builder(app_id, config_server).build()?
+            }
+            (Err(error), _, _) => {
+                return Err(format!(
+                    "{error}; pass --app-id and --config-server or set APP_ID and APOLLO_CONFIG_SERVICE"
+                )
+                .into());
+            }
+        };
+        let overrides = [
+            (&mut config.app_id, &self.app_id),
+            (&mut config.config_server, &self.config_server),
+            (&mut config.cluster, &self.cluster),
+        ];
+        for (field, flag) in overrides {
+            if let Some(flag) = flag {
+                field.clone_from(flag);
+            }
+        }
+        let optional_overrides = [
+            (&mut config.secret, &self.secret),
+            (&mut config.label, &self.label),
+            (&mut config.ip, &self.ip),
+            (&mut config.cache_dir, &self.cache_dir),
+        ];
+        for (field, flag) in optional_overrides {
+            if flag.is_some() {
+                field.clone_from(flag);
+            }
+        }
+        if self.allow_insecure_https {
+            config.allow_insecure_https = Some(true);
+        }
+        if self.refresh_interval.is_some() {
+            config.refresh_interval = self.refresh_interval;
+        }
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// Loads a namespace as a JSON value, with the format it is written in, or
+/// `None` for text.
+async fn load(client: &Client, namespace: &str) -> Result<(Option<Format>, Value)> {
+    Ok(match client.namespace(namespace).await? {
+        Namespace::Properties(properties) => (
+            Some(Format::Properties),
+            properties
+                .iter()
+                .map(|(key, value)| (key.to_string(), Value::String(value)))
+                .collect(),
+        ),
+        Namespace::Json(json) => (Some(Format::Json), json.to_object()?),
+        Namespace::Yaml(yaml) => (Some(Format::Yaml), yaml.to_object()?),
+        Namespace::Text(text) => (None, Value::String(text)),
+    })
+}
+
+/// Renders a namespace value; text is printed as is.
+fn render(value: &Value, format: Option<Format>) -> Result<String> {
+    let mut output = match (value, format) {
+        (Value::String(text), None | Some(Format::Properties)) => text.clone(),
+        (_, Some(Format::Json) | None) => serde_json::to_string_pretty(value)?,
+        (_, Some(Format::Yaml)) => noyalib::to_string(value)?,
+        (_, Some(Format::Properties)) => to_properties(value),
+    };
+    if !output.ends_with('\n') {
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+async fn watch(client: &Client, namespace: &str) -> Result<()> {
+    let (format, value) = load(client, namespace).await?;
+    print!("{}", render(&value, format)?);
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let _subscription = client
+        .add_change_listener(
+            namespace,
+            Arc::new(move |event| {
+                let _ = sender.send(event.map_err(|error| error.to_string()));
+            }),
+        )
+        .await;
+    client.start().await?;
+    while let Some(event) = receiver.recv().await {
+        match event {
+            Ok(event) => print_changes(&event),
+            Err(error) => eprintln!("apollo: {error}"),
+        }
+    }
+    Ok(())
+}
+
+/// Exits like the supervised program: with its code, or on Unix with 128
+/// plus the signal that killed it, as shells do.
+fn exit_code(status: ExitStatus) -> ExitCode {
+    #[cfg(unix)]
+    if let Some(signal) = std::os::unix::process::ExitStatusExt::signal(&status) {
+        return ExitCode::from(u8::try_from(128 + signal).unwrap_or(u8::MAX));
+    }
+    match status.code() {
+        Some(code) => ExitCode::from(u8::try_from(code).unwrap_or(1)),
+        None => ExitCode::FAILURE,
+    }
+}
+
+fn print_changes(event: &ConfigChangeEvent) {
+    let show = |value: Option<&Value>| match value {
+        Some(Value::String(text)) => text.clone(),
+        Some(value) => value.to_string(),
+        None => String::new(),
+    };
+    for (key, change) in event.changes() {
+        match change.change_type() {
+            PropertyChangeType::Added => println!("+ {key} = {}", show(change.new_value())),
+            PropertyChangeType::Deleted => println!("- {key} = {}", show(change.old_value())),
+            PropertyChangeType::Modified => println!(
+                "~ {key} = {} -> {}",
+                show(change.old_value()),
+                show(change.new_value())
+            ),
+        }
+    }
+}
+
+async fn status(client: &Client, config: &ClientConfig, namespaces: &[String]) -> ExitCode {
+    let unset = || "(not set)".to_string();
+    println!("App id:        {}", config.app_id);
+    println!("Cluster:       {}", config.cluster);
+    println!("Config server: {}", config.config_server);
+    println!(
+        "Label:         {}",
+        config.label.clone().unwrap_or_else(unset)
+    );
+    println!("IP:            {}", config.ip.clone().unwrap_or_else(unset));
+    println!(
+        "Secret:        {}",
+        if config.secret.is_some() {
+            "configured"
+        } else {
+            "not configured"
+        }
+    );
+
+    let mut healthy = true;
+    for namespace in namespaces {
+        let diagnosis = client.diagnose(namespace).await;
+        healthy &= diagnosis.is_ok();
+        print_diagnosis(&diagnosis);
+    }
+    if healthy {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn print_diagnosis(diagnosis: &Diagnosis) {
+    println!();
+    println!("{}", diagnosis.namespace);
+    match diagnosis.http_status {
+        Some(status) => println!("  Server:     reachable (HTTP {status})"),
+        None => println!("  Server:     unreachable"),
+    }
+    let signature = match diagnosis.signature {
+        SignatureStatus::Unsigned => "not signed",
+        SignatureStatus::Accepted => "accepted",
+        SignatureStatus::Rejected => "rejected",
+        SignatureStatus::Unknown => "unknown",
+    };
+    println!("  Signature:  {signature}");
+    let present = if std::path::Path::new(&diagnosis.cache_location).exists() {
+        "present"
+    } else {
+        "missing"
+    };
+    println!("  Cache file: {} ({present})", diagnosis.cache_location);
+    if let Some(error) = &diagnosis.error {
+        println!("  Error:      {error}");
+    }
+}
@@ -0,0 +1,279 @@
+//! Java client compatible cache files.
+//!
+//! The Java Apollo client persists each namespace as
+//! `{appId}+{cluster}+{namespace}.properties` using `java.util.Properties`
+//! syntax. Properties namespaces store one entry per key, while JSON, YAML, and
+//! text namespaces store their document under the `content` key, which mirrors
+//! the `configfiles/json` response this crate already caches.
+
+use chrono::Utc;
+use serde_json::{Map, Value};
+use std::fmt::Write;
+
+/// Separator the Java client places between app id, cluster, and namespace.
+const CLUSTER_NAMESPACE_SEPARATOR: char = '+';
+
+/// Returns the Java cache file name, or `None` when a component could escape
+/// the cache directory.
+pub(crate) fn file_name(app_id: &str, cluster: &str, namespace: &str) -> Option<String> {
+    let is_safe = |component: &str| {
+        !component.is_empty()
+            && component != "."
+            && component != ".."
+            && !component.contains(['/', '\\', '\0'])
+    };
+    if [app_id, cluster, namespace].into_iter().all(is_safe) {
+        Some(format!(
+            "{app_id}{CLUSTER_NAMESPACE_SEPARATOR}{cluster}{CLUSTER_NAMESPACE_SEPARATOR}{namespace}.properties"
+        ))
+    } else {
+        None
+    }
+}
+
+/// Serializes a cached configuration object in `java.util.Properties` format.
+///
+/// Output is ASCII-only, with other characters written as `\uXXXX` escapes, so
+/// the Java client's ISO-8859-1 reader loads it unchanged.
+pub(crate) fn to_properties(config: &Value) -> String {
+    let mut content = String::from("#Persisted by apollo-rust-client\n");
+    let _ = writeln!(
+        content,
+        "#{}",
+        Utc::now().format("%a %b %d %H:%M:%S UTC %Y")
+    );
+    if let Value::Object(entries) = config {
+        write_entries(&mut content, entries);
+    }
+    content
+}
+
+/// Appends one escaped `key=value` line per entry, skipping `null` values.
+pub(crate) fn write_entries<'a>(
+    content: &mut String,
+    entries: impl IntoIterator<Item = (&'a String, &'a Value)>,
+) {
+    for (key, value) in entries {
+        let value = match value {
+            Value::Null => continue,
+            Value::String(value) => value.clone(),
+            other => other.to_string(),
+        };
+        escape_into(content, key, true);
+        content.push('=');
+        escape_into(content, &value, false);
+        content.push('\n');
+    }
+}
+
+/// Parses `java.util.Properties` content into a configuration object.
+///
+/// Files written by the Java client are ISO-8859-1; hand-edited UTF-8 files are
+/// accepted as well.
+pub(crate) fn from_properties(bytes: &[u8]) -> Value {
+    let text = std::str::from_utf8(bytes).map_or_else(
+        |_| bytes.iter().map(|&byte| char::from(byte)).collect(),
+        ToString::to_string,
+    );
+
+    let mut entries = Map::new();
+    for line in logical_lines(&text) {
+        let (key, value) = split_entry(&line);
+        entries.insert(unescape(key), Value::String(unescape(value)));
+    }
+    Value::Object(entries)
+}
+
+fn is_properties_whitespace(character: char) -> bool {
+    matches!(character, ' ' | '\t' | '\x0c')
+}
+
+/// Joins continuation lines and drops blank and comment lines.
+///
+/// Like `java.util.Properties`, `\r\n`, `\r` and `\n` each end a line.
+fn logical_lines(text: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current: Option<String> = None;
+    for raw in text.replace("\r\n", "\n").split(['\n', '\r']) {
+        let trimmed = raw.trim_start_matches(is_properties_whitespace);
+        let line = if let Some(mut pending) = current.take() {
+            pending.push_str(trimmed);
+            pending
+        } else {
+            if trimmed.is_empty() || trimmed.starts_with(['#', '!']) {
+                continue;
+            }
+            trimmed.to_string()
+        };
+        let trailing_backslashes = line.chars().rev().take_while(|&c| c == '\\').count();
+        if trailing_backslashes % 2 == 1 {
+            let mut pending = line;
+            pending.pop();
+            current = Some(pending);
+        } else {
+            lines.push(line);
+        }
+    }
+    if let Some(pending) = current {
+        lines.push(pending);
+    }
+    lines
+}
+
+/// Splits a logical line at the first unescaped `=`, `:`, or whitespace.
+fn split_entry(line: &str) -> (&str, &str) {
+    let mut escaped = false;
+    let mut key_end = line.len();
+    let mut separator_found = false;
+    for (index, character) in line.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if character == '\\' {
+            escaped = true;
+        } else if character == '=' || character == ':' {
+            key_end = index;
+            separator_found = true;
+            break;
+        } else if is_properties_whitespace(character) {
+            key_end = index;
+            break;
+        }
+    }
+
+    let key = &line[..key_end];
+    let mut rest = &line[key_end..];
+    if separator_found {
+        rest = &rest[1..];
+    } else {
+        rest = rest.trim_start_matches(is_properties_whitespace);
+        if let Some(stripped) = rest.strip_prefix(['=', ':']) {
+            rest = stripped;
+        }
+    }
+    (key, rest.trim_start_matches(is_properties_whitespace))
+}
+
+fn unescape(value: &str) -> String {
+    let mut units: Vec<u16> = Vec::with_capacity(value.len());
+    let mut characters = value.chars();
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            let mut buffer = [0_u16; 2];
+            units.extend_from_slice(character.encode_utf16(&mut buffer));
+            continue;
+        }
+        let escaped = match characters.next() {
+            Some('t') => '\t',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('f') => '\x0c',
+            Some('u') => {
+                let digits: String = characters.by_ref().take(4).collect();
+                match u16::from_str_radix(&digits, 16) {
+                    Ok(unit) if digits.len() == 4 => {
+                        units.push(unit);
+                        continue;
+                    }
+                    _ => {
+                        units.extend("\\u".encode_utf16());
+                        units.extend(digits.encode_utf16());
+                        continue;
+                    }
+                }
+            }
+            Some(other) => other,
+            None => continue,
+        };
+        let mut buffer = [0_u16; 2];
+        units.extend_from_slice(escaped.encode_utf16(&mut buffer));
+    }
+    String::from_utf16_lossy(&units)
+}
+
+fn escape_into(output: &mut String, value: &str, is_key: bool) {
+    for (index, character) in value.chars().enumerate() {
+        match character {
+            ' ' if is_key || index == 0 => output.push_str("\\ "),
+            '\\' => output.push_str("\\\\"),
+            '\t' => output.push_str("\\t"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\x0c' => output.push_str("\\f"),
+            '=' | ':' | '#' | '!' => {
+                output.push('\\');
+                output.push(character);
+            }
+            ' '..='~' => output.push(character),
+            _ => {
+                let mut buffer = [0_u16; 2];
+                for unit in character.encode_utf16(&mut buffer) {
+                    let _ = write!(output, "\\u{unit:04X}");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_names_follow_the_java_convention_and_reject_path_components() {
+        assert_eq!(
+            file_name("app", "default", "application").as_deref(),
+            Some("app+default+application.properties")
+        );
+        assert_eq!(
+            file_name("app", "default", "settings.json").as_deref(),
+            Some("app+default+settings.json.properties")
+        );
+        assert_eq!(file_name("app", "default", "../escape"), None);
+        assert_eq!(file_name("..", "default", "application"), None);
+    }
+
+    #[test]
+    fn properties_round_trip_escapes_and_unicode() {
+        let config = serde_json::json!({
+            "plain": "value",
+            "key with spaces": " leading space",
+            "separators=:#!": "a=b:c",
+            "multi": "line one\nline two\ttabbed",
+            "unicode": "配置 😀",
+            "content": "{\"nested\":true}"
+        });
+        let content = to_properties(&config);
+        assert!(content.is_ascii());
+        assert!(content.contains("unicode=\\u914D\\u7F6E \\uD83D\\uDE00"));
+        assert_eq!(from_properties(content.as_bytes()), config);
+    }
+
+    #[test]
+    fn parses_files_written_by_the_java_client() {
+        let content = b"#Persisted by DefaultConfig\n\
+            #Sat Oct 18 10:00:00 CST 2026\n\
+            ! bang comment\n\
+            \n\
+            timeout=30\n\
+            name : Apollo\\u0020Client\n\
+            spaced   value\n\
+            list=a,\\\n    b,\\\n    c\n\
+            latin=caf\xe9\n";
+        let config = from_properties(content);
+        assert_eq!(config["timeout"], "30");
+        assert_eq!(config["name"], "Apollo Client");
+        assert_eq!(config["spaced"], "value");
+        assert_eq!(config["list"], "a,b,c");
+        assert_eq!(config["latin"], "café");
+        assert_eq!(config.as_object().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn continuations_span_crlf_and_cr_line_endings() {
+        let config = from_properties(b"a=1\\\r\n  2\r\nb=x\\\r  y\rc=3\r\n");
+        assert_eq!(config["a"], "12");
+        assert_eq!(config["b"], "xy");
+        assert_eq!(config["c"], "3");
+        assert_eq!(config.as_object().unwrap().len(), 3);
+    }
+}
@@ -20,8 +20,10 @@
 //!
 //! # Platform Differences
 //!
-//! - **Native Rust**: Full caching with file persistence and background refresh
-//! - **WebAssembly**: Persistent caching using browser localStorage with in-memory fallback for Node.js environments
+//! - **Native Rust**: Full caching with file persistence and background refresh, using
+//!   either this crate's versioned files or the Java client's layout
+//! - **WebAssembly**: Persistent caching using browser localStorage, or `fs` files under Node.js,
+//!   with an in-memory fallback when neither is available
 //!
 //! # Examples
 //!
@@ -29,7 +31,15 @@
 //! by end users. However, understanding its behavior is important for debugging
 //! and performance optimization.
 
-use crate::{EventListener, client_config::ClientConfig, namespace::get_namespace};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::client_config::CacheLayout;
+use crate::{
+    EventListener, ListenerOptions, Subscription, change,
+    client_config::ClientConfig,
+    overrides::Overrides,
+    placeholders::Placeholders,
+    subscription::{ListenerRegistry, Notification, Notifier, event_notifier},
+};
 use base64::display::Base64Display;
 use cfg_if::cfg_if;
 use chrono::Utc;
@@ -40,14 +50,24 @@ use log::{debug, trace, warn};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sha1::{Digest, Sha1};
-use std::sync::atomic::{AtomicU64, AtomicU32, AtomicI64, Ordering};
 use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU16, AtomicU32, AtomicU64, Ordering};
 use std::{fmt::Write, sync::Arc};
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::io::AsyncWriteExt;
 use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
 use url::{ParseError, Url};
 
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod java;
+#[cfg(target_arch = "wasm32")]
+pub(crate) mod node;
+#[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+mod shared;
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+pub(crate) use shared::CacheWatcher;
+
 #[cfg(not(target_arch = "wasm32"))]
 static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -70,7 +90,7 @@ pub(crate) fn cleanup_stale_temp_files(cache_dir: &std::path::Path) {
         let is_temp_file = path
             .file_name()
             .and_then(|name| name.to_str())
-            .is_some_and(|name| name.starts_with("v2-"))
+            .is_some_and(|name| name.starts_with("v2-") || name.contains(".properties."))
             && path
                 .extension()
                 .is_some_and(|extension| extension.eq_ignore_ascii_case("tmp"));
@@ -172,6 +192,17 @@ pub enum Error {
     /// HMAC initialization rejected the configured key.
     #[error("Invalid Apollo signing key")]
     InvalidSigningKey,
+
+    /// Placeholders in a Properties namespace reference each other in a
+    /// cycle.
+    #[error("Placeholder cycle in namespace {namespace}: {chain}")]
+    PlaceholderCycle {
+        /// Namespace whose values could not be resolved.
+        namespace: String,
+        /// The references forming the cycle, as `namespace/key` entries
+        /// joined by ` -> `.
+        chain: String,
+    },
 }
 
 /// A cache instance for managing configuration data for a specific namespace.
@@ -205,7 +236,7 @@ pub enum Error {
 /// # Platform Differences
 ///
 /// - **Native Rust**: Full feature set with file caching and background refresh
-/// - **WebAssembly**: Persistent caching using browser localStorage with in-memory fallback and single-threaded execution
+/// - **WebAssembly**: Persistent caching using browser localStorage or Node.js `fs`, with in-memory fallback and single-threaded execution
 #[cfg_attr(target_arch = "wasm32", allow(clippy::arc_with_non_send_sync))]
 #[derive(Clone)]
 pub(crate) struct Cache {
@@ -224,13 +255,23 @@ pub(crate) struct Cache {
     /// Collection of event listeners for configuration change notifications.
     ///
     /// Listeners are called when the cache is refreshed, allowing applications
-    /// to react to configuration changes during periodic refresh.
-    listeners: Arc<RwLock<Vec<EventListener>>>,
+    /// to react to configuration changes during periodic refresh. Each
+    /// registration is removed when its [`Subscription`] is dropped.
+    listeners: Arc<ListenerRegistry>,
+
+    /// Serializes notifications with listener replay, so a replayed value is
+    /// never delivered after a newer change.
+    notify_lock: Arc<Mutex<()>>,
 
     /// The isolated cache key for WebAssembly localStorage persistence (wasm32 targets only).
     #[cfg(target_arch = "wasm32")]
     wasm_cache_key: String,
 
+    /// Cache file used under Node.js, where `localStorage` is unavailable
+    /// (wasm32 targets only).
+    #[cfg(target_arch = "wasm32")]
+    node_file_path: Option<String>,
+
     /// Cancellation-safe single-flight lock for cold loads.
     load_lock: Arc<Mutex<()>>,
 
@@ -249,6 +290,27 @@ pub(crate) struct Cache {
     /// Error snapshot for waiters that shared a failed refresh.
     last_refresh_error: Arc<RwLock<Option<String>>>,
 
+    /// HTTP status Apollo answered the last refresh with, or 0 if no response
+    /// arrived, for waiters that shared it.
+    last_refresh_status: Arc<AtomicU16>,
+
+    /// Timestamp in Unix seconds of the last access through the client, used
+    /// for idle expiry.
+    last_access: Arc<AtomicI64>,
+
+    /// Whether a fetch from Apollo has succeeded since the cache was created.
+    fetched_from_server: Arc<AtomicBool>,
+
+    /// Local overrides applied to every value served or delivered.
+    overrides: Arc<Overrides>,
+
+    /// Placeholder resolution applied after the overrides.
+    placeholders: Arc<Placeholders>,
+
+    /// The value last delivered to listeners, which the next change event
+    /// is computed against.
+    delivered: Arc<RwLock<Option<Value>>>,
+
     /// Path to the local cache file (native targets only).
     ///
     /// On native targets, this specifies where the configuration should be
@@ -257,6 +319,22 @@ pub(crate) struct Cache {
     #[cfg(not(target_arch = "wasm32"))]
     file_path: std::path::PathBuf,
 
+    /// Format of the file at `file_path` (native targets only).
+    #[cfg(not(target_arch = "wasm32"))]
+    layout: CacheLayout,
+
+    /// Open lock file while this process polls the namespace in shared-cache
+    /// mode (native targets with the `shared-cache` feature only).
+    #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+    leader_lock: Arc<std::sync::Mutex<Option<std::fs::File>>>,
+
+    /// Timestamp in Unix seconds of the last stale read served by reloading
+    /// the shared cache file instead of fetching, so such reads reload at
+    /// most once per refresh interval (native targets with the `shared-cache`
+    /// feature only).
+    #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+    shared_reload_timestamp: Arc<AtomicI64>,
+
     /// HTTP client for making network requests.
     http_client: reqwest::Client,
 }
@@ -282,6 +360,40 @@ fn cache_identity(client_config: &ClientConfig, namespace: &str) -> String {
     encoded
 }
 
+/// Resolves the persistent cache file for the configured layout.
+///
+/// Java-layout names embed raw identifiers, so a component that could escape
+/// the cache directory falls back to the hashed native file.
+#[cfg(not(target_arch = "wasm32"))]
+fn persistent_file_path(
+    client_config: &ClientConfig,
+    namespace: &str,
+    cache_identity: &str,
+) -> (std::path::PathBuf, CacheLayout) {
+    if client_config.effective_cache_layout() == CacheLayout::Java {
+        if let Some(file_name) =
+            java::file_name(&client_config.app_id, &client_config.cluster, namespace)
+        {
+            return (
+                client_config.get_cache_dir().join(file_name),
+                CacheLayout::Java,
+            );
+        }
+        warn!(
+            "Namespace {namespace} cannot be stored in the Java cache layout; using the native layout"
+        );
+        let mut native = client_config.clone();
+        native.cache_layout = Some(CacheLayout::Native);
+        return persistent_file_path(&native, namespace, cache_identity);
+    }
+    (
+        client_config
+            .get_cache_dir()
+            .join(format!("v2-{cache_identity}.cache.json")),
+        CacheLayout::Native,
+    )
+}
+
 fn hash_identity_part(hasher: &mut Sha1, value: &str) {
     hasher.update(value.len().to_be_bytes());
     hasher.update(value.as_bytes());
@@ -317,29 +429,49 @@ impl Cache {
         let cache_identity = cache_identity(&client_config, namespace);
 
         #[cfg(not(target_arch = "wasm32"))]
-        let file_path = client_config
-            .get_cache_dir()
-            .join(format!("v2-{cache_identity}.cache.json"));
+        let (file_path, layout) = persistent_file_path(&client_config, namespace, &cache_identity);
 
         #[cfg(target_arch = "wasm32")]
         let wasm_cache_key = format!("apollo_cache_v2_{cache_identity}");
+        #[cfg(target_arch = "wasm32")]
+        let node_file_path = if crate::check_local_storage_availability() {
+            None
+        } else {
+            node::cache_dir(&client_config)
+                .map(|directory| format!("{directory}/v2-{cache_identity}.cache.json"))
+        };
 
         Self {
             client_config,
             namespace: namespace.to_string(),
             memory: Arc::new(RwLock::new(None)),
-            listeners: Arc::new(RwLock::new(Vec::new())),
+            listeners: Arc::new(ListenerRegistry::default()),
+            notify_lock: Arc::new(Mutex::new(())),
             load_lock: Arc::new(Mutex::new(())),
             refresh_lock: Arc::new(Mutex::new(())),
             refresh_generation: Arc::new(AtomicU64::new(0)),
             consecutive_failures: Arc::new(AtomicU32::new(0)),
             next_allowed_refresh_timestamp: Arc::new(AtomicI64::new(0)),
             last_refresh_error: Arc::new(RwLock::new(None)),
+            last_refresh_status: Arc::new(AtomicU16::new(0)),
+            last_access: Arc::new(AtomicI64::new(Utc::now().timestamp())),
+            fetched_from_server: Arc::new(AtomicBool::new(false)),
+            overrides: Arc::default(),
+            placeholders: Arc::default(),
+            delivered: Arc::new(RwLock::new(None)),
 
             #[cfg(not(target_arch = "wasm32"))]
             file_path,
+            #[cfg(not(target_arch = "wasm32"))]
+            layout,
+            #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+            leader_lock: Arc::new(std::sync::Mutex::new(None)),
+            #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+            shared_reload_timestamp: Arc::new(AtomicI64::new(0)),
             #[cfg(target_arch = "wasm32")]
             wasm_cache_key,
+            #[cfg(target_arch = "wasm32")]
+            node_file_path,
             http_client,
         }
     }
@@ -372,7 +504,7 @@ impl Cache {
     /// usable stale value exists. Persistent read/write failures are logged and ignored.
     pub(crate) async fn get_value(&self) -> Result<Value, Error> {
         if let Some(item) = self.memory.read().await.clone() {
-            return Ok(self.serve_cached_item(item));
+            return self.serve_cached_item(item);
         }
 
         let observed_refresh_generation = self.refresh_generation.load(Ordering::Acquire);
@@ -380,46 +512,82 @@ impl Cache {
         // avoiding the stuck boolean/notification state of the previous design.
         let _load_guard = self.load_lock.lock().await;
         if let Some(item) = self.memory.read().await.clone() {
-            return Ok(self.serve_cached_item(item));
+            return self.serve_cached_item(item);
         }
 
         if let Some(item) = self.load_persistent_item().await {
             self.replace_memory(item.clone()).await;
-            return Ok(self.serve_cached_item(item));
+            return self.serve_cached_item(item);
         }
 
         if self.refresh_generation.load(Ordering::Acquire) != observed_refresh_generation {
             return Err(self.coalesced_refresh_error().await);
         }
 
-        self.coalesced_refresh(false).await?;
-        self.memory
+        self.coalesced_refresh(false).await.1?;
+        let config = self
+            .memory
             .read()
             .await
             .as_ref()
             .map(|item| item.config.clone())
-            .ok_or_else(|| Error::CoalescedRefresh("refresh produced no cache value".to_string()))
+            .ok_or_else(|| {
+                Error::CoalescedRefresh("refresh produced no cache value".to_string())
+            })?;
+        self.effective(config)
     }
 
-    fn serve_cached_item(&self, item: CacheItem) -> Value {
+    fn serve_cached_item(&self, item: CacheItem) -> Result<Value, Error> {
         if !self.is_fresh(&item) {
             self.schedule_revalidation();
         }
-        item.config
+        self.effective(item.config)
+    }
+
+    /// Applies the overrides, then resolves placeholders, in a raw cached
+    /// value.
+    fn effective(&self, config: Value) -> Result<Value, Error> {
+        let config = self.overrides.apply(&self.namespace, config);
+        self.placeholders
+            .resolve(&self.client_config, &self.namespace, config)
+            .map_err(|chain| Error::PlaceholderCycle {
+                namespace: self.namespace.clone(),
+                chain,
+            })
     }
 
     fn schedule_revalidation(&self) {
+        #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+        {
+            let interval =
+                i64::try_from(self.client_config.effective_refresh_interval()).unwrap_or(i64::MAX);
+            let reloaded = self.shared_reload_timestamp.load(Ordering::Acquire);
+            if Utc::now().timestamp() < reloaded.saturating_add(interval) {
+                return;
+            }
+        }
         let Ok(refresh_guard) = self.refresh_lock.clone().try_lock_owned() else {
             return;
         };
         let cache = self.clone();
         let task = async move {
-            if let Err(error) = cache.perform_refresh(refresh_guard).await {
+            #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+            if cache.has_other_leader().await {
+                debug!(
+                    "Reloading namespace {} from the shared cache instead of fetching it",
+                    cache.namespace
+                );
+                cache
+                    .shared_reload_timestamp
+                    .store(Utc::now().timestamp(), Ordering::Release);
+                cache.reload_from_disk().await;
+                return;
+            }
+            if let (_, Err(error)) = cache.perform_refresh(refresh_guard, true).await {
                 warn!(
                     "Using stale cached configuration for namespace {} after refresh failure: {}",
                     cache.namespace, error
                 );
-                cache.notify_error(&error).await;
             }
         };
         cfg_if! {
@@ -448,6 +616,9 @@ impl Cache {
         cfg_if! {
             if #[cfg(not(target_arch = "wasm32"))] {
                 match tokio::fs::read(&self.file_path).await {
+                    Ok(content) if self.layout == CacheLayout::Java => {
+                        Some(self.java_cache_item(&content).await)
+                    }
                     Ok(content) => match serde_json::from_slice(&content) {
                         Ok(item) => Some(item),
                         Err(error) => {
@@ -462,11 +633,15 @@ impl Cache {
                     }
                 }
             } else {
-                load_from_local_storage(&self.wasm_cache_key).and_then(|cached| {
+                let (cached, source) = match &self.node_file_path {
+                    Some(path) => (node::read(path), path.as_str()),
+                    None => (load_from_local_storage(&self.wasm_cache_key), self.wasm_cache_key.as_str()),
+                };
+                cached.and_then(|cached| {
                     match serde_json::from_str(&cached) {
                         Ok(item) => Some(item),
                         Err(error) => {
-                            warn!("Ignoring corrupt localStorage cache entry {}: {error}", self.wasm_cache_key);
+                            warn!("Ignoring corrupt cache entry {source}: {error}");
                             None
                         }
                     }
@@ -475,6 +650,22 @@ impl Cache {
         }
     }
 
+    /// Builds a cache item from a Java-layout file, using its modification time
+    /// as the fetch timestamp.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn java_cache_item(&self, content: &[u8]) -> CacheItem {
+        let timestamp = tokio::fs::metadata(&self.file_path)
+            .await
+            .and_then(|metadata| metadata.modified())
+            .map_or(0, |modified| {
+                chrono::DateTime::<Utc>::from(modified).timestamp()
+            });
+        CacheItem {
+            timestamp,
+            config: java::from_properties(content),
+        }
+    }
+
     /// Refreshes the cache by fetching the latest configuration from the Apollo server.
     ///
     /// This method requests the latest configuration from the remote server,
@@ -494,30 +685,45 @@ impl Cache {
     /// This method can return various errors, such as network errors, parsing errors,
     /// or HTTP-status errors. Persistence failures are logged and do not fail refresh.
     pub(crate) async fn refresh(&self) -> Result<(), Error> {
+        self.coalesced_refresh(true).await.1
+    }
+
+    /// Refreshes like [`Cache::refresh`], also returning the HTTP status
+    /// Apollo answered the refresh with, or `None` if no response arrived.
+    pub(crate) async fn refresh_with_status(&self) -> (Option<u16>, Result<(), Error>) {
         self.coalesced_refresh(true).await
     }
 
-    async fn coalesced_refresh(&self, notify_error: bool) -> Result<(), Error> {
+    async fn coalesced_refresh(&self, notify_error: bool) -> (Option<u16>, Result<(), Error>) {
         let observed_generation = self.refresh_generation.load(Ordering::Acquire);
         let refresh_guard = self.refresh_lock.clone().lock_owned().await;
         if self.refresh_generation.load(Ordering::Acquire) != observed_generation {
-            return self.last_refresh_result().await;
+            let status = self.last_refresh_status.load(Ordering::Acquire);
+            return (
+                (status != 0).then_some(status),
+                self.last_refresh_result().await,
+            );
         }
 
-        let result = self.perform_refresh(refresh_guard).await;
-        if notify_error && let Err(error) = &result {
-            self.notify_error(error).await;
-        }
-        result
+        self.perform_refresh(refresh_guard, notify_error).await
     }
 
-    async fn perform_refresh(&self, _refresh_guard: OwnedMutexGuard<()>) -> Result<(), Error> {
-        let result = match self.fetch_remote_config().await {
+    /// Refreshes from Apollo while holding the refresh lock, which also covers
+    /// persistence and listener delivery so [`Cache::drain`] can wait for them.
+    async fn perform_refresh(
+        &self,
+        _refresh_guard: OwnedMutexGuard<()>,
+        notify_error: bool,
+    ) -> (Option<u16>, Result<(), Error>) {
+        let (status, fetched) = self.fetch_remote_config().await;
+        let result = match fetched {
             Ok(item) => {
                 self.persist_best_effort(&item).await;
                 self.replace_memory(item).await;
+                self.fetched_from_server.store(true, Ordering::Release);
                 self.consecutive_failures.store(0, Ordering::Release);
-                self.next_allowed_refresh_timestamp.store(0, Ordering::Release);
+                self.next_allowed_refresh_timestamp
+                    .store(0, Ordering::Release);
                 Ok(())
             }
             Err(error) => {
@@ -528,13 +734,19 @@ impl Cache {
                 let delay = refresh_delay_seconds(base_interval, failures, random);
                 #[allow(clippy::cast_possible_wrap)]
                 let next_time = Utc::now().timestamp() + delay as i64;
-                self.next_allowed_refresh_timestamp.store(next_time, Ordering::Release);
+                self.next_allowed_refresh_timestamp
+                    .store(next_time, Ordering::Release);
                 Err(error)
             }
         };
         *self.last_refresh_error.write().await = result.as_ref().err().map(ToString::to_string);
+        self.last_refresh_status
+            .store(status.unwrap_or_default(), Ordering::Release);
         self.refresh_generation.fetch_add(1, Ordering::Release);
-        result
+        if notify_error && let Err(error) = &result {
+            self.notify_error(error).await;
+        }
+        (status, result)
     }
 
     async fn last_refresh_result(&self) -> Result<(), Error> {
@@ -552,45 +764,114 @@ impl Cache {
     }
 
     async fn replace_memory(&self, item: CacheItem) {
-        let changed = {
-            let mut memory = self.memory.write().await;
-            let changed = memory
-                .as_ref()
-                .is_none_or(|previous| previous.config != item.config);
-            *memory = Some(item.clone());
-            changed
+        let notify_guard = self.notify_lock.lock().await;
+        let previous = self.memory.write().await.replace(item.clone());
+        if previous.is_some_and(|previous| previous.config == item.config) {
+            return;
+        }
+        let dependents_stale = self.publish().await;
+        drop(notify_guard);
+        if dependents_stale {
+            self.placeholders.propagate().await;
+        }
+    }
+
+    /// Notifies listeners if the value in memory, with the current overrides
+    /// and placeholders, differs from the value they last received.
+    pub(crate) async fn republish(&self) {
+        if self.resolve_again().await {
+            self.placeholders.propagate().await;
+        }
+    }
+
+    /// Like [`Cache::republish`], but leaves propagating a change of the
+    /// values placeholders resolve against to the caller.
+    pub(crate) async fn resolve_again(&self) -> bool {
+        let _notify_guard = self.notify_lock.lock().await;
+        self.publish().await
+    }
+
+    /// Delivers the effective value in memory if it changed, with the
+    /// notification lock held and no cache data lock held.
+    ///
+    /// Returns whether the values other namespaces' placeholders resolve
+    /// against changed.
+    async fn publish(&self) -> bool {
+        let Some(config) = self
+            .memory
+            .read()
+            .await
+            .as_ref()
+            .map(|item| item.config.clone())
+        else {
+            return false;
+        };
+        let config = self.overrides.apply(&self.namespace, config);
+        let dependents_stale =
+            self.placeholders
+                .record(&self.client_config, &self.namespace, &config);
+        let config = match self
+            .placeholders
+            .resolve(&self.client_config, &self.namespace, config)
+        {
+            Ok(config) => config,
+            Err(chain) => {
+                let error = Error::PlaceholderCycle {
+                    namespace: self.namespace.clone(),
+                    chain,
+                };
+                warn!("{error}");
+                notify(
+                    &Notification::Failed(&error.to_string()),
+                    &self.listeners.snapshot(),
+                );
+                return dependents_stale;
+            }
         };
 
-        if changed {
-            let listeners = self.listeners.read().await.clone();
-            self.notify_listeners(&item.config, &listeners);
+        let mut delivered = self.delivered.write().await;
+        if delivered.as_ref() == Some(&config) {
+            return dependents_stale;
         }
+        let event = change::diff(&self.namespace, delivered.as_ref(), &config);
+        *delivered = Some(config.clone());
+        drop(delivered);
+        notify(
+            &Notification::Changed {
+                config: &config,
+                event: &event,
+            },
+            &self.listeners.snapshot(),
+        );
+        dependents_stale
     }
 
     async fn notify_error(&self, error: &Error) {
-        let listeners = self.listeners.read().await.clone();
-        for listener in listeners {
-            invoke_listener(&listener, Err(crate::Error::Refresh(error.to_string())));
-        }
+        let _notify_guard = self.notify_lock.lock().await;
+        notify(
+            &Notification::Failed(&error.to_string()),
+            &self.listeners.snapshot(),
+        );
     }
 
-    fn notify_listeners(&self, config: &Value, listeners: &[EventListener]) {
-        for listener in listeners {
-            let config = config.clone();
-            let namespace = self.namespace.clone();
-            invoke_listener(
-                listener,
-                get_namespace(&namespace, config).map_err(crate::Error::Namespace),
-            );
-        }
+    /// Fetches the namespace, returning the HTTP status of the response, if
+    /// one arrived, alongside the result.
+    async fn fetch_remote_config(&self) -> (Option<u16>, Result<CacheItem, Error>) {
+        let status = AtomicU16::new(0);
+        let result = self.fetch_remote_config_recording(&status).await;
+        let status = status.into_inner();
+        ((status != 0).then_some(status), result)
     }
 
-    async fn fetch_remote_config(&self) -> Result<CacheItem, Error> {
+    /// Fetches the namespace, storing the HTTP status of the response in
+    /// `status` once it arrives.
+    async fn fetch_remote_config_recording(&self, status: &AtomicU16) -> Result<CacheItem, Error> {
         let url = self.build_request_url()?;
         let client = self.build_http_request(&url)?;
         let timeout_seconds = self.client_config.effective_request_timeout();
         let request = async {
             let response = self.execute_request(client).await?;
+            status.store(response.status().as_u16(), Ordering::Relaxed);
             self.parse_response(response).await
         };
 
@@ -632,8 +913,14 @@ impl Cache {
                     warn!("Unable to persist cache for namespace {}: {error}", self.namespace);
                 }
             } else {
-                if let Ok(content) = serde_json::to_string(item)
-                    && save_to_local_storage(&self.wasm_cache_key, &content).is_none() {
+                let Ok(content) = serde_json::to_string(item) else {
+                    return;
+                };
+                if let Some(path) = &self.node_file_path {
+                    if let Err(error) = node::write(path, &content) {
+                        warn!("Unable to persist cache for namespace {}: {error}", self.namespace);
+                    }
+                } else if save_to_local_storage(&self.wasm_cache_key, &content).is_none() {
                     log::debug!("Unable to persist localStorage cache entry {}", self.wasm_cache_key);
                 }
             }
@@ -764,7 +1051,8 @@ impl Cache {
     /// Writes the configuration to the file cache (native targets only).
     ///
     /// Creates parent directories if they don't exist and writes the cache item
-    /// with timestamp and configuration data.
+    /// with timestamp and configuration data. The Java layout stores the
+    /// configuration as properties and relies on the file modification time.
     ///
     /// # Arguments
     ///
@@ -784,15 +1072,23 @@ impl Cache {
             tokio::fs::create_dir_all(parent).await?;
         }
 
-        let cache_content = serde_json::to_vec(item)?;
+        let cache_content = match self.layout {
+            CacheLayout::Native => serde_json::to_vec(item)?,
+            CacheLayout::Java => java::to_properties(&item.config).into_bytes(),
+        };
+        let file_name = self
+            .file_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
 
         // Each writer gets a unique temporary file. `create_new` also protects
         // against a rare PID/counter collision across concurrent processes.
         let mut attempt = 0_u8;
         let (temp_file_path, mut temp_file) = loop {
             let counter = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
-            let temp_file_path = self.file_path.with_extension(format!(
-                "json.{}.{}.tmp",
+            let temp_file_path = self.file_path.with_file_name(format!(
+                "{file_name}.{}.{}.tmp",
                 std::process::id(),
                 counter
             ));
@@ -834,16 +1130,22 @@ impl Cache {
     /// Adds an event listener to the cache.
     ///
     /// Listeners are called in registration order when configuration changes or a
-    /// refresh fails. They receive `Result<Namespace, crate::Error>`.
-    ///
+    /// refresh fails. They receive `Result<Namespace, crate::Error>`. The listener
+    /// stays registered until the returned [`Subscription`] is dropped or
+    /// unsubscribed.
     ///
-    /// Callbacks run synchronously after internal locks are released. Panics are caught
-    /// and logged, and do not prevent subsequent listeners from running.
+    /// Callbacks run synchronously after the cache data locks are released,
+    /// but while the namespace's notification lock is held so deliveries stay
+    /// in order. A callback must therefore not block on anything that
+    /// delivers to the same namespace, such as a refresh or a new listener
+    /// with replay; spawn that work instead. Panics are caught and logged, and
+    /// do not prevent subsequent listeners from running.
     ///
     /// # Arguments
     ///
     /// * `listener` - The event listener to register. Native callbacks must be
     ///   `Send + Sync`; WASM callbacks run on the local JavaScript thread.
+    /// * `options` - Registration options, such as replaying the current value.
     ///
     /// # Example
     ///
@@ -861,13 +1163,272 @@ impl Cache {
     ///         Err(error) => eprintln!("Cache refresh failed: {error}"),
     ///     }
     /// });
-    /// client.add_listener("application", listener).await;
+    /// let subscription = client.add_listener("application", listener).await;
+    /// // ...
+    /// drop(subscription); // stops notifications
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn add_listener(&self, listener: EventListener) {
-        let mut listeners = self.listeners.write().await;
-        listeners.push(listener);
+    pub async fn add_listener(
+        &self,
+        listener: EventListener,
+        options: ListenerOptions,
+    ) -> Subscription {
+        self.subscribe(event_notifier(listener), options).await
+    }
+
+    /// Registers a notifier, replaying the current value as an all-added
+    /// change event if requested.
+    pub(crate) async fn subscribe(
+        &self,
+        notifier: Notifier,
+        options: ListenerOptions,
+    ) -> Subscription {
+        if options.replays_current()
+            && let Err(error) = self.get_value().await
+        {
+            debug!(
+                "Not replaying namespace {} to a new listener: {error}",
+                self.namespace
+            );
+        }
+
+        let _notify_guard = self.notify_lock.lock().await;
+        let subscription = self.listeners.register(notifier.clone());
+        if options.replays_current() {
+            let current = self.delivered.read().await.clone();
+            if let Some(config) = current {
+                let event = change::diff(&self.namespace, None, &config);
+                notify(
+                    &Notification::Changed {
+                        config: &config,
+                        event: &event,
+                    },
+                    &[notifier],
+                );
+            }
+        }
+        subscription
+    }
+
+    /// Returns whether this process should poll Apollo for the namespace.
+    ///
+    /// Always `true` unless shared-cache mode is enabled, in which case only
+    /// the holder of the namespace's advisory lock polls. Lock failures other
+    /// than contention fall back to polling so updates are never lost.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+    pub(crate) fn should_poll(&self) -> bool {
+        if !self.client_config.effective_shared_cache() {
+            return true;
+        }
+        let mut leader_lock = self
+            .leader_lock
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if leader_lock.is_some() {
+            return true;
+        }
+        let lock_path = shared::lock_path(&self.file_path);
+        match shared::try_lock(&lock_path) {
+            Ok(Some(file)) => {
+                debug!(
+                    "Elected to poll namespace {} for the shared cache",
+                    self.namespace
+                );
+                *leader_lock = Some(file);
+                true
+            }
+            Ok(None) => false,
+            Err(error) => {
+                warn!(
+                    "Unable to lock shared cache file {}; polling independently: {error}",
+                    lock_path.display()
+                );
+                true
+            }
+        }
+    }
+
+    /// Returns whether this process should poll Apollo for the namespace,
+    /// which it always does without shared-cache mode.
+    #[cfg(any(target_arch = "wasm32", not(feature = "shared-cache")))]
+    #[allow(clippy::unused_self)]
+    pub(crate) fn should_poll(&self) -> bool {
+        true
+    }
+
+    /// Returns whether another process holds the namespace's polling lock in
+    /// shared-cache mode, so its updates arrive through the cache file.
+    ///
+    /// Probing does not take the lock: a process that only reads must not
+    /// become the leader that others wait on. The probe opens a file, so it
+    /// runs on the blocking pool.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+    async fn has_other_leader(&self) -> bool {
+        if !self.client_config.effective_shared_cache()
+            || self
+                .leader_lock
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .is_some()
+        {
+            return false;
+        }
+        let lock_path = shared::lock_path(&self.file_path);
+        tokio::task::spawn_blocking(move || matches!(shared::try_lock(&lock_path), Ok(None)))
+            .await
+            .unwrap_or(false)
+    }
+
+    /// Gives up polling leadership so a sibling process can take over.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+    pub(crate) fn release_leadership(&self) {
+        self.leader_lock
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take();
+    }
+
+    /// Loads the persistent entry after another process wrote it.
+    ///
+    /// Entries older than the value in memory are ignored; otherwise the entry
+    /// replaces memory and listeners are notified if the value changed.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+    pub(crate) async fn reload_from_disk(&self) {
+        let Some(item) = self.load_persistent_item().await else {
+            return;
+        };
+        let is_newer = self
+            .memory
+            .read()
+            .await
+            .as_ref()
+            .is_none_or(|current| item.timestamp >= current.timestamp);
+        if is_newer {
+            self.replace_memory(item).await;
+        }
+    }
+
+    /// Applies the client's shared overrides instead of an empty set.
+    #[must_use]
+    pub(crate) fn with_overrides(mut self, overrides: Arc<Overrides>) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Resolves placeholders against the client's shared registry instead
+    /// of leaving them verbatim.
+    #[must_use]
+    pub(crate) fn with_placeholders(mut self, placeholders: Arc<Placeholders>) -> Self {
+        self.placeholders = placeholders;
+        self
+    }
+
+    /// Lists the overrides that apply to the value in memory.
+    pub(crate) async fn active_overrides(&self) -> Vec<crate::overrides::ActiveOverride> {
+        match self.memory.read().await.as_ref() {
+            Some(item) => self.overrides.active(&self.namespace, &item.config),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the namespace this cache manages.
+    pub(crate) fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Waits for an in-flight refresh, including its persistence write and
+    /// listener callbacks, and for any other listener delivery to finish.
+    pub(crate) async fn drain(&self) {
+        drop(self.refresh_lock.lock().await);
+        drop(self.notify_lock.lock().await);
+    }
+
+    /// Returns whether the value in memory was fetched from Apollo by this
+    /// process or is still within the cache TTL.
+    pub(crate) async fn is_current(&self) -> bool {
+        self.memory.read().await.as_ref().is_some_and(|item| {
+            self.fetched_from_server.load(Ordering::Acquire) || self.is_fresh(item)
+        })
+    }
+
+    /// Records an access through the client, postponing idle expiry.
+    pub(crate) fn touch(&self) {
+        self.last_access
+            .store(Utc::now().timestamp(), Ordering::Release);
+    }
+
+    /// Returns whether the namespace has no registered listeners and has not
+    /// been accessed for at least `timeout` seconds.
+    pub(crate) fn is_idle(&self, timeout: u64) -> bool {
+        let idle = Utc::now()
+            .timestamp()
+            .saturating_sub(self.last_access.load(Ordering::Acquire));
+        u64::try_from(idle).is_ok_and(|idle| idle >= timeout) && self.listeners.is_empty()
+    }
+
+    /// Unregisters every listener and gives up shared-cache polling
+    /// leadership, after the client stops tracking the namespace.
+    ///
+    /// Outstanding [`Subscription`] handles become inactive.
+    pub(crate) fn release(&self) {
+        self.listeners.clear();
+        self.placeholders
+            .forget(&self.client_config, &self.namespace);
+        #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+        self.release_leadership();
+    }
+
+    /// Deletes the persistent entry for the namespace.
+    ///
+    /// Waits for an in-flight refresh first, so the entry is not written back
+    /// after it is deleted. A missing entry is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the entry exists but cannot be removed.
+    pub(crate) async fn delete_persisted(&self) -> Result<(), Error> {
+        let _refresh_guard = self.refresh_lock.lock().await;
+        cfg_if! {
+            if #[cfg(not(target_arch = "wasm32"))] {
+                match tokio::fs::remove_file(&self.file_path).await {
+                    Ok(()) => {
+                        debug!("Deleted cache file {}", self.file_path.display());
+                        Ok(())
+                    }
+                    Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                    Err(error) => Err(Error::Io(error)),
+                }
+            } else {
+                if let Some(path) = &self.node_file_path {
+                    node::remove(path).map_err(|error| Error::Io(std::io::Error::other(error)))
+                } else {
+                    if remove_from_local_storage(&self.wasm_cache_key).is_none() {
+                        log::debug!("Unable to remove localStorage cache entry {}", self.wasm_cache_key);
+                    }
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Returns the configuration this namespace is fetched with.
+    pub(crate) fn client_config(&self) -> &ClientConfig {
+        &self.client_config
+    }
+
+    /// Returns where the namespace is persisted: a file path on native
+    /// targets and under Node.js, a `localStorage` key in browsers.
+    pub(crate) fn location(&self) -> String {
+        cfg_if::cfg_if! {
+            if #[cfg(target_arch = "wasm32")] {
+                self.node_file_path
+                    .clone()
+                    .unwrap_or_else(|| self.wasm_cache_key.clone())
+            } else {
+                self.file_path.display().to_string()
+            }
+        }
     }
 
     /// Returns the WASM cache key (wasm32 targets only).
@@ -887,13 +1448,14 @@ impl Cache {
     }
 }
 
-fn invoke_listener(
-    listener: &EventListener,
-    result: Result<crate::namespace::Namespace, crate::Error>,
-) {
-    let invocation = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| listener(result)));
-    if invocation.is_err() {
-        log::error!("Apollo configuration listener panicked");
+/// Delivers a notification to each notifier in order, isolating panics.
+fn notify(notification: &Notification<'_>, notifiers: &[Notifier]) {
+    for notifier in notifiers {
+        let invocation =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| notifier(notification)));
+        if invocation.is_err() {
+            log::error!("Apollo configuration listener panicked");
+        }
     }
 }
 
@@ -1026,6 +1588,23 @@ fn save_to_local_storage(key: &str, value: &str) -> Option<()> {
     Some(())
 }
 
+#[cfg(target_arch = "wasm32")]
+fn remove_from_local_storage(key: &str) -> Option<()> {
+    let global = js_sys::global();
+    let storage =
+        js_sys::Reflect::get(&global, &wasm_bindgen::JsValue::from_str("localStorage")).ok()?;
+    if storage.is_undefined() || storage.is_null() {
+        return None;
+    }
+    let remove_item_fn =
+        js_sys::Reflect::get(&storage, &wasm_bindgen::JsValue::from_str("removeItem")).ok()?;
+    if remove_item_fn.is_function() {
+        let args = js_sys::Array::of1(&wasm_bindgen::JsValue::from_str(key));
+        let _ = js_sys::Reflect::apply(&remove_item_fn.into(), &storage, &args).ok()?;
+    }
+    Some(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1137,6 +1716,54 @@ mod tests {
         }
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn java_layout_bootstraps_from_and_writes_java_cache_files() {
+        let temp_dir = TempDir::new("java_layout");
+        let unavailable = fixed_server(503, "unavailable");
+        let mut config = test_config(&unavailable, temp_dir.path());
+        config.cache_layout = Some(CacheLayout::Java);
+        let cache = Cache::new(config.clone(), "application", test_http_client());
+        assert_eq!(
+            cache.file_path,
+            temp_dir
+                .path()
+                .join("test-app")
+                .join("config-cache")
+                .join("test-app+default+application.properties")
+        );
+        tokio::fs::create_dir_all(cache.file_path.parent().unwrap())
+            .await
+            .unwrap();
+        tokio::fs::write(
+            &cache.file_path,
+            "#Persisted by DefaultConfig\nvalue=from java\n",
+        )
+        .await
+        .unwrap();
+        assert_eq!(cache.get_value().await.unwrap()["value"], "from java");
+        assert_eq!(unavailable.request_count(), 0);
+
+        let escaping = Cache::new(config, "../escape", test_http_client());
+        assert!(
+            escaping
+                .file_path
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("v2-")
+        );
+
+        let server = fixed_server(200, r#"{"value":"remote"}"#);
+        let mut config = test_config(&server, temp_dir.path());
+        config.cache_layout = Some(CacheLayout::Java);
+        let cache = Cache::new(config, "application", test_http_client());
+        cache.refresh().await.unwrap();
+        let written = tokio::fs::read_to_string(&cache.file_path).await.unwrap();
+        assert!(written.starts_with("#Persisted by apollo-rust-client\n"));
+        assert!(written.contains("\nvalue=remote\n"));
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     #[tokio::test]
     async fn rejects_http_errors_without_caching_them() {
@@ -1187,12 +1814,15 @@ mod tests {
             .unwrap();
         let errors = Arc::new(AtomicUsize::new(0));
         let errors_in_listener = errors.clone();
-        cache
-            .add_listener(Arc::new(move |result| {
-                if result.is_err() {
-                    errors_in_listener.fetch_add(1, AtomicOrdering::AcqRel);
-                }
-            }))
+        let _subscription = cache
+            .add_listener(
+                Arc::new(move |result| {
+                    if result.is_err() {
+                        errors_in_listener.fetch_add(1, AtomicOrdering::AcqRel);
+                    }
+                }),
+                ListenerOptions::default(),
+            )
             .await;
 
         assert_eq!(cache.get_value().await.unwrap()["value"], "stale");
@@ -1295,15 +1925,18 @@ mod tests {
         let errors = Arc::new(AtomicUsize::new(0));
         let changes_in_listener = changes.clone();
         let errors_in_listener = errors.clone();
-        cache
-            .add_listener(Arc::new(move |result| match result {
-                Ok(_) => {
-                    changes_in_listener.fetch_add(1, AtomicOrdering::AcqRel);
-                }
-                Err(_) => {
-                    errors_in_listener.fetch_add(1, AtomicOrdering::AcqRel);
-                }
-            }))
+        let _subscription = cache
+            .add_listener(
+                Arc::new(move |result| match result {
+                    Ok(_) => {
+                        changes_in_listener.fetch_add(1, AtomicOrdering::AcqRel);
+                    }
+                    Err(_) => {
+                        errors_in_listener.fetch_add(1, AtomicOrdering::AcqRel);
+                    }
+                }),
+                ListenerOptions::default(),
+            )
             .await;
         cache.get_value().await.unwrap();
         cache.refresh().await.unwrap();
@@ -1315,6 +1948,45 @@ mod tests {
         assert_eq!(errors.load(AtomicOrdering::Acquire), 1);
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn change_listeners_receive_one_diff_per_change() {
+        let server = TestHttpServer::new(Arc::new(|index, _| match index {
+            1 => MockResponse::json(200, r#"{"kept":"1","modified":"old","deleted":"x"}"#),
+            _ => MockResponse::json(200, r#"{"kept":"1","modified":"new","added":"y"}"#),
+        }));
+        let temp_dir = TempDir::new("change_listener_diff");
+        let cache = Cache::new(
+            test_config(&server, temp_dir.path()),
+            "application",
+            test_http_client(),
+        );
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let events_in_listener = events.clone();
+        let _subscription = cache
+            .subscribe(
+                crate::subscription::change_notifier(Arc::new(move |result| {
+                    events_in_listener.lock().unwrap().push(result.unwrap());
+                })),
+                ListenerOptions::default(),
+            )
+            .await;
+
+        cache.get_value().await.unwrap();
+        cache.refresh().await.unwrap();
+        cache.refresh().await.unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].changes().len(), 3);
+        let keys: Vec<_> = events[1].changed_keys().collect();
+        assert_eq!(keys, ["added", "deleted", "modified"]);
+        assert_eq!(
+            events[1].change("deleted").unwrap().change_type(),
+            crate::PropertyChangeType::Deleted
+        );
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     #[tokio::test]
     async fn authenticated_request_contains_required_headers() {
@@ -1398,6 +2070,56 @@ mod tests {
         assert_eq!(server.request_count(), 2);
     }
 
+    #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+    #[tokio::test]
+    async fn stale_reads_defer_to_the_shared_cache_leader() {
+        let server = fixed_server(200, r#"{"value":"fetched"}"#);
+        let temp_dir = TempDir::new("shared_cache_stale_reads");
+        let mut config = test_config(&server, temp_dir.path());
+        config.cache_ttl = Some(1);
+        config.shared_cache = Some(true);
+        let leader = Cache::new(config.clone(), "application", test_http_client());
+        let follower = Cache::new(config, "application", test_http_client());
+        assert_eq!(follower.get_value().await.unwrap()["value"], "fetched");
+
+        assert!(leader.should_poll());
+        leader
+            .write_to_file_cache(&CacheItem {
+                timestamp: Utc::now().timestamp(),
+                config: serde_json::json!({"value": "from leader"}),
+            })
+            .await
+            .unwrap();
+        follower.memory.write().await.as_mut().unwrap().timestamp -= 60;
+        assert_eq!(follower.get_value().await.unwrap()["value"], "fetched");
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while follower.get_value().await.unwrap()["value"] != "from leader" {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("stale read did not reload the leader's cache file");
+        assert_eq!(server.request_count(), 1);
+
+        // Within a refresh interval, later stale reads do not reload again.
+        follower.memory.write().await.as_mut().unwrap().timestamp -= 60;
+        leader
+            .write_to_file_cache(&CacheItem {
+                timestamp: Utc::now().timestamp(),
+                config: serde_json::json!({"value": "not reloaded"}),
+            })
+            .await
+            .unwrap();
+        follower.get_value().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(follower.get_value().await.unwrap()["value"], "from leader");
+
+        leader.release_leadership();
+        follower.shared_reload_timestamp.store(0, Ordering::Release);
+        follower.get_value().await.unwrap();
+        server.wait_for_requests(2).await;
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     #[tokio::test]
     async fn concurrent_refresh_callers_share_one_request() {
@@ -1457,12 +2179,15 @@ mod tests {
         );
         let errors = Arc::new(AtomicUsize::new(0));
         let listener_errors = errors.clone();
-        cache
-            .add_listener(Arc::new(move |result| {
-                if result.is_err() {
-                    listener_errors.fetch_add(1, AtomicOrdering::AcqRel);
-                }
-            }))
+        let _subscription = cache
+            .add_listener(
+                Arc::new(move |result| {
+                    if result.is_err() {
+                        listener_errors.fetch_add(1, AtomicOrdering::AcqRel);
+                    }
+                }),
+                ListenerOptions::default(),
+            )
             .await;
 
         assert!(cache.get_value().await.is_err());
@@ -1474,13 +2199,18 @@ mod tests {
     fn startup_cleanup_removes_only_cache_temp_files() {
         let temp_dir = TempDir::new("temp_cleanup");
         let stale = temp_dir.path().join("v2-entry.cache.json.1.2.tmp");
+        let stale_java = temp_dir
+            .path()
+            .join("app+default+application.properties.1.2.tmp");
         let unrelated = temp_dir.path().join("notes.tmp");
         std::fs::write(&stale, b"stale").unwrap();
+        std::fs::write(&stale_java, b"stale").unwrap();
         std::fs::write(&unrelated, b"keep").unwrap();
 
         cleanup_stale_temp_files(temp_dir.path());
 
         assert!(!stale.exists());
+        assert!(!stale_java.exists());
         assert!(unrelated.exists());
     }
 
@@ -1535,13 +2265,18 @@ mod tests {
         assert_eq!(cache.consecutive_failures.load(Ordering::Acquire), 1);
         assert!(cache.is_backing_off());
 
-        cache.next_allowed_refresh_timestamp.store(0, Ordering::Release);
+        cache
+            .next_allowed_refresh_timestamp
+            .store(0, Ordering::Release);
         assert!(!cache.is_backing_off());
 
         let res2 = cache.refresh().await;
         assert!(res2.is_ok());
         assert_eq!(cache.consecutive_failures.load(Ordering::Acquire), 0);
-        assert_eq!(cache.next_allowed_refresh_timestamp.load(Ordering::Acquire), 0);
+        assert_eq!(
+            cache.next_allowed_refresh_timestamp.load(Ordering::Acquire),
+            0
+        );
         assert!(!cache.is_backing_off());
     }
 }
@@ -0,0 +1,264 @@
+//! Node.js filesystem persistence for the WebAssembly build.
+//!
+//! Node.js has no `localStorage`, so cache entries are written with the
+//! built-in `fs` module instead. Files use the native naming scheme,
+//! `v2-{sha1(identity)}.cache.json` below `{cache_dir}/apollo-rust-client/config-cache`,
+//! and the same atomic temp-file-and-rename write as native targets. The
+//! directory comes from `ClientConfig::cache_dir`, then `APOLLO_CACHE_DIR`, then
+//! the operating system's temporary directory (`os.tmpdir()`), which may not
+//! survive a reboot.
+//!
+//! Built-in modules are loaded with `process.getBuiltinModule`, available from
+//! Node.js 20.16 and 22.3. The generated bindings may run as ES modules, where
+//! no `require` is in scope, so older runtimes keep the cache in memory only
+//! and log a warning once.
+//!
+//! Synchronous `fs` calls are used: cache entries are small, and they keep the
+//! single-threaded WASM code free of promise plumbing.
+
+use crate::client_config::ClientConfig;
+use js_sys::{Array, Function, Object, Reflect};
+use log::{debug, warn};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use wasm_bindgen::{JsCast, JsValue};
+
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Whether the missing `process.getBuiltinModule` has been reported.
+static UNSUPPORTED_RUNTIME_REPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Loads a Node.js built-in module through `process.getBuiltinModule`.
+///
+/// Returns `None` outside Node.js, and on Node.js releases before 20.16 and
+/// 22.3, which lack `getBuiltinModule`.
+fn builtin_module(name: &str) -> Option<JsValue> {
+    let process = Reflect::get(&js_sys::global(), &JsValue::from_str("process")).ok()?;
+    if !process.is_object() {
+        return None;
+    }
+    let Some(get_builtin_module) = method(&process, "getBuiltinModule") else {
+        if !UNSUPPORTED_RUNTIME_REPORTED.swap(true, Ordering::Relaxed) {
+            warn!(
+                "process.getBuiltinModule is unavailable; Node.js 20.16 or 22.3 is required \
+                 to persist the cache, so it is kept in memory only"
+            );
+        }
+        return None;
+    };
+    get_builtin_module
+        .call1(&process, &JsValue::from_str(name))
+        .ok()
+        .filter(JsValue::is_object)
+}
+
+fn method(target: &JsValue, name: &str) -> Option<Function> {
+    Reflect::get(target, &JsValue::from_str(name))
+        .ok()?
+        .dyn_into::<Function>()
+        .ok()
+}
+
+fn call(module: &JsValue, name: &str, args: &[JsValue]) -> Result<JsValue, JsValue> {
+    let function =
+        method(module, name).ok_or_else(|| JsValue::from_str(&format!("{name} is unavailable")))?;
+    Reflect::apply(&function, module, &args.iter().collect::<Array>())
+}
+
+fn describe(error: &JsValue) -> String {
+    Reflect::get(error, &JsValue::from_str("message"))
+        .ok()
+        .and_then(|message| message.as_string())
+        .unwrap_or_else(|| format!("{error:?}"))
+}
+
+fn error_code(error: &JsValue) -> Option<String> {
+    Reflect::get(error, &JsValue::from_str("code"))
+        .ok()
+        .and_then(|code| code.as_string())
+}
+
+/// Returns the cache directory, or `None` outside Node.js.
+pub(crate) fn cache_dir(client_config: &ClientConfig) -> Option<String> {
+    builtin_module("fs")?;
+    let base = client_config.cache_dir.clone().or_else(|| {
+        crate::client_config::node_env("APOLLO_CACHE_DIR")
+            .ok()
+            .flatten()
+    });
+    let base = if let Some(base) = base {
+        base
+    } else {
+        let os = builtin_module("os")?;
+        call(&os, "tmpdir", &[]).ok()?.as_string()?
+    };
+    Some(format!(
+        "{}/apollo-rust-client/config-cache",
+        base.trim_end_matches(['/', '\\'])
+    ))
+}
+
+/// Reads a cache file, returning `None` when it is missing or unreadable.
+pub(crate) fn read(path: &str) -> Option<String> {
+    let fs = builtin_module("fs")?;
+    match call(
+        &fs,
+        "readFileSync",
+        &[JsValue::from_str(path), JsValue::from_str("utf8")],
+    ) {
+        Ok(content) => content.as_string(),
+        Err(error) if error_code(&error).as_deref() == Some("ENOENT") => None,
+        Err(error) => {
+            warn!("Unable to read cache file {path}: {}", describe(&error));
+            None
+        }
+    }
+}
+
+/// Atomically replaces a cache file.
+///
+/// Each writer creates a unique temporary file with the exclusive `wx` flag in
+/// the destination directory, then renames it over the entry.
+///
+/// # Errors
+///
+/// Returns a description of the failing `fs` call.
+pub(crate) fn write(path: &str, content: &str) -> Result<(), String> {
+    let fs = builtin_module("fs").ok_or_else(|| "the fs module is unavailable".to_string())?;
+    if let Some((directory, _)) = path.rsplit_once('/') {
+        let options = Object::new();
+        Reflect::set(&options, &JsValue::from_str("recursive"), &JsValue::TRUE)
+            .map_err(|error| describe(&error))?;
+        call(
+            &fs,
+            "mkdirSync",
+            &[JsValue::from_str(directory), options.into()],
+        )
+        .map_err(|error| describe(&error))?;
+    }
+
+    let pid = Reflect::get(&js_sys::global(), &JsValue::from_str("process"))
+        .and_then(|process| Reflect::get(&process, &JsValue::from_str("pid")))
+        .ok()
+        .and_then(|pid| pid.as_f64())
+        .unwrap_or_default();
+    let options = Object::new();
+    Reflect::set(
+        &options,
+        &JsValue::from_str("flag"),
+        &JsValue::from_str("wx"),
+    )
+    .map_err(|error| describe(&error))?;
+    let mut attempt = 0_u8;
+    let temp_path = loop {
+        let counter = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let temp_path = format!("{path}.{pid}.{counter}.tmp");
+        match call(
+            &fs,
+            "writeFileSync",
+            &[
+                JsValue::from_str(&temp_path),
+                JsValue::from_str(content),
+                options.clone().into(),
+            ],
+        ) {
+            Ok(_) => break temp_path,
+            Err(error) if error_code(&error).as_deref() == Some("EEXIST") && attempt < 8 => {
+                attempt += 1;
+            }
+            Err(error) => return Err(describe(&error)),
+        }
+    };
+
+    if let Err(error) = call(
+        &fs,
+        "renameSync",
+        &[JsValue::from_str(&temp_path), JsValue::from_str(path)],
+    ) {
+        let _ = call(&fs, "unlinkSync", &[JsValue::from_str(&temp_path)]);
+        return Err(describe(&error));
+    }
+    debug!("Wrote cache file {path}");
+    Ok(())
+}
+
+/// Deletes a cache file. A missing file is not an error.
+///
+/// # Errors
+///
+/// Returns a description of the failing `fs` call.
+pub(crate) fn remove(path: &str) -> Result<(), String> {
+    let fs = builtin_module("fs").ok_or_else(|| "the fs module is unavailable".to_string())?;
+    match call(&fs, "unlinkSync", &[JsValue::from_str(path)]) {
+        Ok(_) => {
+            debug!("Deleted cache file {path}");
+            Ok(())
+        }
+        Err(error) if error_code(&error).as_deref() == Some("ENOENT") => Ok(()),
+        Err(error) => Err(describe(&error)),
+    }
+}
+
+/// Removes temporary files orphaned by interrupted writers.
+pub(crate) fn cleanup_stale_temp_files(directory: &str) {
+    let Some(fs) = builtin_module("fs") else {
+        return;
+    };
+    let entries = match call(&fs, "readdirSync", &[JsValue::from_str(directory)]) {
+        Ok(entries) => Array::from(&entries),
+        Err(error) if error_code(&error).as_deref() == Some("ENOENT") => return,
+        Err(error) => {
+            warn!(
+                "Unable to scan cache directory {directory} for stale temporary files: {}",
+                describe(&error)
+            );
+            return;
+        }
+    };
+    for name in entries.iter().filter_map(|entry| entry.as_string()) {
+        let is_temporary = std::path::Path::new(&name)
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("tmp"));
+        if name.starts_with("v2-") && is_temporary {
+            let path = format!("{directory}/{name}");
+            if let Err(error) = call(&fs, "unlinkSync", &[JsValue::from_str(&path)]) {
+                warn!(
+                    "Unable to remove stale cache temporary file {path}: {}",
+                    describe(&error)
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn writes_atomically_and_reads_back_under_node() {
+        let (Some(fs), Some(os)) = (builtin_module("fs"), builtin_module("os")) else {
+            return;
+        };
+        let temp_dir = call(&os, "tmpdir", &[]).unwrap().as_string().unwrap();
+        let directory = format!("{temp_dir}/apollo-rust-client-node-{}", js_sys::Date::now());
+        let path = format!("{directory}/v2-test.cache.json");
+        assert_eq!(read(&path), None);
+
+        write(&path, "first").unwrap();
+        write(&path, "second").unwrap();
+        assert_eq!(read(&path).as_deref(), Some("second"));
+        let entries =
+            Array::from(&call(&fs, "readdirSync", &[JsValue::from_str(&directory)]).unwrap());
+        assert_eq!(entries.length(), 1);
+
+        let options = Object::new();
+        Reflect::set(&options, &JsValue::from_str("recursive"), &JsValue::TRUE).unwrap();
+        call(
+            &fs,
+            "rmSync",
+            &[JsValue::from_str(&directory), options.into()],
+        )
+        .unwrap();
+    }
+}
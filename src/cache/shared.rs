@@ -0,0 +1,217 @@
+//! Cross-process cache sharing (native targets only).
+//!
+//! Processes that opt into shared-cache mode and use the same cache directory
+//! cooperate per namespace:
+//!
+//! - **Leader election**: an advisory lock on `{cache file}.lock` elects the one
+//!   process that polls Apollo for the namespace. The lock is released when
+//!   the holder stops polling or exits, so another process takes over on its
+//!   next refresh cycle.
+//! - **File watching**: every process watches the cache directory (inotify on
+//!   Linux) and feeds entries written by siblings through the regular
+//!   memory-replacement path, which notifies listeners on change.
+
+use super::Cache;
+use log::{debug, warn};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    fs::{File, TryLockError},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::sync::{RwLock, mpsc};
+
+/// Returns the advisory lock file guarding polling for a cache file.
+pub(crate) fn lock_path(file_path: &Path) -> PathBuf {
+    let mut file_name = file_path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".lock");
+    file_path.with_file_name(file_name)
+}
+
+/// Attempts to take the polling lock without blocking.
+///
+/// Returns `Ok(None)` while another process holds the lock. The lock lasts as
+/// long as the returned file stays open.
+pub(crate) fn try_lock(lock_path: &Path) -> std::io::Result<Option<File>> {
+    if let Some(parent) = lock_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path)?;
+    match file.try_lock() {
+        Ok(()) => Ok(Some(file)),
+        Err(TryLockError::WouldBlock) => Ok(None),
+        Err(TryLockError::Error(error)) => Err(error),
+    }
+}
+
+/// Watches a cache directory and reloads caches whose files change on disk.
+///
+/// Dropping the watcher stops both the operating-system watch and the reload
+/// task.
+pub(crate) struct CacheWatcher {
+    _watcher: RecommendedWatcher,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl CacheWatcher {
+    /// Starts watching `directory` for entries belonging to `namespaces`.
+    ///
+    /// Every cache file lives directly in `directory`, so events are matched
+    /// by file name: the paths `notify` reports may spell the directory
+    /// differently, for example relative or through a symlink.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created or watched.
+    pub(crate) fn spawn(
+        directory: &Path,
+        namespaces: Arc<RwLock<HashMap<String, Arc<Cache>>>>,
+    ) -> notify::Result<Self> {
+        std::fs::create_dir_all(directory)?;
+        let (sender, mut receiver) = mpsc::unbounded_channel::<OsString>();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<Event>| match event {
+                Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+                    for file_name in event.paths.iter().filter_map(|path| path.file_name()) {
+                        let _ = sender.send(file_name.to_os_string());
+                    }
+                }
+                Ok(_) => {}
+                Err(error) => warn!("Shared cache watcher error: {error}"),
+            })?;
+        watcher.watch(directory, RecursiveMode::NonRecursive)?;
+
+        let task = tokio::spawn(async move {
+            while let Some(file_name) = receiver.recv().await {
+                let cache = namespaces
+                    .read()
+                    .await
+                    .values()
+                    .find(|cache| cache.file_path.file_name() == Some(file_name.as_os_str()))
+                    .cloned();
+                if let Some(cache) = cache {
+                    debug!(
+                        "Cache file {} changed on disk; reloading namespace {}",
+                        cache.file_path.display(),
+                        cache.namespace
+                    );
+                    cache.reload_from_disk().await;
+                }
+            }
+        });
+
+        Ok(Self {
+            _watcher: watcher,
+            task,
+        })
+    }
+}
+
+impl Drop for CacheWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ListenerOptions, TempDir, client_config::ClientConfig};
+    use std::{sync::Mutex, time::Duration};
+
+    fn shared_config(cache_dir: &Path) -> ClientConfig {
+        ClientConfig::builder("test-app", "http://127.0.0.1:9")
+            .cache_dir(cache_dir.to_string_lossy())
+            .shared_cache(true)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn one_process_holds_the_polling_lock_until_it_releases_it() {
+        let temp_dir = TempDir::new("shared_cache_leadership");
+        let config = shared_config(temp_dir.path());
+        let first = Cache::new(config.clone(), "application", reqwest::Client::new());
+        let second = Cache::new(config, "application", reqwest::Client::new());
+
+        assert!(first.should_poll());
+        assert!(first.should_poll());
+        assert!(!second.should_poll());
+        first.release_leadership();
+        assert!(second.should_poll());
+        assert!(!first.should_poll());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn sibling_writes_are_loaded_and_reported_to_listeners() {
+        let temp_dir = TempDir::new("shared_cache_watcher");
+        let config = shared_config(temp_dir.path());
+        let writer = Cache::new(config.clone(), "application", reqwest::Client::new());
+        let reader = Arc::new(Cache::new(
+            config.clone(),
+            "application",
+            reqwest::Client::new(),
+        ));
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_in_listener = received.clone();
+        let _subscription = reader
+            .add_listener(
+                Arc::new(move |result| {
+                    if let Ok(crate::namespace::Namespace::Properties(properties)) = result {
+                        received_in_listener
+                            .lock()
+                            .unwrap()
+                            .push(properties.get_string("value"));
+                    }
+                }),
+                ListenerOptions::default(),
+            )
+            .await;
+        let namespaces = Arc::new(RwLock::new(HashMap::from([(
+            "application".to_string(),
+            reader.clone(),
+        )])));
+        // Watch the directory through a symlink, whose event paths differ
+        // from the cache file paths.
+        let watched = temp_dir.path().with_extension("link");
+        let _ = std::fs::remove_file(&watched);
+        std::fs::create_dir_all(config.get_cache_dir()).unwrap();
+        std::os::unix::fs::symlink(config.get_cache_dir(), &watched).unwrap();
+        let _watcher = CacheWatcher::spawn(&watched, namespaces).unwrap();
+
+        writer
+            .write_to_file_cache(&super::super::CacheItem {
+                timestamp: chrono::Utc::now().timestamp(),
+                config: serde_json::json!({"value": "from sibling"}),
+            })
+            .await
+            .unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while received.lock().unwrap().is_empty() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("sibling cache write was not observed");
+        assert_eq!(
+            received
+                .lock()
+                .unwrap()
+                .first()
+                .cloned()
+                .flatten()
+                .as_deref(),
+            Some("from sibling")
+        );
+        assert_eq!(reader.get_value().await.unwrap()["value"], "from sibling");
+        std::fs::remove_file(&watched).unwrap();
+    }
+}
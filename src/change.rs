@@ -0,0 +1,392 @@
+//! Key-level change events.
+//!
+//! When a namespace changes, the cache computes one [`ConfigChangeEvent`]
+//! describing which keys were added, modified, or deleted, and shares it with
+//! every change listener. Keys depend on the namespace format:
+//!
+//! - **Properties** (and other flat formats): the property keys themselves.
+//! - **JSON and YAML**: [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901)
+//!   paths to each leaf of the document, such as `/database/hosts/0`. Empty
+//!   objects and arrays are leaves, and a scalar document has the path `""`.
+//!   Content that does not parse is compared as the raw `content` key.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use apollo_rust_client::{Client, PropertyChangeType, client_config::ClientConfig};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = ClientConfig::builder("my-app", "http://apollo-server:8080").build()?;
+//! let client = Client::new(config)?;
+//!
+//! let subscription = client
+//!     .add_change_listener(
+//!         "application",
+//!         Arc::new(|result| {
+//!             let Ok(event) = result else { return };
+//!             for (key, change) in event.changes() {
+//!                 if change.change_type() == PropertyChangeType::Deleted {
+//!                     println!("{key} was removed from {}", event.namespace());
+//!                 }
+//!             }
+//!         }),
+//!     )
+//!     .await;
+//! # drop(subscription);
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// How a key changed between two versions of a namespace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PropertyChangeType {
+    /// The key is new.
+    Added,
+    /// The key exists in both versions with different values.
+    Modified,
+    /// The key was removed.
+    Deleted,
+}
+
+/// The change to a single key.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigChange {
+    old_value: Option<Value>,
+    new_value: Option<Value>,
+    change_type: PropertyChangeType,
+}
+
+impl ConfigChange {
+    /// Returns the value before the change, or `None` if the key was added.
+    #[must_use]
+    pub fn old_value(&self) -> Option<&Value> {
+        self.old_value.as_ref()
+    }
+
+    /// Returns the value after the change, or `None` if the key was deleted.
+    #[must_use]
+    pub fn new_value(&self) -> Option<&Value> {
+        self.new_value.as_ref()
+    }
+
+    /// Returns how the key changed.
+    #[must_use]
+    pub fn change_type(&self) -> PropertyChangeType {
+        self.change_type
+    }
+}
+
+/// The keys that changed in one namespace update.
+///
+/// The first value a listener sees, whether from the initial load or a replay,
+/// reports every key as [`PropertyChangeType::Added`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ConfigChangeEvent {
+    namespace: String,
+    changes: BTreeMap<String, ConfigChange>,
+}
+
+impl ConfigChangeEvent {
+    /// Returns the namespace that changed.
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Returns the changes keyed by property key or JSON Pointer path.
+    #[must_use]
+    pub fn changes(&self) -> &BTreeMap<String, ConfigChange> {
+        &self.changes
+    }
+
+    /// Returns the changed keys in sorted order.
+    pub fn changed_keys(&self) -> impl Iterator<Item = &str> {
+        self.changes.keys().map(String::as_str)
+    }
+
+    /// Returns the change to `key`, if it changed.
+    #[must_use]
+    pub fn change(&self, key: &str) -> Option<&ConfigChange> {
+        self.changes.get(key)
+    }
+
+    /// Returns whether `key` changed.
+    #[must_use]
+    pub fn is_changed(&self, key: &str) -> bool {
+        self.changes.contains_key(key)
+    }
+
+    /// Returns whether no key changed, as when only JSON formatting differs.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl From<ConfigChangeEvent> for wasm_bindgen::JsValue {
+    fn from(event: ConfigChangeEvent) -> Self {
+        match event.serialize(&serde_wasm_bindgen::Serializer::json_compatible()) {
+            Ok(value) => value,
+            Err(error) => {
+                log::error!("Unable to serialize change event for JavaScript: {error}");
+                wasm_bindgen::JsValue::NULL
+            }
+        }
+    }
+}
+
+/// A typed change to one key, delivered by [`crate::Client::on_key_change`].
+///
+/// Values are parsed with the [`crate::namespace::properties::Properties::get_property`]
+/// rules; a value that is absent, not a scalar, or does not parse as `T` is
+/// `None`, so check [`KeyChange::change_type`] to tell deletions apart.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyChange<T> {
+    /// The property key or JSON Pointer path that changed.
+    pub key: String,
+    /// The parsed value before the change.
+    pub old_value: Option<T>,
+    /// The parsed value after the change.
+    pub new_value: Option<T>,
+    /// How the key changed.
+    pub change_type: PropertyChangeType,
+}
+
+impl ConfigChange {
+    pub(crate) fn parse<T: std::str::FromStr>(&self, key: &str) -> KeyChange<T> {
+        KeyChange {
+            key: key.to_string(),
+            old_value: self
+                .old_value
+                .as_ref()
+                .and_then(crate::namespace::properties::parse_scalar),
+            new_value: self
+                .new_value
+                .as_ref()
+                .and_then(crate::namespace::properties::parse_scalar),
+            change_type: self.change_type,
+        }
+    }
+}
+
+/// Returns whether `key` is `key_or_prefix` or lies below it.
+///
+/// A prefix matches whole segments: `db.pool` matches `db.pool.size` and
+/// `/db` matches `/db/port`, but neither matches `db.poolSize`. A prefix that
+/// already ends in `.` or `/`, and the empty prefix, match every key below it.
+pub(crate) fn key_matches(key: &str, key_or_prefix: &str) -> bool {
+    let Some(rest) = key.strip_prefix(key_or_prefix) else {
+        return false;
+    };
+    rest.is_empty()
+        || key_or_prefix.is_empty()
+        || key_or_prefix.ends_with(['.', '/'])
+        || rest.starts_with(['.', '/'])
+}
+
+/// Computes the changes between two raw cached values of `namespace`.
+pub(crate) fn diff(namespace: &str, old: Option<&Value>, new: &Value) -> ConfigChangeEvent {
+    let old = old.map(|old| flatten(namespace, old)).unwrap_or_default();
+    let mut new = flatten(namespace, new);
+
+    let mut changes = BTreeMap::new();
+    for (key, old_value) in old {
+        match new.remove(&key) {
+            Some(new_value) if new_value == old_value => {}
+            Some(new_value) => {
+                changes.insert(
+                    key,
+                    ConfigChange {
+                        old_value: Some(old_value),
+                        new_value: Some(new_value),
+                        change_type: PropertyChangeType::Modified,
+                    },
+                );
+            }
+            None => {
+                changes.insert(
+                    key,
+                    ConfigChange {
+                        old_value: Some(old_value),
+                        new_value: None,
+                        change_type: PropertyChangeType::Deleted,
+                    },
+                );
+            }
+        }
+    }
+    for (key, new_value) in new {
+        changes.insert(
+            key,
+            ConfigChange {
+                old_value: None,
+                new_value: Some(new_value),
+                change_type: PropertyChangeType::Added,
+            },
+        );
+    }
+
+    ConfigChangeEvent {
+        namespace: namespace.to_string(),
+        changes,
+    }
+}
+
+/// Maps every key of a raw cached value to its value.
+pub(crate) fn flatten(namespace: &str, config: &Value) -> BTreeMap<String, Value> {
+    let mut entries = BTreeMap::new();
+    if let Some(document) = crate::namespace::document_tree(namespace, config) {
+        flatten_document(String::new(), document, &mut entries);
+    } else if let Value::Object(map) = config {
+        entries.extend(map.iter().map(|(key, value)| (key.clone(), value.clone())));
+    }
+    entries
+}
+
+/// Inserts every leaf of `value` below `pointer`, keyed by JSON Pointer path.
+pub(crate) fn flatten_document(
+    pointer: String,
+    value: Value,
+    entries: &mut BTreeMap<String, Value>,
+) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let escaped = key.replace('~', "~0").replace('/', "~1");
+                flatten_document(format!("{pointer}/{escaped}"), value, entries);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (index, value) in items.into_iter().enumerate() {
+                flatten_document(format!("{pointer}/{index}"), value, entries);
+            }
+        }
+        value => {
+            entries.insert(pointer, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn types(event: &ConfigChangeEvent) -> Vec<(&str, PropertyChangeType)> {
+        event
+            .changes()
+            .iter()
+            .map(|(key, change)| (key.as_str(), change.change_type()))
+            .collect()
+    }
+
+    #[test]
+    fn properties_report_added_modified_and_deleted_keys() {
+        let old = json!({"kept": "1", "modified": "old", "deleted": "gone"});
+        let new = json!({"kept": "1", "modified": "new", "added": "here"});
+        let event = diff("application", Some(&old), &new);
+
+        assert_eq!(event.namespace(), "application");
+        assert_eq!(
+            types(&event),
+            vec![
+                ("added", PropertyChangeType::Added),
+                ("deleted", PropertyChangeType::Deleted),
+                ("modified", PropertyChangeType::Modified),
+            ]
+        );
+        let modified = event.change("modified").unwrap();
+        assert_eq!(modified.old_value(), Some(&json!("old")));
+        assert_eq!(modified.new_value(), Some(&json!("new")));
+        assert_eq!(event.change("deleted").unwrap().new_value(), None);
+        assert!(!event.is_changed("kept"));
+
+        let initial = diff("application", None, &new);
+        assert!(
+            initial
+                .changes()
+                .values()
+                .all(|change| change.change_type() == PropertyChangeType::Added)
+        );
+        assert_eq!(initial.changes().len(), 3);
+    }
+
+    #[test]
+    fn documents_are_compared_by_json_pointer() {
+        let old = json!({"content": r#"{"db":{"hosts":["a","b"],"port":5432},"a/b":1,"tags":{}}"#});
+        let new =
+            json!({"content": r#"{"db":{"hosts":["a"],"port":6432},"a/b":1,"tags":{"x":true}}"#});
+        let event = diff("settings.json", Some(&old), &new);
+        assert_eq!(
+            types(&event),
+            vec![
+                ("/db/hosts/1", PropertyChangeType::Deleted),
+                ("/db/port", PropertyChangeType::Modified),
+                ("/tags", PropertyChangeType::Deleted),
+                ("/tags/x", PropertyChangeType::Added),
+            ]
+        );
+
+        let formatting_only = json!({"content": "{ \"db\": {\"hosts\": [\"a\"], \"port\": 6432}, \"a/b\": 1, \"tags\": {\"x\": true} }"});
+        assert!(diff("settings.json", Some(&new), &formatting_only).is_empty());
+        assert!(diff("settings.json", None, &new).is_changed("/a~1b"));
+    }
+
+    #[test]
+    fn key_prefixes_match_whole_segments_and_values_parse_like_properties() {
+        assert!(key_matches("db.pool.size", "db.pool.size"));
+        assert!(key_matches("db.pool.size", "db.pool"));
+        assert!(key_matches("db.pool.size", "db."));
+        assert!(key_matches("/db/port", "/db"));
+        assert!(key_matches("/db/port", ""));
+        assert!(!key_matches("db.poolSize", "db.pool"));
+        assert!(!key_matches("/dbx", "/db"));
+
+        let old = json!({"content": r#"{"db":{"port":"5432","pool":{"size":10}}}"#});
+        let new = json!({"content": r#"{"db":{"port":"not a number"}}"#});
+        let event = diff("settings.json", Some(&old), &new);
+        let port = event.change("/db/port").unwrap().parse::<u16>("/db/port");
+        assert_eq!(port.old_value, Some(5432));
+        assert_eq!(port.new_value, None);
+        assert_eq!(port.change_type, PropertyChangeType::Modified);
+        let size = event
+            .change("/db/pool/size")
+            .unwrap()
+            .parse::<u32>("/db/pool/size");
+        assert_eq!((size.old_value, size.new_value), (Some(10), None));
+        assert_eq!(size.change_type, PropertyChangeType::Deleted);
+    }
+
+    #[test]
+    fn yaml_documents_use_the_same_paths_and_fall_back_to_raw_content() {
+        let old = json!({"content": "server:\n  port: 80\n"});
+        let new = json!({"content": "server:\n  port: 8080\n  debug: yes\n"});
+        let event = diff("config.yaml", Some(&old), &new);
+        assert_eq!(
+            event.change("/server/port").unwrap().new_value(),
+            Some(&json!(8080))
+        );
+        assert_eq!(
+            event.change("/server/debug").unwrap().new_value(),
+            Some(&json!(true))
+        );
+
+        let invalid = json!({"content": "server: [unclosed"});
+        let event = diff("config.yaml", Some(&old), &invalid);
+        assert_eq!(
+            types(&event),
+            vec![
+                ("/server/port", PropertyChangeType::Deleted),
+                ("content", PropertyChangeType::Added),
+            ]
+        );
+    }
+}
@@ -22,12 +22,18 @@
 //! - `APOLLO_CACHE_TTL`: Cache time-to-live in seconds
 //! - `APOLLO_REFRESH_INTERVAL`: Periodic refresh interval in seconds
 //! - `APOLLO_REQUEST_TIMEOUT`: Complete request timeout in seconds
+//! - `APOLLO_NAMESPACE_IDLE_TIMEOUT`: Idle time in seconds after which unused namespaces are forgotten
+//! - `APOLLO_OVERRIDE_ENV_PREFIX`: Prefix of environment variables that override Apollo values
+//! - `APOLLO_OVERRIDE_FILE`: Watched file of local value overrides (native only)
+//! - `APOLLO_RESOLVE_PLACEHOLDERS`: Whether `${key:default}` placeholders in Properties values are resolved
 //! - `APOLLO_ALLOW_INSECURE_HTTPS`: Whether to allow insecure HTTPS connections
+//! - `APOLLO_CACHE_LAYOUT`: Native cache file layout, `native` or `java` (native only)
+//! - `APOLLO_SHARED_CACHE`: Whether processes sharing a cache directory cooperate (native only)
 //!
 //! # Platform Support
 //!
 //! - **Native Rust**: Full feature set including file caching and environment variable support
-//! - **WebAssembly**: Optimized for browser environments with persistent localStorage caching (and Node.js in-memory fallback)
+//! - **WebAssembly**: Persistent localStorage caching in browsers and `fs` file caching under Node.js
 //!
 //! # Examples
 //!
@@ -47,8 +53,17 @@
 //!     allow_insecure_https: None,
 //!     #[cfg(not(target_arch = "wasm32"))]
 //!     http_client: None,
+//!     #[cfg(not(target_arch = "wasm32"))]
+//!     cache_layout: None,
+//!     #[cfg(not(target_arch = "wasm32"))]
+//!     shared_cache: None,
 //!     refresh_interval: Some(30),
 //!     request_timeout: Some(10),
+//!     namespace_idle_timeout: None,
+//!     override_env_prefix: None,
+//!     resolve_placeholders: None,
+//!     #[cfg(not(target_arch = "wasm32"))]
+//!     override_file: None,
 //!     cache_ttl: None,
 //! };
 //! ```
@@ -148,6 +163,12 @@ pub enum Error {
 /// - `cache_ttl`: Cache freshness lifetime (`0` means always revalidate)
 /// - `refresh_interval`: Periodic polling interval
 /// - `request_timeout`: Complete request and response-body timeout
+/// - `namespace_idle_timeout`: Idle time after which unused namespaces are forgotten
+/// - `override_env_prefix`: Prefix of environment variables that override Apollo values
+/// - `override_file`: File of local value overrides (native targets only)
+/// - `resolve_placeholders`: Whether `${key:default}` placeholders in Properties values are resolved
+/// - `cache_layout`: Native cache file layout (native targets only)
+/// - `shared_cache`: Cross-process cache sharing (native targets only)
 ///
 /// # Examples
 ///
@@ -167,8 +188,17 @@ pub enum Error {
 ///     allow_insecure_https: None,
 ///     #[cfg(not(target_arch = "wasm32"))]
 ///     http_client: None,
+///     #[cfg(not(target_arch = "wasm32"))]
+///     cache_layout: None,
+///     #[cfg(not(target_arch = "wasm32"))]
+///     shared_cache: None,
 ///     refresh_interval: Some(30),
 ///     request_timeout: Some(10),
+///     namespace_idle_timeout: None,
+///     override_env_prefix: None,
+///     resolve_placeholders: None,
+///     #[cfg(not(target_arch = "wasm32"))]
+///     override_file: None,
 ///     cache_ttl: None,
 /// };
 /// ```
@@ -189,8 +219,17 @@ pub enum Error {
 ///     allow_insecure_https: Some(true), // Allow self-signed certificates
 ///     #[cfg(not(target_arch = "wasm32"))]
 ///     http_client: None,
+///     #[cfg(not(target_arch = "wasm32"))]
+///     cache_layout: None,
+///     #[cfg(not(target_arch = "wasm32"))]
+///     shared_cache: None,
 ///     refresh_interval: Some(30),
 ///     request_timeout: Some(10),
+///     namespace_idle_timeout: None,
+///     override_env_prefix: None,
+///     resolve_placeholders: None,
+///     #[cfg(not(target_arch = "wasm32"))]
+///     override_file: None,
 ///     cache_ttl: None,
 /// };
 /// ```
@@ -213,7 +252,10 @@ pub struct ClientConfig {
     ///
     /// On native Rust targets, this specifies where configuration files should
     /// be cached locally. If `None`, a platform-standard application cache directory is used.
-    /// On WebAssembly targets this value is ignored because filesystem access is unavailable.
+    /// Under Node.js 20.16 or 22.3 and later, WebAssembly builds persist files below this
+    /// directory, falling back to `APOLLO_CACHE_DIR` and then the operating system's
+    /// temporary directory (`os.tmpdir()`), which may be cleared on reboot.
+    /// Browsers ignore it and use `localStorage`.
     pub cache_dir: Option<String>,
 
     /// The Apollo configuration server URL.
@@ -277,12 +319,162 @@ pub struct ClientConfig {
     /// custom native HTTP client. A value of zero is rejected during validation.
     pub request_timeout: Option<u64>,
 
+    /// Idle time in seconds after which an unused namespace is forgotten.
+    ///
+    /// A namespace is idle when it has not been read, refreshed on request, or
+    /// subscribed to for this long and has no registered listeners. Background
+    /// polling then stops and its memory is released, as with
+    /// `Client::forget`. `None`, the default, keeps namespaces until they are
+    /// forgotten explicitly. Zero is rejected during validation.
+    pub namespace_idle_timeout: Option<u64>,
+
+    /// Prefix of environment variables that override Apollo values.
+    ///
+    /// A variable named `{prefix}{NAMESPACE}__{KEY}` overrides an existing
+    /// key, where the namespace name and the property key or JSON Pointer
+    /// path are upper-cased with every other character replaced by `_`. For
+    /// example, with the prefix `APOLLO_OVERRIDE_`, the variable
+    /// `APOLLO_OVERRIDE_APPLICATION__DB_POOL_SIZE` overrides `db.pool.size` in
+    /// `application`. `None`, the default, ignores the environment.
+    pub override_env_prefix: Option<String>,
+
+    /// Path of a YAML or JSON file of local overrides (native targets only).
+    ///
+    /// The file maps namespace names to maps of property keys or JSON Pointer
+    /// paths to values. With the `override-watch` feature it is watched, so
+    /// edits take effect and notify listeners without a restart; otherwise it
+    /// is read once, when the client is created. A missing file holds no
+    /// overrides.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[wasm_bindgen(skip)]
+    pub override_file: Option<String>,
+
+    /// Whether to resolve `${key}` and `${key:default}` placeholders in the
+    /// values of Properties namespaces.
+    ///
+    /// Placeholders reference keys of the same namespace, of other loaded
+    /// Properties namespaces, or environment variables. See
+    /// `crate::placeholders` for the lookup order. `None`, the default,
+    /// serves values verbatim.
+    pub resolve_placeholders: Option<bool>,
+
     /// A pre-configured `reqwest::Client` (native targets only) to allow custom HTTP pools, proxies, headers, or tracers.
     ///
     /// If not specified, defaults to standard client construction.
     #[cfg(not(target_arch = "wasm32"))]
     #[wasm_bindgen(skip)]
     pub http_client: Option<reqwest::Client>,
+
+    /// The on-disk layout of native persistent cache files.
+    ///
+    /// `None` and [`CacheLayout::Native`] keep the versioned, hashed files of
+    /// this crate. [`CacheLayout::Java`] reads and writes the Java client's
+    /// properties files so both clients can share one cache directory.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[wasm_bindgen(skip)]
+    pub cache_layout: Option<CacheLayout>,
+
+    /// Whether processes sharing one cache directory cooperate (native targets only).
+    ///
+    /// When `Some(true)`, background polling elects one process per namespace
+    /// through an advisory lock on a `.lock` file next to the cache entry. The
+    /// other processes watch the cache directory and load entries written by the
+    /// elected process instead of polling Apollo themselves. Leadership moves
+    /// to another process within one refresh interval after the holder stops.
+    ///
+    /// Requires the `shared-cache` feature; without it, `Some(true)` fails
+    /// validation.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[wasm_bindgen(skip)]
+    pub shared_cache: Option<bool>,
+}
+
+/// The on-disk layout used for native persistent cache files.
+///
+/// # Examples
+///
+/// ```rust
+/// use apollo_rust_client::client_config::CacheLayout;
+///
+/// assert_eq!("java".parse::<CacheLayout>(), Ok(CacheLayout::Java));
+/// assert_eq!(CacheLayout::default(), CacheLayout::Native);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CacheLayout {
+    /// Versioned `v2-{sha1(identity)}.cache.json` files below
+    /// `{cache_dir}/apollo-rust-client/config-cache`.
+    ///
+    /// The hashed identity isolates server, app, cluster, namespace, IP, and label.
+    #[default]
+    Native,
+
+    /// The Java client's `{appId}+{cluster}+{namespace}.properties` files below
+    /// `{cache_dir}/{appId}/config-cache`, or `/opt/data/{appId}/config-cache`
+    /// (`C:\opt\data\{appId}\config-cache` on Windows) without a `cache_dir`.
+    ///
+    /// Like the Java client, this layout does not distinguish servers, IPs, or
+    /// labels, and the file modification time serves as the cache timestamp.
+    Java,
+}
+
+impl std::str::FromStr for CacheLayout {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "native" => Ok(Self::Native),
+            "java" => Ok(Self::Java),
+            _ => Err("expected \"native\" or \"java\"".to_string()),
+        }
+    }
+}
+
+/// Apollo coordinates for namespaces published under another app id or
+/// cluster, used with `Client::with_coordinates`.
+///
+/// The cluster defaults to the client's cluster. The secret defaults to the
+/// client's secret when the app id is the client's own, and to none otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// use apollo_rust_client::client_config::Coordinates;
+///
+/// let coordinates = Coordinates::new("platform-app")
+///     .cluster("beijing")
+///     .secret("platform-access-key");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Coordinates {
+    app_id: String,
+    cluster: Option<String>,
+    secret: Option<String>,
+}
+
+impl Coordinates {
+    /// Addresses namespaces published by `app_id`.
+    #[must_use]
+    pub fn new(app_id: impl Into<String>) -> Self {
+        Self {
+            app_id: app_id.into(),
+            cluster: None,
+            secret: None,
+        }
+    }
+
+    /// Sets the Apollo cluster.
+    #[must_use]
+    pub fn cluster(mut self, cluster: impl Into<String>) -> Self {
+        self.cluster = Some(cluster.into());
+        self
+    }
+
+    /// Sets the access-key secret of the app.
+    #[must_use]
+    pub fn secret(mut self, secret: impl Into<String>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
 }
 
 /// Builder for a validated [`ClientConfig`].
@@ -358,6 +550,38 @@ impl ClientConfigBuilder {
         self
     }
 
+    /// Forgets namespaces that stay unused for `seconds`.
+    #[must_use]
+    pub fn namespace_idle_timeout(mut self, seconds: u64) -> Self {
+        self.config.namespace_idle_timeout = Some(seconds);
+        self
+    }
+
+    /// Reads value overrides from environment variables starting with `prefix`.
+    #[must_use]
+    pub fn override_env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.config.override_env_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Reads value overrides from a watched YAML or JSON file.
+    ///
+    /// The file is read when the client is created; changes are applied
+    /// only if the client is created inside a Tokio runtime.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[must_use]
+    pub fn override_file(mut self, path: impl Into<String>) -> Self {
+        self.config.override_file = Some(path.into());
+        self
+    }
+
+    /// Resolves `${key:default}` placeholders in Properties values.
+    #[must_use]
+    pub fn resolve_placeholders(mut self, enabled: bool) -> Self {
+        self.config.resolve_placeholders = Some(enabled);
+        self
+    }
+
     /// Supplies a custom native HTTP client.
     #[cfg(not(target_arch = "wasm32"))]
     #[must_use]
@@ -366,6 +590,24 @@ impl ClientConfigBuilder {
         self
     }
 
+    /// Selects the on-disk layout of native persistent cache files.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[must_use]
+    pub fn cache_layout(mut self, layout: CacheLayout) -> Self {
+        self.config.cache_layout = Some(layout);
+        self
+    }
+
+    /// Enables or disables cooperation with other processes sharing the cache directory.
+    ///
+    /// Enabling it requires the `shared-cache` feature.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[must_use]
+    pub fn shared_cache(mut self, enabled: bool) -> Self {
+        self.config.shared_cache = Some(enabled);
+        self
+    }
+
     /// Validates and returns the completed configuration.
     ///
     /// # Errors
@@ -398,8 +640,17 @@ impl ClientConfig {
                 cache_ttl: Some(DEFAULT_CACHE_TTL_SECONDS),
                 refresh_interval: Some(DEFAULT_REFRESH_INTERVAL_SECONDS),
                 request_timeout: Some(DEFAULT_REQUEST_TIMEOUT_SECONDS),
+                namespace_idle_timeout: None,
+                override_env_prefix: None,
+                resolve_placeholders: None,
+                #[cfg(not(target_arch = "wasm32"))]
+                override_file: None,
                 #[cfg(not(target_arch = "wasm32"))]
                 http_client: None,
+                #[cfg(not(target_arch = "wasm32"))]
+                cache_layout: None,
+                #[cfg(not(target_arch = "wasm32"))]
+                shared_cache: None,
             },
         }
     }
@@ -434,6 +685,14 @@ impl ClientConfig {
                 reason: "base URL must not contain a query or fragment".to_string(),
             });
         }
+        #[cfg(all(not(target_arch = "wasm32"), not(feature = "shared-cache")))]
+        if self.shared_cache == Some(true) {
+            return Err(Error::InvalidValue {
+                name: "shared_cache".to_string(),
+                value: "true".to_string(),
+                reason: "requires the shared-cache feature".to_string(),
+            });
+        }
         if self.refresh_interval == Some(0) {
             return Err(Error::InvalidValue {
                 name: "refresh_interval".to_string(),
@@ -448,6 +707,16 @@ impl ClientConfig {
                 reason: "value must be greater than zero".to_string(),
             });
         }
+        if self.namespace_idle_timeout == Some(0) {
+            return Err(Error::InvalidValue {
+                name: "namespace_idle_timeout".to_string(),
+                value: "0".to_string(),
+                reason: "value must be greater than zero".to_string(),
+            });
+        }
+        if let Some(prefix) = &self.override_env_prefix {
+            validate_nonempty("override_env_prefix", prefix)?;
+        }
         Ok(())
     }
 
@@ -470,6 +739,27 @@ impl ClientConfig {
         self.request_timeout
             .unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECONDS)
     }
+
+    /// Returns a copy of this configuration addressing `coordinates`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidValue`] when the app id or cluster is empty.
+    pub(crate) fn with_coordinates(&self, coordinates: Coordinates) -> Result<Self, Error> {
+        let mut config = self.clone();
+        if coordinates.app_id != self.app_id {
+            config.secret = None;
+        }
+        config.app_id = coordinates.app_id;
+        if let Some(cluster) = coordinates.cluster {
+            config.cluster = cluster;
+        }
+        if let Some(secret) = coordinates.secret {
+            config.secret = Some(secret);
+        }
+        config.validate()?;
+        Ok(config)
+    }
 }
 
 fn validate_nonempty(name: &str, value: &str) -> Result<(), Error> {
@@ -517,7 +807,7 @@ where
 }
 
 #[cfg(target_arch = "wasm32")]
-fn node_env(name: &str) -> Result<Option<String>, Error> {
+pub(crate) fn node_env(name: &str) -> Result<Option<String>, Error> {
     node_env_from_global(&js_sys::global(), name)
 }
 
@@ -585,6 +875,16 @@ impl ClientConfig {
             .or(Some(DEFAULT_REFRESH_INTERVAL_SECONDS));
         let request_timeout = parse_optional_env_with(&lookup, "APOLLO_REQUEST_TIMEOUT")?
             .or(Some(DEFAULT_REQUEST_TIMEOUT_SECONDS));
+        let namespace_idle_timeout =
+            parse_optional_env_with(&lookup, "APOLLO_NAMESPACE_IDLE_TIMEOUT")?;
+        let override_env_prefix = lookup("APOLLO_OVERRIDE_ENV_PREFIX")?;
+        let resolve_placeholders = parse_optional_env_with(&lookup, "APOLLO_RESOLVE_PLACEHOLDERS")?;
+        #[cfg(not(target_arch = "wasm32"))]
+        let override_file = lookup("APOLLO_OVERRIDE_FILE")?;
+        #[cfg(not(target_arch = "wasm32"))]
+        let cache_layout = parse_optional_env_with(&lookup, "APOLLO_CACHE_LAYOUT")?;
+        #[cfg(not(target_arch = "wasm32"))]
+        let shared_cache = parse_optional_env_with(&lookup, "APOLLO_SHARED_CACHE")?;
         let config = Self {
             app_id,
             secret,
@@ -597,8 +897,17 @@ impl ClientConfig {
             cache_ttl,
             refresh_interval,
             request_timeout,
+            namespace_idle_timeout,
+            override_env_prefix,
+            #[cfg(not(target_arch = "wasm32"))]
+            override_file,
+            resolve_placeholders,
             #[cfg(not(target_arch = "wasm32"))]
             http_client: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            cache_layout,
+            #[cfg(not(target_arch = "wasm32"))]
+            shared_cache,
         };
         config.validate()?;
         Ok(config)
@@ -627,6 +936,16 @@ cfg_if! {
             /// - `APOLLO_CACHE_TTL` (optional): Cache time-to-live in seconds. Defaults to 600 if not set.
             /// - `APOLLO_REFRESH_INTERVAL` (optional): Periodic refresh interval in seconds. Defaults to 30.
             /// - `APOLLO_REQUEST_TIMEOUT` (optional): Complete request timeout in seconds. Defaults to 10.
+            /// - `APOLLO_NAMESPACE_IDLE_TIMEOUT` (optional): Idle time in seconds after which
+            ///   unused namespaces are forgotten. Unset keeps namespaces indefinitely.
+            /// - `APOLLO_OVERRIDE_ENV_PREFIX` (optional): Prefix of environment variables
+            ///   that override Apollo values.
+            /// - `APOLLO_OVERRIDE_FILE` (optional): Watched YAML or JSON file of local overrides.
+            /// - `APOLLO_RESOLVE_PLACEHOLDERS` (optional): If set to `"true"`, resolves
+            ///   `${key:default}` placeholders in Properties values.
+            /// - `APOLLO_CACHE_LAYOUT` (optional): `native` (default) or `java` cache file layout.
+            /// - `APOLLO_SHARED_CACHE` (optional): If set to `"true"`, cooperates with other
+            ///   processes using the same cache directory.
             ///
             /// # Returns
             ///
@@ -761,6 +1080,9 @@ mod tests {
                 "APOLLO_CACHE_TTL" => Some("0".to_string()),
                 "APOLLO_REFRESH_INTERVAL" => Some("15".to_string()),
                 "APOLLO_REQUEST_TIMEOUT" => Some("3".to_string()),
+                "APOLLO_NAMESPACE_IDLE_TIMEOUT" => Some("3600".to_string()),
+                "APOLLO_OVERRIDE_ENV_PREFIX" => Some("SAMPLE_OVERRIDE_".to_string()),
+                "APOLLO_RESOLVE_PLACEHOLDERS" => Some("true".to_string()),
                 _ => None,
             })
         };
@@ -769,6 +1091,51 @@ mod tests {
         assert_eq!(config.cache_ttl, Some(0));
         assert_eq!(config.refresh_interval, Some(15));
         assert_eq!(config.request_timeout, Some(3));
+        assert_eq!(config.namespace_idle_timeout, Some(3600));
+        assert_eq!(
+            config.override_env_prefix.as_deref(),
+            Some("SAMPLE_OVERRIDE_")
+        );
+        assert_eq!(config.resolve_placeholders, Some(true));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn java_cache_layout_follows_the_java_directory_convention() {
+        let lookup = |name: &str| {
+            Ok(match name {
+                "APP_ID" => Some("sample".to_string()),
+                "APOLLO_CONFIG_SERVICE" => Some("https://apollo.example".to_string()),
+                "APOLLO_CACHE_LAYOUT" => Some("Java".to_string()),
+                _ => None,
+            })
+        };
+        let config = ClientConfig::from_lookup(lookup).unwrap();
+        assert_eq!(config.cache_layout, Some(CacheLayout::Java));
+        if cfg!(windows) {
+            assert_eq!(
+                config.get_cache_dir(),
+                std::path::PathBuf::from(r"C:\opt\data\sample\config-cache")
+            );
+        } else {
+            assert_eq!(
+                config.get_cache_dir(),
+                std::path::PathBuf::from("/opt/data/sample/config-cache")
+            );
+        }
+
+        let custom = ClientConfig::builder("sample", "https://apollo.example")
+            .cache_dir("/var/cache")
+            .cache_layout(CacheLayout::Java)
+            .build()
+            .unwrap();
+        assert_eq!(
+            custom.get_cache_dir(),
+            std::path::PathBuf::from("/var/cache")
+                .join("sample")
+                .join("config-cache")
+        );
+        assert!("xml".parse::<CacheLayout>().is_err());
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -827,10 +1194,28 @@ cfg_if! {
             /// - If `cache_dir` is `None`, the result follows XDG conventions on
             ///   Linux, `~/Library/Caches` on macOS, and Local `AppData` on Windows.
             ///
+            /// With [`CacheLayout::Java`] the Java client's convention applies instead:
+            /// `{cache_dir}/{app_id}/config-cache`, or `/opt/data/{app_id}/config-cache`
+            /// (`C:\opt\data\{app_id}\config-cache` on Windows) without a `cache_dir`.
+            ///
             /// # Returns
             ///
             /// A `std::path::PathBuf` for the cache directory.
             pub(crate) fn get_cache_dir(&self) -> std::path::PathBuf {
+                if self.effective_cache_layout() == CacheLayout::Java {
+                    let root = self.cache_dir.as_ref().map_or_else(
+                        || {
+                            if cfg!(windows) {
+                                std::path::PathBuf::from(r"C:\opt\data")
+                            } else {
+                                std::path::PathBuf::from("/opt/data")
+                            }
+                        },
+                        std::path::PathBuf::from,
+                    );
+                    return root.join(&self.app_id).join("config-cache");
+                }
+
                 if let Some(cache_dir) = &self.cache_dir {
                     return std::path::PathBuf::from(cache_dir)
                         .join("apollo-rust-client")
@@ -846,6 +1231,18 @@ cfg_if! {
                     |dirs| dirs.cache_dir().join("config-cache"),
                 )
             }
+
+            /// Returns the effective native cache file layout.
+            pub(crate) fn effective_cache_layout(&self) -> CacheLayout {
+                self.cache_layout.unwrap_or_default()
+            }
+
+            /// Returns whether cross-process cache sharing is enabled.
+            #[cfg(feature = "shared-cache")]
+            #[must_use]
+            pub(crate) fn effective_shared_cache(&self) -> bool {
+                self.shared_cache.unwrap_or(false)
+            }
         }
     } else {
         #[wasm_bindgen]
@@ -887,6 +1284,11 @@ cfg_if! {
                     cache_ttl: Some(DEFAULT_CACHE_TTL_SECONDS),
                     refresh_interval: Some(DEFAULT_REFRESH_INTERVAL_SECONDS),
                     request_timeout: Some(DEFAULT_REQUEST_TIMEOUT_SECONDS),
+                    namespace_idle_timeout: None,
+                    override_env_prefix: None,
+                    resolve_placeholders: None,
+                    #[cfg(not(target_arch = "wasm32"))]
+                    override_file: None,
                 }
             }
         }
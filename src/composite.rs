@@ -0,0 +1,393 @@
+//! Layered configuration across namespaces.
+//!
+//! A [`CompositeConfig`] combines an ordered list of namespaces into one
+//! effective view, like the Java client's merged `Config`. Later namespaces
+//! override earlier ones:
+//!
+//! - **Properties** layers resolve each key to the last layer that defines it.
+//! - **JSON and YAML** layers are deep-merged into one document: objects merge
+//!   key by key, while arrays and scalars replace what earlier layers had.
+//! - **Text** layers do not contribute.
+//!
+//! The effective view records which namespace supplied every property key and
+//! every document leaf, addressed by JSON Pointer path as in
+//! [`crate::ConfigChangeEvent`]. Keys and paths are tracked apart, so a
+//! Properties key spelled like a path never hides a document leaf.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use apollo_rust_client::{Client, client_config::ClientConfig};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = ClientConfig::builder("my-app", "http://apollo-server:8080").build()?;
+//! let client = Client::new(config)?;
+//!
+//! let composite = client.composite(&["application", "shared.common"]).await?;
+//! let snapshot = composite.snapshot();
+//! let timeout: Option<u32> = snapshot.get_property("http.timeout");
+//! println!("{timeout:?} from {:?}", snapshot.source("http.timeout"));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    Error, ListenerOptions, Subscription,
+    cache::Cache,
+    change::flatten_document,
+    namespace::{document_tree, is_properties, properties::parse_scalar},
+    subscription::{Notification, Notifier},
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        /// Listener for a [`CompositeConfig`].
+        pub type CompositeListener = Arc<dyn Fn(Result<Arc<CompositeSnapshot>, Error>)>;
+    } else {
+        /// Listener for a [`CompositeConfig`].
+        ///
+        /// Listeners receive the new effective view, or a refresh error from
+        /// any layer.
+        pub type CompositeListener =
+            Arc<dyn Fn(Result<Arc<CompositeSnapshot>, Error>) + Send + Sync>;
+    }
+}
+
+/// The effective configuration of a [`CompositeConfig`] at one point in time.
+#[derive(Clone, Debug, Default)]
+pub struct CompositeSnapshot {
+    properties: BTreeMap<String, Value>,
+    document: Option<Value>,
+    property_sources: BTreeMap<String, String>,
+    document_sources: BTreeMap<String, String>,
+}
+
+impl CompositeSnapshot {
+    /// Gets a property from the highest-precedence Properties layer that
+    /// defines it, parsed like [`crate::namespace::properties::Properties::get_property`].
+    #[must_use]
+    pub fn get_property<T: std::str::FromStr>(&self, key: &str) -> Option<T> {
+        parse_scalar(self.properties.get(key)?)
+    }
+
+    /// Gets a property as a string.
+    #[must_use]
+    pub fn get_string(&self, key: &str) -> Option<String> {
+        self.get_property(key)
+    }
+
+    /// Returns the deep-merged JSON and YAML layers, or `None` if no layer is
+    /// a document.
+    #[must_use]
+    pub fn document(&self) -> Option<&Value> {
+        self.document.as_ref()
+    }
+
+    /// Deserializes the merged document into `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no document or it does not match `T`.
+    pub fn to_object<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        T::deserialize(self.document.as_ref().unwrap_or(&Value::Null))
+    }
+
+    /// Returns the namespace that supplied a property key.
+    #[must_use]
+    pub fn source(&self, key: &str) -> Option<&str> {
+        self.property_sources.get(key).map(String::as_str)
+    }
+
+    /// Returns the namespace that supplied the document leaf at a JSON Pointer
+    /// path such as `/server/port`.
+    #[must_use]
+    pub fn document_source(&self, pointer: &str) -> Option<&str> {
+        self.document_sources.get(pointer).map(String::as_str)
+    }
+
+    fn same_values(&self, other: &Self) -> bool {
+        self.properties == other.properties && self.document == other.document
+    }
+}
+
+struct State {
+    layers: Vec<Option<Value>>,
+    snapshot: Arc<CompositeSnapshot>,
+    version: u64,
+}
+
+struct Inner {
+    namespaces: Vec<String>,
+    caches: Vec<Arc<Cache>>,
+    state: Arc<Mutex<State>>,
+    _subscription: Subscription,
+}
+
+/// A live, layered view over several namespaces.
+///
+/// Cloning is cheap. Layers are tracked until the last clone is dropped.
+#[derive(Clone)]
+pub struct CompositeConfig {
+    inner: Arc<Inner>,
+}
+
+impl std::fmt::Debug for CompositeConfig {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("CompositeConfig")
+            .field("namespaces", &self.inner.namespaces)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CompositeConfig {
+    /// Loads every layer, then tracks changes to each.
+    pub(crate) async fn new(
+        namespaces: Vec<String>,
+        caches: Vec<Arc<Cache>>,
+    ) -> Result<Self, Error> {
+        let mut layers = Vec::with_capacity(caches.len());
+        for cache in &caches {
+            layers.push(Some(cache.get_value().await?));
+        }
+        let snapshot = Arc::new(merge(&namespaces, &layers));
+        let state = Arc::new(Mutex::new(State {
+            layers,
+            snapshot,
+            version: 0,
+        }));
+
+        // Replays close the gap between the loads above and registration.
+        let mut subscriptions = Vec::with_capacity(caches.len());
+        for (index, cache) in caches.iter().enumerate() {
+            let state = state.clone();
+            let namespaces = namespaces.clone();
+            let notifier: Notifier = Arc::new(move |notification| {
+                if let Notification::Changed { config, .. } = notification {
+                    let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+                    state.layers[index] = Some((*config).clone());
+                    let snapshot = merge(&namespaces, &state.layers);
+                    if !snapshot.same_values(&state.snapshot) {
+                        state.version += 1;
+                    }
+                    state.snapshot = Arc::new(snapshot);
+                }
+            });
+            subscriptions.push(
+                cache
+                    .subscribe(notifier, ListenerOptions::default().replay_current(true))
+                    .await,
+            );
+        }
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                namespaces,
+                caches,
+                state,
+                _subscription: Subscription::combine(subscriptions),
+            }),
+        })
+    }
+
+    /// Returns the layer namespaces, lowest precedence first.
+    #[must_use]
+    pub fn namespaces(&self) -> &[String] {
+        &self.inner.namespaces
+    }
+
+    /// Returns the current effective view.
+    #[must_use]
+    pub fn snapshot(&self) -> Arc<CompositeSnapshot> {
+        self.inner
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .snapshot
+            .clone()
+    }
+
+    /// Registers a listener for changes to the effective view.
+    ///
+    /// The listener runs when a layer change alters an effective value, but
+    /// not when the change is hidden by a higher-precedence layer. Refresh
+    /// errors from any layer are delivered as [`Error::Refresh`]. The listener
+    /// stays registered while the returned [`Subscription`] is alive.
+    pub async fn add_listener(&self, listener: CompositeListener) -> Subscription {
+        let delivered = Arc::new(AtomicU64::new(
+            self.inner
+                .state
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .version,
+        ));
+        let mut subscriptions = Vec::with_capacity(self.inner.caches.len());
+        for cache in &self.inner.caches {
+            let state = self.inner.state.clone();
+            let delivered = delivered.clone();
+            let listener = listener.clone();
+            let notifier: Notifier = Arc::new(move |notification| match notification {
+                Notification::Changed { .. } => {
+                    let (version, snapshot) = {
+                        let state = state.lock().unwrap_or_else(PoisonError::into_inner);
+                        (state.version, state.snapshot.clone())
+                    };
+                    if delivered.fetch_max(version, Ordering::AcqRel) < version {
+                        listener(Ok(snapshot));
+                    }
+                }
+                Notification::Failed(error) => listener(Err(Error::Refresh((*error).to_string()))),
+            });
+            subscriptions.push(cache.subscribe(notifier, ListenerOptions::default()).await);
+        }
+        Subscription::combine(subscriptions)
+    }
+}
+
+/// Builds the effective view of `layers`, lowest precedence first.
+fn merge(namespaces: &[String], layers: &[Option<Value>]) -> CompositeSnapshot {
+    let mut snapshot = CompositeSnapshot::default();
+    for (namespace, config) in namespaces.iter().zip(layers) {
+        let Some(config) = config else { continue };
+        if let Some(document) = document_tree(namespace, config) {
+            if let Some(target) = snapshot.document.as_mut() {
+                merge_document(
+                    target,
+                    document,
+                    "",
+                    namespace,
+                    &mut snapshot.document_sources,
+                );
+            } else {
+                record_sources(&document, "", namespace, &mut snapshot.document_sources);
+                snapshot.document = Some(document);
+            }
+        } else if is_properties(namespace)
+            && let Value::Object(map) = config
+        {
+            for (key, value) in map {
+                snapshot.properties.insert(key.clone(), value.clone());
+                snapshot
+                    .property_sources
+                    .insert(key.clone(), namespace.clone());
+            }
+        }
+    }
+    snapshot
+}
+
+fn merge_document(
+    target: &mut Value,
+    layer: Value,
+    pointer: &str,
+    namespace: &str,
+    sources: &mut BTreeMap<String, String>,
+) {
+    match (target, layer) {
+        (Value::Object(target), Value::Object(layer)) => {
+            for (key, value) in layer {
+                let path = format!("{pointer}/{}", key.replace('~', "~0").replace('/', "~1"));
+                if let Some(existing) = target.get_mut(&key) {
+                    merge_document(existing, value, &path, namespace, sources);
+                } else {
+                    record_sources(&value, &path, namespace, sources);
+                    target.insert(key, value);
+                }
+            }
+        }
+        (target, layer) => {
+            let below = format!("{pointer}/");
+            sources.retain(|key, _| key != pointer && !key.starts_with(&below));
+            record_sources(&layer, pointer, namespace, sources);
+            *target = layer;
+        }
+    }
+}
+
+fn record_sources(
+    value: &Value,
+    pointer: &str,
+    namespace: &str,
+    sources: &mut BTreeMap<String, String>,
+) {
+    let mut leaves = BTreeMap::new();
+    flatten_document(pointer.to_string(), value.clone(), &mut leaves);
+    for path in leaves.into_keys() {
+        sources.insert(path, namespace.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn layers(values: &[(&str, Value)]) -> (Vec<String>, Vec<Option<Value>>) {
+        values
+            .iter()
+            .map(|(namespace, value)| (namespace.to_string(), Some(value.clone())))
+            .unzip()
+    }
+
+    #[test]
+    fn later_layers_override_properties_and_deep_merge_documents() {
+        let (namespaces, values) = layers(&[
+            ("application", json!({"timeout": "10", "name": "app"})),
+            ("shared", json!({"timeout": "30"})),
+            (
+                "base.json",
+                json!({"content": r#"{"db":{"host":"a","port":1},"tags":["x"]}"#}),
+            ),
+            (
+                "override.yaml",
+                json!({"content": "db:\n  port: 2\ntags: [blue, green]\n"}),
+            ),
+        ]);
+        let snapshot = merge(&namespaces, &values);
+
+        assert_eq!(snapshot.get_property::<u32>("timeout"), Some(30));
+        assert_eq!(snapshot.source("timeout"), Some("shared"));
+        assert_eq!(snapshot.source("name"), Some("application"));
+        assert_eq!(
+            snapshot.document(),
+            Some(&json!({"db": {"host": "a", "port": 2}, "tags": ["blue", "green"]}))
+        );
+        assert_eq!(snapshot.document_source("/db/host"), Some("base.json"));
+        assert_eq!(snapshot.document_source("/db/port"), Some("override.yaml"));
+        assert_eq!(snapshot.document_source("/tags/1"), Some("override.yaml"));
+        assert_eq!(snapshot.document_source("/tags/0"), Some("override.yaml"));
+    }
+
+    #[test]
+    fn replacing_an_object_with_a_scalar_drops_the_shadowed_sources() {
+        let (namespaces, values) = layers(&[
+            ("base.json", json!({"content": r#"{"db":{"host":"a"}}"#})),
+            ("override.json", json!({"content": r#"{"db":"disabled"}"#})),
+        ]);
+        let snapshot = merge(&namespaces, &values);
+        assert_eq!(snapshot.document_source("/db/host"), None);
+        assert_eq!(snapshot.document_source("/db"), Some("override.json"));
+    }
+
+    #[test]
+    fn property_keys_spelled_like_paths_do_not_hide_document_leaves() {
+        let (namespaces, values) = layers(&[
+            ("base.json", json!({"content": r#"{"db":{"port":1}}"#})),
+            ("application", json!({"/db/port": "2"})),
+        ]);
+        let snapshot = merge(&namespaces, &values);
+        assert_eq!(snapshot.source("/db/port"), Some("application"));
+        assert_eq!(snapshot.document_source("/db/port"), Some("base.json"));
+        assert_eq!(snapshot.get_string("/db/port").as_deref(), Some("2"));
+    }
+}
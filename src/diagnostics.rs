@@ -0,0 +1,171 @@
+//! Connectivity diagnostics.
+//!
+//! [`crate::Client::diagnose`] fetches a namespace from Apollo through the
+//! same refresh path that serves applications and reports whether the server
+//! answered, whether it accepted the request signature, and where the
+//! namespace is persisted. The `apollo status` command prints this report.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use apollo_rust_client::{Client, SignatureStatus, client_config::ClientConfig};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = ClientConfig::builder("my-app", "http://apollo-server:8080")
+//!     .secret("access-key-secret")
+//!     .build()?;
+//! let client = Client::new(config)?;
+//!
+//! let diagnosis = client.diagnose("application").await;
+//! if diagnosis.signature == SignatureStatus::Rejected {
+//!     eprintln!("check the access key secret and the clock");
+//! }
+//! println!("cached at {}", diagnosis.cache_location);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::cache::Cache;
+use serde::Serialize;
+
+/// Whether Apollo accepted the signature of a request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SignatureStatus {
+    /// No secret is configured and Apollo did not require one.
+    Unsigned,
+    /// Apollo accepted the signed request.
+    Accepted,
+    /// Apollo answered `401 Unauthorized`: the secret is wrong or missing,
+    /// or the client clock is too far off.
+    Rejected,
+    /// Apollo did not answer, or answered with a server error, so the
+    /// signature could not be checked.
+    Unknown,
+}
+
+/// The outcome of fetching one namespace from Apollo.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnosis {
+    /// The namespace name.
+    pub namespace: String,
+    /// The configured Apollo server.
+    pub config_server: String,
+    /// The HTTP status Apollo answered with, or `None` if it could not be
+    /// reached.
+    pub http_status: Option<u16>,
+    /// Whether Apollo accepted the request signature.
+    pub signature: SignatureStatus,
+    /// Where the namespace is persisted: a file path on native targets and
+    /// under Node.js, a `localStorage` key in browsers.
+    pub cache_location: String,
+    /// The error from the fetch, if it failed.
+    pub error: Option<String>,
+}
+
+impl Diagnosis {
+    /// Returns whether Apollo answered the request.
+    #[must_use]
+    pub fn is_reachable(&self) -> bool {
+        self.http_status.is_some()
+    }
+
+    /// Returns whether the namespace was fetched successfully.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Refreshes `cache` from Apollo and classifies the outcome.
+///
+/// Server errors say nothing about the signature, which Apollo may not have
+/// checked, so they report [`SignatureStatus::Unknown`].
+pub(crate) async fn diagnose(cache: &Cache) -> Diagnosis {
+    let (http_status, result) = cache.refresh_with_status().await;
+    let config = cache.client_config();
+    let answered = if config.secret.is_some() {
+        SignatureStatus::Accepted
+    } else {
+        SignatureStatus::Unsigned
+    };
+    let signature = match http_status {
+        Some(401) => SignatureStatus::Rejected,
+        Some(status) if status < 500 => answered,
+        _ => SignatureStatus::Unknown,
+    };
+    Diagnosis {
+        namespace: cache.namespace().to_string(),
+        config_server: config.config_server.clone(),
+        http_status,
+        signature,
+        cache_location: cache.location(),
+        error: result.err().map(|error| error.to_string()),
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::{
+        Client, TempDir,
+        client_config::ClientConfig,
+        test_util::{FakeApollo, Fault, Release},
+    };
+
+    #[tokio::test]
+    async fn diagnoses_reachability_and_signatures() {
+        let apollo = FakeApollo::start();
+        apollo.publish(Release::properties(
+            "app",
+            "application",
+            [("key", "value")],
+        ));
+        apollo.require_secret("app", "right");
+        let temp_dir = TempDir::new("diagnostics");
+        let diagnose = |server: String, secret: Option<&str>| {
+            let mut builder = ClientConfig::builder("app", server)
+                .cache_dir(temp_dir.path().to_string_lossy())
+                .request_timeout(1);
+            if let Some(secret) = secret {
+                builder = builder.secret(secret);
+            }
+            let client = Client::new(builder.build().unwrap()).unwrap();
+            async move { client.diagnose("application").await }
+        };
+
+        let accepted = diagnose(apollo.url(), Some("right")).await;
+        assert!(accepted.is_ok());
+        assert_eq!(accepted.http_status, Some(200));
+        assert_eq!(accepted.signature, SignatureStatus::Accepted);
+        assert!(
+            accepted
+                .cache_location
+                .starts_with(&*temp_dir.path().to_string_lossy())
+        );
+
+        let rejected = diagnose(apollo.url(), Some("wrong")).await;
+        assert_eq!(rejected.http_status, Some(401));
+        assert_eq!(rejected.signature, SignatureStatus::Rejected);
+        assert!(!rejected.is_ok());
+
+        apollo.inject(Fault::Status(503));
+        let unavailable = diagnose(apollo.url(), Some("right")).await;
+        assert_eq!(unavailable.http_status, Some(503));
+        assert_eq!(unavailable.signature, SignatureStatus::Unknown);
+        assert!(unavailable.is_reachable());
+
+        apollo.inject(Fault::MalformedBody);
+        let malformed = diagnose(apollo.url(), Some("right")).await;
+        assert_eq!(malformed.http_status, Some(200));
+        assert_eq!(malformed.signature, SignatureStatus::Accepted);
+        assert!(!malformed.is_ok());
+
+        let unreachable = diagnose("http://127.0.0.1:9".to_string(), None).await;
+        assert!(!unreachable.is_reachable());
+        assert_eq!(unreachable.signature, SignatureStatus::Unknown);
+        assert!(unreachable.error.is_some());
+    }
+}
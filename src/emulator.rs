@@ -0,0 +1,389 @@
+//! A local emulator of the Apollo config service.
+//!
+//! Enabled by the `emulator` feature, which also builds the
+//! `apollo-emulator` binary. An [`Emulator`] serves a directory holding one
+//! file per namespace, such as `application.properties`, `settings.json`,
+//! `routes.yaml` or `notes.txt`, through the same endpoints as
+//! [`FakeApollo`]: `configfiles/json`, `configs` and `notifications/v2`.
+//! Files named like Properties namespaces are parsed with
+//! `java.util.Properties` syntax; every other file is served verbatim as the
+//! namespace content.
+//!
+//! The directory is watched, so creating or editing a file publishes its
+//! namespace and wakes long-polling clients, and deleting a file removes its
+//! namespace. Reloads run on the Tokio runtime the emulator was started in.
+//! With a secret, requests must be signed like those of a client configured
+//! with the same access key.
+//!
+//! ```no_run
+//! use apollo_rust_client::emulator::Emulator;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), apollo_rust_client::emulator::Error> {
+//! let emulator = Emulator::builder("./config", "my-app")
+//!     .secret("local-secret")
+//!     .start()?;
+//! println!("config_server = {}", emulator.url());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::test_util::{FakeApollo, Release};
+use log::{info, warn};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    collections::BTreeMap,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+/// How long the watcher lets an edit of a namespace file settle.
+const RELOAD_DELAY: Duration = Duration::from_millis(100);
+
+/// Errors starting or reloading an [`Emulator`].
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The namespace directory or one of its files could not be read.
+    #[error("Failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The listening address could not be bound.
+    #[error("Failed to listen on {address}: {source}")]
+    Bind {
+        address: SocketAddr,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The namespace directory could not be watched.
+    #[error("Failed to watch the namespace directory: {0}")]
+    Watch(#[from] notify::Error),
+}
+
+/// Builder for an [`Emulator`].
+#[derive(Clone, Debug)]
+pub struct EmulatorBuilder {
+    directory: PathBuf,
+    app_id: String,
+    cluster: String,
+    address: SocketAddr,
+    secret: Option<String>,
+    https: bool,
+}
+
+/// Serves a directory of namespace files as an Apollo config service.
+///
+/// The server stops when the value is dropped.
+pub struct Emulator {
+    loader: Arc<Loader>,
+    _watcher: RecommendedWatcher,
+    task: tokio::task::JoinHandle<()>,
+}
+
+/// Publishes the files of the namespace directory.
+struct Loader {
+    apollo: FakeApollo,
+    directory: PathBuf,
+    app_id: String,
+    cluster: String,
+    /// The contents last published, by namespace.
+    published: Mutex<BTreeMap<String, Vec<u8>>>,
+}
+
+impl Emulator {
+    /// Returns a builder serving the files of `directory` under `app_id`.
+    pub fn builder(directory: impl Into<PathBuf>, app_id: impl Into<String>) -> EmulatorBuilder {
+        EmulatorBuilder {
+            directory: directory.into(),
+            app_id: app_id.into(),
+            cluster: "default".to_string(),
+            address: SocketAddr::from(([127, 0, 0, 1], 8080)),
+            secret: None,
+            https: false,
+        }
+    }
+
+    /// Returns the base URL to use as a client's `config_server`.
+    #[must_use]
+    pub fn url(&self) -> String {
+        self.loader.apollo.url()
+    }
+
+    /// Returns the namespaces currently served.
+    #[must_use]
+    pub fn namespaces(&self) -> Vec<String> {
+        self.loader
+            .published
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// Reads the directory again, publishing changed files and removing
+    /// deleted ones.
+    ///
+    /// Changes are picked up automatically; this forces a reload.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or one of its files cannot be read.
+    pub fn reload(&self) -> Result<(), Error> {
+        self.loader.reload()
+    }
+}
+
+impl EmulatorBuilder {
+    /// Publishes to `cluster` instead of `default`.
+    #[must_use]
+    pub fn cluster(mut self, cluster: impl Into<String>) -> Self {
+        self.cluster = cluster.into();
+        self
+    }
+
+    /// Listens on `address` instead of `127.0.0.1:8080`.
+    #[must_use]
+    pub fn bind(mut self, address: SocketAddr) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Requires requests to be signed with the access key `secret`.
+    #[must_use]
+    pub fn secret(mut self, secret: impl Into<String>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+
+    /// Serves over HTTPS with a self-signed certificate for `localhost`.
+    #[must_use]
+    pub fn https(mut self, https: bool) -> Self {
+        self.https = https;
+        self
+    }
+
+    /// Loads the directory, starts serving it and starts watching it.
+    ///
+    /// Must be called within a Tokio runtime, which reloads the directory
+    /// when it changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read or watched, the
+    /// address cannot be bound, or no Tokio runtime is running.
+    pub fn start(self) -> Result<Emulator, Error> {
+        let runtime = tokio::runtime::Handle::try_current().map_err(|_| {
+            notify::Error::generic("watching the namespace directory requires a Tokio runtime")
+        })?;
+        let apollo = FakeApollo::builder()
+            .bind(self.address)
+            .https(self.https)
+            .try_start()
+            .map_err(|source| Error::Bind {
+                address: self.address,
+                source,
+            })?;
+        if let Some(secret) = self.secret {
+            apollo.require_secret(self.app_id.clone(), secret);
+        }
+        let loader = Arc::new(Loader {
+            apollo,
+            directory: self.directory,
+            app_id: self.app_id,
+            cluster: self.cluster,
+            published: Mutex::new(BTreeMap::new()),
+        });
+        loader.reload()?;
+
+        // Events arrive on the watcher's own thread, so they are forwarded to
+        // a task on the runtime that reloads the directory.
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<()>();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<Event>| match event {
+                Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+                    let _ = sender.send(());
+                }
+                Ok(_) => {}
+                Err(error) => warn!("Namespace directory watcher error: {error}"),
+            })?;
+        watcher.watch(&loader.directory, RecursiveMode::NonRecursive)?;
+
+        // Waiting briefly before reloading lets an edit finish; events
+        // arriving meanwhile are covered by the same reload.
+        let reloading = loader.clone();
+        let task = runtime.spawn(async move {
+            while receiver.recv().await.is_some() {
+                tokio::time::sleep(RELOAD_DELAY).await;
+                while receiver.try_recv().is_ok() {}
+                let reloading = reloading.clone();
+                match tokio::task::spawn_blocking(move || reloading.reload()).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(error)) => warn!("Failed to reload namespace files: {error}"),
+                    Err(error) => warn!("Namespace reload task failed: {error}"),
+                }
+            }
+        });
+        Ok(Emulator {
+            loader,
+            _watcher: watcher,
+            task,
+        })
+    }
+}
+
+impl Drop for Emulator {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl Loader {
+    fn reload(&self) -> Result<(), Error> {
+        let files = self.read_files()?;
+        let mut published = self
+            .published
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        for namespace in published.keys() {
+            if !files.contains_key(namespace) {
+                self.apollo
+                    .unpublish(&self.app_id, &self.cluster, namespace);
+                info!("Removed namespace {namespace}");
+            }
+        }
+        for (namespace, content) in &files {
+            if published.get(namespace) != Some(content) {
+                self.apollo.publish(self.release(namespace, content));
+                info!("Published namespace {namespace}");
+            }
+        }
+        *published = files;
+        Ok(())
+    }
+
+    /// Reads the namespace files, skipping hidden files, editor backups and
+    /// subdirectories.
+    fn read_files(&self) -> Result<BTreeMap<String, Vec<u8>>, Error> {
+        let io_error = |path: &Path| {
+            let path = path.to_path_buf();
+            move |source| Error::Io { path, source }
+        };
+        let mut files = BTreeMap::new();
+        for entry in std::fs::read_dir(&self.directory).map_err(io_error(&self.directory))? {
+            let path = entry.map_err(io_error(&self.directory))?.path();
+            let Some(namespace) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if namespace.starts_with('.') || namespace.ends_with('~') || !path.is_file() {
+                continue;
+            }
+            match std::fs::read(&path) {
+                Ok(content) => {
+                    files.insert(namespace.to_string(), content);
+                }
+                // Deleted between listing and reading.
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+                Err(source) => return Err(Error::Io { path, source }),
+            }
+        }
+        Ok(files)
+    }
+
+    fn release(&self, namespace: &str, content: &[u8]) -> Release {
+        let release = if crate::namespace::is_properties(namespace) {
+            let properties = crate::cache::java::from_properties(content);
+            let properties = properties.as_object().into_iter().flatten();
+            Release::properties(
+                &self.app_id,
+                namespace,
+                properties.map(|(key, value)| (key.clone(), value.as_str().unwrap_or_default())),
+            )
+        } else {
+            Release::content(&self.app_id, namespace, String::from_utf8_lossy(content))
+        };
+        release.cluster(&self.cluster)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Client, TempDir, client_config::ClientConfig, namespace::Namespace};
+    use std::time::Instant;
+
+    async fn wait_for<F: Future<Output = bool>>(mut condition: impl FnMut() -> F) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !condition().await {
+            assert!(Instant::now() < deadline, "condition not met in time");
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn emulator_serves_namespace_files_and_reloads_them() {
+        let directory = TempDir::new("emulator_namespaces");
+        std::fs::write(
+            directory.path().join("application.properties"),
+            "# comment\ndb.host = localhost\ndb.pool=8\n",
+        )
+        .unwrap();
+        std::fs::write(
+            directory.path().join("settings.json"),
+            r#"{"theme":"dark"}"#,
+        )
+        .unwrap();
+        std::fs::write(directory.path().join(".hidden"), "ignored").unwrap();
+        let emulator = Emulator::builder(directory.path(), "local-app")
+            .bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .secret("local-secret")
+            .start()
+            .unwrap();
+        assert_eq!(
+            emulator.namespaces(),
+            ["application.properties", "settings.json"]
+        );
+
+        let cache_dir = TempDir::new("emulator_client");
+        let client = Client::new(
+            ClientConfig::builder("local-app", emulator.url())
+                .cache_dir(cache_dir.path().to_string_lossy())
+                .secret("local-secret")
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+        let pool = || async {
+            client.refresh("application").await.ok()?;
+            match client.namespace("application").await.ok()? {
+                Namespace::Properties(properties) => properties.get_int("db.pool"),
+                _ => None,
+            }
+        };
+        assert_eq!(pool().await, Some(8));
+        let Namespace::Json(settings) = client.namespace("settings.json").await.unwrap() else {
+            panic!("settings.json should be a JSON namespace");
+        };
+        assert_eq!(
+            settings.to_object::<serde_json::Value>().unwrap()["theme"],
+            "dark"
+        );
+
+        std::fs::write(
+            directory.path().join("application.properties"),
+            "db.host=localhost\ndb.pool=16\n",
+        )
+        .unwrap();
+        wait_for(|| async { pool().await == Some(16) }).await;
+
+        std::fs::remove_file(directory.path().join("settings.json")).unwrap();
+        wait_for(|| async { client.refresh("settings.json").await.is_err() }).await;
+        assert_eq!(emulator.namespaces(), ["application.properties"]);
+    }
+}
@@ -0,0 +1,547 @@
+//! Runs a child process with configuration injected as environment variables.
+//!
+//! A [`Supervisor`] maps the keys of one or more Properties namespaces to
+//! environment variables, spawns a program with them, and keeps the program
+//! in step with Apollo: when a change alters the variables, the program is
+//! restarted with the new ones, or sent a signal so it can re-read its own
+//! configuration. The `apollo exec` command wraps it. Enabled by the `exec`
+//! feature, which the `cli` feature turns on.
+//!
+//! Namespaces are read like any other, so a supervisor started while Apollo
+//! is unreachable runs the program with the persisted cache and picks up
+//! changes once Apollo answers again.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use apollo_rust_client::{
+//!     Client,
+//!     client_config::ClientConfig,
+//!     exec::{KeyCase, OnChange, Supervisor},
+//! };
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = ClientConfig::builder("my-app", "http://apollo-server:8080").build()?;
+//! let client = Client::new(config)?;
+//!
+//! // `db.url` in `application` becomes `APP_DB_URL`.
+//! let status = Supervisor::new(client, "./legacy-server")
+//!     .arg("--verbose")
+//!     .namespace("application")
+//!     .prefix("APP_")
+//!     .key_case(KeyCase::Upper)
+//!     .on_change(OnChange::Restart)
+//!     .run()
+//!     .await?;
+//! println!("legacy-server exited with {status}");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    Client, LiveConfig,
+    namespace::{Namespace, get_namespace},
+};
+use futures::future::{Either, select};
+use log::{info, warn};
+use serde_json::{Map, Value};
+use std::{
+    collections::BTreeMap, ffi::OsString, pin::pin, process::ExitStatus, str::FromStr, sync::Arc,
+    time::Duration,
+};
+use tokio::{
+    process::{Child, Command},
+    sync::Notify,
+};
+
+/// How long a restarted child has to exit before it is killed.
+const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+/// Errors supervising a child process.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A namespace could not be loaded from Apollo or the persisted cache.
+    #[error("Failed to load configuration: {0}")]
+    Client(#[from] crate::Error),
+
+    /// The program could not be started.
+    #[error("Failed to start {program}: {source}")]
+    Spawn {
+        program: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// Waiting for the program to exit failed.
+    #[error("Failed to wait for the child process: {0}")]
+    Wait(#[source] std::io::Error),
+
+    /// The program could not be signalled or stopped.
+    #[error("Failed to signal the child process: {0}")]
+    Signal(#[source] std::io::Error),
+
+    /// An [`OnChange`] or [`KeyCase`] could not be parsed.
+    #[error("Invalid {kind} {value:?}")]
+    Parse { kind: &'static str, value: String },
+}
+
+/// How configuration keys are cased in environment variable names.
+///
+/// Characters other than ASCII letters and digits become `_` in every case.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyCase {
+    /// `db.url` becomes `DB_URL`.
+    #[default]
+    Upper,
+    /// `db.URL` becomes `db_url`.
+    Lower,
+    /// `db.Url` becomes `db_Url`.
+    Preserve,
+}
+
+/// What a [`Supervisor`] does when a change alters the environment.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OnChange {
+    /// Stops the child and starts it again with the new environment.
+    #[default]
+    Restart,
+    /// Sends the child a signal, such as `libc::SIGHUP`, and keeps it
+    /// running. The child keeps the environment it was started with.
+    #[cfg(unix)]
+    Signal(i32),
+}
+
+/// Runs a program with Properties namespaces as environment variables.
+pub struct Supervisor {
+    client: Client,
+    program: OsString,
+    args: Vec<OsString>,
+    namespaces: Vec<String>,
+    prefix: String,
+    key_case: KeyCase,
+    on_change: OnChange,
+    grace_period: Duration,
+}
+
+impl Supervisor {
+    /// Returns a supervisor running `program` with the `application`
+    /// namespace of `client`.
+    pub fn new(client: Client, program: impl Into<OsString>) -> Self {
+        Self {
+            client,
+            program: program.into(),
+            args: Vec::new(),
+            namespaces: Vec::new(),
+            prefix: String::new(),
+            key_case: KeyCase::default(),
+            on_change: OnChange::default(),
+            grace_period: DEFAULT_GRACE_PERIOD,
+        }
+    }
+
+    /// Appends an argument of the program.
+    #[must_use]
+    pub fn arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Appends arguments of the program.
+    #[must_use]
+    pub fn args(mut self, args: impl IntoIterator<Item = impl Into<OsString>>) -> Self {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds a namespace to inject, replacing the default `application`.
+    ///
+    /// When namespaces share a key, the one added last wins.
+    #[must_use]
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespaces.push(namespace.into());
+        self
+    }
+
+    /// Prepends `prefix`, as given, to every variable name.
+    #[must_use]
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Sets how keys are cased in variable names. Defaults to
+    /// [`KeyCase::Upper`].
+    #[must_use]
+    pub fn key_case(mut self, key_case: KeyCase) -> Self {
+        self.key_case = key_case;
+        self
+    }
+
+    /// Sets what happens when a change alters the environment. Defaults to
+    /// [`OnChange::Restart`].
+    #[must_use]
+    pub fn on_change(mut self, on_change: OnChange) -> Self {
+        self.on_change = on_change;
+        self
+    }
+
+    /// Sets how long a restarted child has to exit after `SIGTERM` before it
+    /// is killed. Defaults to 10 seconds; elsewhere than Unix the child is
+    /// killed at once.
+    #[must_use]
+    pub fn grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Returns the variables the program would be started with now, in
+    /// addition to the inherited environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a namespace cannot be loaded from Apollo or the
+    /// persisted cache.
+    pub async fn environment(&self) -> Result<BTreeMap<String, String>, Error> {
+        Ok(self.compose(&self.bind().await?))
+    }
+
+    /// Starts the program and supervises it until it exits.
+    ///
+    /// The client is started if it is not running yet, so changes are
+    /// followed at its refresh interval. A change that leaves the variables
+    /// as they were is ignored. The program is killed if the returned future
+    /// is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a namespace cannot be loaded at startup, or the
+    /// program cannot be started, signalled or waited for.
+    pub async fn run(self) -> Result<ExitStatus, Error> {
+        let bound = self.bind().await?;
+        let changed = Arc::new(Notify::new());
+        let mut subscriptions = Vec::with_capacity(bound.len());
+        for live in &bound {
+            let changed = changed.clone();
+            subscriptions.push(
+                live.add_listener(Arc::new(move |result| {
+                    if result.is_ok() {
+                        changed.notify_one();
+                    }
+                }))
+                .await,
+            );
+        }
+        match self.client.start().await {
+            Ok(()) | Err(crate::Error::AlreadyRunning) => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        let mut environment = self.compose(&bound);
+        let mut child = self.spawn(&environment)?;
+        loop {
+            {
+                let exited = pin!(child.wait());
+                let notified = pin!(changed.notified());
+                if let Either::Left((status, _)) = select(exited, notified).await {
+                    return status.map_err(Error::Wait);
+                }
+            }
+            let next = self.compose(&bound);
+            if next == environment {
+                continue;
+            }
+            environment = next;
+            match self.on_change {
+                OnChange::Restart => {
+                    info!(
+                        "Configuration changed, restarting {}",
+                        self.program.display()
+                    );
+                    self.stop(&mut child).await?;
+                    child = self.spawn(&environment)?;
+                }
+                #[cfg(unix)]
+                OnChange::Signal(signal) => {
+                    info!(
+                        "Configuration changed, signalling {}",
+                        self.program.display()
+                    );
+                    send_signal(&child, signal)?;
+                }
+            }
+        }
+    }
+
+    async fn bind(&self) -> Result<Vec<LiveConfig<Map<String, Value>>>, Error> {
+        let mut bound = Vec::new();
+        if self.namespaces.is_empty() {
+            bound.push(
+                LiveConfig::bind_with(self.client.cache("application").await, entries).await?,
+            );
+        }
+        for namespace in &self.namespaces {
+            bound.push(LiveConfig::bind_with(self.client.cache(namespace).await, entries).await?);
+        }
+        Ok(bound)
+    }
+
+    fn compose(&self, bound: &[LiveConfig<Map<String, Value>>]) -> BTreeMap<String, String> {
+        let mut environment = BTreeMap::new();
+        for live in bound {
+            for (key, value) in live.load().iter() {
+                let value = match value {
+                    Value::Null => continue,
+                    Value::String(text) => text.clone(),
+                    value => value.to_string(),
+                };
+                environment.insert(self.variable(key), value);
+            }
+        }
+        environment
+    }
+
+    fn variable(&self, key: &str) -> String {
+        let key = key.chars().map(|character| {
+            if character.is_ascii_alphanumeric() {
+                character
+            } else {
+                '_'
+            }
+        });
+        let mut name = self.prefix.clone();
+        match self.key_case {
+            KeyCase::Upper => name.extend(key.map(|character| character.to_ascii_uppercase())),
+            KeyCase::Lower => name.extend(key.map(|character| character.to_ascii_lowercase())),
+            KeyCase::Preserve => name.extend(key),
+        }
+        name
+    }
+
+    fn spawn(&self, environment: &BTreeMap<String, String>) -> Result<Child, Error> {
+        Command::new(&self.program)
+            .args(&self.args)
+            .envs(environment)
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|source| Error::Spawn {
+                program: self.program.display().to_string(),
+                source,
+            })
+    }
+
+    /// Asks the child to exit, then kills it after the grace period.
+    async fn stop(&self, child: &mut Child) -> Result<(), Error> {
+        #[cfg(unix)]
+        {
+            if send_signal(child, libc::SIGTERM).is_ok()
+                && let Ok(status) = tokio::time::timeout(self.grace_period, child.wait()).await
+            {
+                return status.map(drop).map_err(Error::Wait);
+            }
+            warn!(
+                "{} did not exit within {:?}, killing it",
+                self.program.display(),
+                self.grace_period
+            );
+        }
+        child.kill().await.map_err(Error::Signal)
+    }
+}
+
+/// Converts a namespace to its top-level entries, keeping Properties keys
+/// flat where [`Client::bind`] would nest them.
+fn entries(namespace: &str, config: &Value) -> Result<Map<String, Value>, String> {
+    match get_namespace(namespace, config.clone()) {
+        Ok(Namespace::Properties(properties)) => Ok(properties
+            .as_value()
+            .as_object()
+            .cloned()
+            .unwrap_or_default()),
+        _ => crate::live::deserialize(namespace, config),
+    }
+}
+
+#[cfg(unix)]
+fn send_signal(child: &Child, signal: i32) -> Result<(), Error> {
+    // `id` is `None` once the child has been reaped; it has nothing to
+    // receive then.
+    let Some(pid) = child.id() else {
+        return Ok(());
+    };
+    let pid =
+        libc::pid_t::try_from(pid).map_err(|error| Error::Signal(std::io::Error::other(error)))?;
+    // SAFETY: `kill` has no memory-safety preconditions, and `pid` is a child
+    // that has not been reaped, so it cannot have been reused.
+    if unsafe { libc::kill(pid, signal) } == 0 {
+        Ok(())
+    } else {
+        Err(Error::Signal(std::io::Error::last_os_error()))
+    }
+}
+
+impl FromStr for KeyCase {
+    type Err = Error;
+
+    /// Parses `upper`, `lower` or `preserve`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "upper" => Ok(Self::Upper),
+            "lower" => Ok(Self::Lower),
+            "preserve" => Ok(Self::Preserve),
+            _ => Err(Error::Parse {
+                kind: "key case",
+                value: value.to_string(),
+            }),
+        }
+    }
+}
+
+impl FromStr for OnChange {
+    type Err = Error;
+
+    /// Parses `restart`, or on Unix a signal name with or without its `SIG`
+    /// prefix: `HUP`, `INT`, `QUIT`, `TERM`, `USR1` or `USR2`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let name = value.to_ascii_uppercase();
+        if name == "RESTART" {
+            return Ok(Self::Restart);
+        }
+        #[cfg(unix)]
+        {
+            let signal = match name.strip_prefix("SIG").unwrap_or(&name) {
+                "HUP" => Some(libc::SIGHUP),
+                "INT" => Some(libc::SIGINT),
+                "QUIT" => Some(libc::SIGQUIT),
+                "TERM" => Some(libc::SIGTERM),
+                "USR1" => Some(libc::SIGUSR1),
+                "USR2" => Some(libc::SIGUSR2),
+                _ => None,
+            };
+            if let Some(signal) = signal {
+                return Ok(Self::Signal(signal));
+            }
+        }
+        Err(Error::Parse {
+            kind: "change action",
+            value: value.to_string(),
+        })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::{
+        TempDir,
+        client_config::ClientConfig,
+        test_util::{FakeApollo, Release},
+    };
+    use std::time::Instant;
+
+    fn client(server: String, cache_dir: &TempDir) -> Client {
+        Client::new(
+            ClientConfig::builder("app", server)
+                .cache_dir(cache_dir.path().to_string_lossy())
+                .cache_ttl(0)
+                .refresh_interval(1)
+                .request_timeout(1)
+                .build()
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn maps_namespaces_and_falls_back_to_the_cache() {
+        let apollo = FakeApollo::start();
+        apollo.publish(Release::properties(
+            "app",
+            "application",
+            [("db.url", "postgres://db"), ("pool-size", "8")],
+        ));
+        apollo.publish(Release::properties(
+            "app",
+            "overrides",
+            [("pool-size", "16")],
+        ));
+        let cache_dir = TempDir::new("exec_environment");
+        let supervisor = |server: String| {
+            Supervisor::new(client(server, &cache_dir), "true")
+                .namespace("application")
+                .namespace("overrides")
+                .prefix("APP_")
+        };
+
+        let url = apollo.url();
+        let environment = supervisor(url.clone()).environment().await.unwrap();
+        assert_eq!(
+            environment,
+            BTreeMap::from([
+                ("APP_DB_URL".to_string(), "postgres://db".to_string()),
+                ("APP_POOL_SIZE".to_string(), "16".to_string()),
+            ])
+        );
+
+        drop(apollo);
+        let offline = supervisor(url)
+            .key_case(KeyCase::Lower)
+            .environment()
+            .await
+            .unwrap();
+        assert_eq!(offline["APP_db_url"], "postgres://db");
+    }
+
+    #[tokio::test]
+    async fn restarts_the_child_when_the_environment_changes() {
+        let apollo = FakeApollo::start();
+        apollo.publish(Release::properties(
+            "app",
+            "application",
+            [("version", "1")],
+        ));
+        let cache_dir = TempDir::new("exec_restart");
+        let output = cache_dir.path().join("versions");
+        let supervisor = Supervisor::new(client(apollo.url(), &cache_dir), "sh")
+            .arg("-c")
+            .arg(r#"echo "$APP_VERSION" >> "$0"; [ "$APP_VERSION" = 2 ] && exit 3; exec sleep 30"#)
+            .arg(&output)
+            .prefix("APP_")
+            .grace_period(Duration::from_secs(1));
+        let running = tokio::spawn(supervisor.run());
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while std::fs::read_to_string(&output).unwrap_or_default() != "1\n" {
+            assert!(Instant::now() < deadline, "child did not start");
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        apollo.publish(Release::properties(
+            "app",
+            "application",
+            [("version", "2")],
+        ));
+
+        let status = tokio::time::timeout(Duration::from_secs(10), running)
+            .await
+            .expect("child was not restarted")
+            .unwrap()
+            .unwrap();
+        assert_eq!(status.code(), Some(3));
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), "1\n2\n");
+    }
+
+    #[test]
+    fn parses_change_actions() {
+        assert_eq!("restart".parse::<OnChange>().unwrap(), OnChange::Restart);
+        assert_eq!(
+            "SIGHUP".parse::<OnChange>().unwrap(),
+            OnChange::Signal(libc::SIGHUP)
+        );
+        assert_eq!(
+            "usr1".parse::<OnChange>().unwrap(),
+            OnChange::Signal(libc::SIGUSR1)
+        );
+        assert!("reload".parse::<OnChange>().is_err());
+        assert_eq!("preserve".parse::<KeyCase>().unwrap(), KeyCase::Preserve);
+    }
+}
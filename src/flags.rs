@@ -0,0 +1,760 @@
+//! Feature flags evaluated from an Apollo namespace.
+//!
+//! [`crate::Client::feature_flags`] reads flag definitions from a JSON or
+//! YAML namespace whose document maps flag names to definitions, and
+//! [`crate::Client::feature_flags_with_prefix`] reads them from the
+//! Properties keys starting with a prefix. The returned [`FeatureFlags`]
+//! handle follows namespace changes through a listener, so evaluations
+//! always use the latest definitions.
+//!
+//! # Definitions
+//!
+//! ```json
+//! {
+//!   "checkout.v2": { "type": "boolean", "enabled": true, "deny": ["user-13"] },
+//!   "search.ranker": { "type": "percentage", "percentage": 25, "allow": ["user-42"] },
+//!   "button.color": {
+//!     "type": "variant",
+//!     "variants": { "blue": 50, "red": 50 },
+//!     "default": "red",
+//!     "targets": { "user-42": "blue" }
+//!   }
+//! }
+//! ```
+//!
+//! Every flag accepts `enabled`, a kill switch that defaults to `true`,
+//! `deny`, a list of keys that always evaluate off, and `salt`, which
+//! defaults to the flag name. Boolean and percentage flags accept `allow`, a
+//! list of keys that always evaluate on; variant flags accept `targets`,
+//! which assigns keys to variants. Properties values may hold such a JSON
+//! definition or a shorthand: `true` or `false` for a boolean flag, `25%` for
+//! a percentage flag, and `blue:50,red:50` for a variant flag.
+//!
+//! # Evaluation
+//!
+//! A flag evaluates off for every key when it is switched off, then off for
+//! denied keys, then as allowed or targeted, and otherwise by its type:
+//! boolean flags are on, percentage flags are on for that share of keys, and
+//! variant flags pick a variant in proportion to its weight. Keys are
+//! bucketed by the SHA-1 hash of `{salt}:{key}`, so native and WebAssembly
+//! clients assign every key the same result. Each evaluation is delivered to
+//! exposure listeners, for experiment analysis.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use apollo_rust_client::{Client, client_config::ClientConfig};
+//! use std::sync::Arc;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = ClientConfig::builder("my-app", "http://apollo-server:8080").build()?;
+//! let client = Client::new(config)?;
+//!
+//! let flags = client.feature_flags("flags.json").await?;
+//! let _exposures = flags.add_exposure_listener(Arc::new(|evaluation| {
+//!     println!("{} saw {:?}", evaluation.key, evaluation.variant);
+//! }));
+//! if flags.is_enabled("checkout.v2", "user-42") {
+//!     // new checkout
+//! }
+//! let color = flags.variant("button.color", "user-42");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    Error, Subscription,
+    cache::Cache,
+    subscription::{ListenerRegistry, Notification, Notifier},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha1::{Digest, Sha1};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{Arc, PoisonError, RwLock},
+};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        /// Listener for the evaluations of a [`FeatureFlags`] handle.
+        pub type ExposureListener = Arc<dyn Fn(&Evaluation)>;
+
+        /// Listener for the names of the flags that changed.
+        pub type FlagsListener = Arc<dyn Fn(&[String])>;
+    } else {
+        /// Listener for the evaluations of a [`FeatureFlags`] handle.
+        ///
+        /// Listeners run synchronously on the evaluating thread, so they
+        /// should only record or enqueue the exposure.
+        pub type ExposureListener = Arc<dyn Fn(&Evaluation) + Send + Sync>;
+
+        /// Listener for the names of the flags that changed.
+        pub type FlagsListener = Arc<dyn Fn(&[String]) + Send + Sync>;
+    }
+}
+
+/// Why a flag evaluated as it did.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EvaluationReason {
+    /// No flag has this name.
+    NotFound,
+    /// The flag definition is invalid; see [`FeatureFlags::errors`].
+    Invalid,
+    /// The flag is switched off for every key.
+    Off,
+    /// The key is on the flag's deny list.
+    Denied,
+    /// The key is on the flag's allow list or assigned to a variant.
+    Targeted,
+    /// A boolean flag is on for every key.
+    On,
+    /// The key's bucket decided a percentage or variant flag.
+    Rollout,
+}
+
+/// The result of evaluating a flag for a key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Evaluation {
+    /// Name of the evaluated flag.
+    pub flag: String,
+    /// User or context key the flag was evaluated for.
+    pub key: String,
+    /// Whether the flag is on for the key.
+    pub enabled: bool,
+    /// Variant served to the key, for variant flags. Keys a variant flag is
+    /// off for receive its default variant, if any.
+    pub variant: Option<String>,
+    /// Why the flag evaluated this way.
+    pub reason: EvaluationReason,
+}
+
+/// A parsed flag definition.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+struct Definition {
+    #[serde(default = "enabled_by_default")]
+    enabled: bool,
+    #[serde(default)]
+    deny: BTreeSet<String>,
+    salt: Option<String>,
+    #[serde(flatten)]
+    kind: Kind,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Kind {
+    Boolean {
+        #[serde(default)]
+        allow: BTreeSet<String>,
+    },
+    Percentage {
+        percentage: f64,
+        #[serde(default)]
+        allow: BTreeSet<String>,
+    },
+    Variant {
+        variants: BTreeMap<String, u32>,
+        default: Option<String>,
+        #[serde(default)]
+        targets: BTreeMap<String, String>,
+    },
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+/// Flag definitions by name, or why a definition was rejected.
+type FlagSet = BTreeMap<String, Result<Definition, String>>;
+
+/// Where the definitions are read from.
+#[derive(Clone, Debug)]
+enum Source {
+    /// The document of a JSON or YAML namespace.
+    Document,
+    /// Properties keys starting with a prefix.
+    Properties(String),
+}
+
+/// Current definitions and the flags changed by the latest update.
+struct State {
+    source: Source,
+    flags: RwLock<Arc<FlagSet>>,
+    changed: RwLock<Vec<String>>,
+}
+
+struct Inner {
+    state: Arc<State>,
+    cache: Arc<Cache>,
+    exposures: Arc<ListenerRegistry<ExposureListener>>,
+    _subscription: Subscription,
+}
+
+/// Feature flags read from a namespace and kept up to date.
+///
+/// Cloning is cheap and clones share definitions and exposure listeners.
+/// The handle follows the namespace until its last clone is dropped.
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct FeatureFlags {
+    inner: Arc<Inner>,
+}
+
+impl std::fmt::Debug for FeatureFlags {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("FeatureFlags")
+            .field("namespace", &self.inner.cache.namespace())
+            .field("flags", &self.flag_names())
+            .finish_non_exhaustive()
+    }
+}
+
+impl FeatureFlags {
+    /// Loads definitions from a JSON or YAML namespace.
+    pub(crate) async fn from_document(cache: Arc<Cache>) -> Result<Self, Error> {
+        if !crate::namespace::is_document(cache.namespace()) {
+            return Err(Error::InvalidFlags {
+                namespace: cache.namespace().to_string(),
+                reason: "flag definitions need a JSON or YAML namespace, or a prefix".to_string(),
+            });
+        }
+        Self::load(cache, Source::Document).await
+    }
+
+    /// Loads definitions from the Properties keys starting with `prefix`.
+    pub(crate) async fn from_properties(cache: Arc<Cache>, prefix: &str) -> Result<Self, Error> {
+        if !crate::namespace::is_properties(cache.namespace()) {
+            return Err(Error::InvalidFlags {
+                namespace: cache.namespace().to_string(),
+                reason: "prefixed flag definitions need a Properties namespace".to_string(),
+            });
+        }
+        Self::load(cache, Source::Properties(prefix.to_string())).await
+    }
+
+    async fn load(cache: Arc<Cache>, source: Source) -> Result<Self, Error> {
+        let namespace = cache.namespace().to_string();
+        let flags = parse(&namespace, &source, &cache.get_value().await?).map_err(|reason| {
+            Error::InvalidFlags {
+                namespace: namespace.clone(),
+                reason,
+            }
+        })?;
+        let state = Arc::new(State {
+            source,
+            flags: RwLock::new(Arc::new(flags)),
+            changed: RwLock::new(Vec::new()),
+        });
+
+        // Replaying the current value closes the gap between the load above
+        // and registration.
+        let updater = state.clone();
+        let notifier: Notifier = Arc::new(move |notification| {
+            if let Notification::Changed { config, event } = notification {
+                updater.update(event.namespace(), config);
+            }
+        });
+        let subscription = cache
+            .subscribe(
+                notifier,
+                crate::ListenerOptions::default().replay_current(true),
+            )
+            .await;
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                state,
+                cache,
+                exposures: Arc::new(ListenerRegistry::default()),
+                _subscription: subscription,
+            }),
+        })
+    }
+
+    /// Evaluates a flag for a user or context key and notifies exposure
+    /// listeners.
+    #[must_use]
+    pub fn evaluate(&self, flag: &str, key: &str) -> Evaluation {
+        let (enabled, variant, reason) = match self.inner.state.current().get(flag) {
+            None => (false, None, EvaluationReason::NotFound),
+            Some(Err(_)) => (false, None, EvaluationReason::Invalid),
+            Some(Ok(definition)) => definition.evaluate(flag, key),
+        };
+        let evaluation = Evaluation {
+            flag: flag.to_string(),
+            key: key.to_string(),
+            enabled,
+            variant,
+            reason,
+        };
+        for listener in self.inner.exposures.snapshot() {
+            listener(&evaluation);
+        }
+        evaluation
+    }
+
+    /// Registers a listener for every evaluation through this handle and its
+    /// clones.
+    ///
+    /// The listener stays registered while the returned [`Subscription`] is
+    /// alive.
+    pub fn add_exposure_listener(&self, listener: ExposureListener) -> Subscription {
+        self.inner.exposures.register(listener)
+    }
+
+    /// Registers a listener for definition changes.
+    ///
+    /// The listener receives the names of the flags that were added,
+    /// removed, or redefined, after the new definitions are in effect. The
+    /// listener stays registered while the returned [`Subscription`] is
+    /// alive.
+    pub async fn add_listener(&self, listener: FlagsListener) -> Subscription {
+        let state = self.inner.state.clone();
+        let notifier: Notifier = Arc::new(move |notification| {
+            if let Notification::Changed { .. } = notification {
+                let changed = state
+                    .changed
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .clone();
+                if !changed.is_empty() {
+                    listener(&changed);
+                }
+            }
+        });
+        self.inner
+            .cache
+            .subscribe(notifier, crate::ListenerOptions::default())
+            .await
+    }
+
+    /// Returns the invalid definitions with the reason each was rejected.
+    ///
+    /// Invalid flags evaluate off with [`EvaluationReason::Invalid`].
+    #[must_use]
+    pub fn errors(&self) -> BTreeMap<String, String> {
+        self.inner
+            .state
+            .current()
+            .iter()
+            .filter_map(|(flag, definition)| {
+                definition
+                    .as_ref()
+                    .err()
+                    .map(|error| (flag.clone(), error.clone()))
+            })
+            .collect()
+    }
+}
+
+#[wasm_bindgen]
+impl FeatureFlags {
+    /// Returns whether a flag is on for a user or context key.
+    ///
+    /// Notifies exposure listeners like [`FeatureFlags::evaluate`].
+    #[must_use]
+    #[wasm_bindgen(js_name = "isEnabled")]
+    pub fn is_enabled(&self, flag: &str, key: &str) -> bool {
+        self.evaluate(flag, key).enabled
+    }
+
+    /// Returns the variant a variant flag serves to a user or context key.
+    ///
+    /// Notifies exposure listeners like [`FeatureFlags::evaluate`].
+    #[must_use]
+    pub fn variant(&self, flag: &str, key: &str) -> Option<String> {
+        self.evaluate(flag, key).variant
+    }
+
+    /// Returns the names of the defined flags, including invalid ones.
+    #[must_use]
+    #[wasm_bindgen(js_name = "flagNames")]
+    pub fn flag_names(&self) -> Vec<String> {
+        self.inner.state.current().keys().cloned().collect()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl FeatureFlags {
+    /// Evaluates a flag for JavaScript, returning a plain object with
+    /// `flag`, `key`, `enabled`, `variant` and `reason` fields.
+    #[must_use]
+    #[wasm_bindgen(js_name = "evaluate")]
+    pub fn evaluate_wasm(&self, flag: &str, key: &str) -> wasm_bindgen::JsValue {
+        to_js_value(&self.evaluate(flag, key))
+    }
+
+    /// Registers a JavaScript exposure listener called with each
+    /// evaluation object. Call `unsubscribe()` on the returned handle to
+    /// remove it.
+    #[wasm_bindgen(js_name = "addExposureListener")]
+    pub fn add_exposure_listener_wasm(&self, js_listener: js_sys::Function) -> Subscription {
+        self.add_exposure_listener(Arc::new(move |evaluation| {
+            if let Err(error) =
+                js_listener.call1(&wasm_bindgen::JsValue::UNDEFINED, &to_js_value(evaluation))
+            {
+                log::error!("JavaScript exposure listener threw an error: {error:?}");
+            }
+        }))
+        .into_explicit()
+    }
+
+    /// Registers a JavaScript listener called with an array of the changed
+    /// flag names. Call `unsubscribe()` on the returned handle to remove it.
+    #[wasm_bindgen(js_name = "addListener")]
+    pub async fn add_listener_wasm(&self, js_listener: js_sys::Function) -> Subscription {
+        self.add_listener(Arc::new(move |changed| {
+            let changed: js_sys::Array = changed
+                .iter()
+                .map(|flag| wasm_bindgen::JsValue::from_str(flag))
+                .collect();
+            if let Err(error) = js_listener.call1(&wasm_bindgen::JsValue::UNDEFINED, &changed) {
+                log::error!("JavaScript flag listener threw an error: {error:?}");
+            }
+        }))
+        .await
+        .into_explicit()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn to_js_value(evaluation: &Evaluation) -> wasm_bindgen::JsValue {
+    evaluation
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or_else(|error| {
+            log::error!("Unable to serialize a flag evaluation for JavaScript: {error}");
+            wasm_bindgen::JsValue::NULL
+        })
+}
+
+impl State {
+    fn current(&self) -> Arc<FlagSet> {
+        self.flags
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replaces the definitions, recording which flags changed.
+    ///
+    /// A value that does not hold definitions keeps the previous ones.
+    fn update(&self, namespace: &str, config: &Value) {
+        let flags = match parse(namespace, &self.source, config) {
+            Ok(flags) => flags,
+            Err(error) => {
+                log::warn!("Keeping the previous feature flags of namespace {namespace}: {error}");
+                return;
+            }
+        };
+        let mut current = self.flags.write().unwrap_or_else(PoisonError::into_inner);
+        let changed = current
+            .keys()
+            .chain(flags.keys())
+            .filter(|flag| current.get(*flag) != flags.get(*flag))
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        for (flag, definition) in &flags {
+            if let Err(error) = definition
+                && changed.contains(flag)
+            {
+                log::warn!("Invalid feature flag {flag} in namespace {namespace}: {error}");
+            }
+        }
+        *current = Arc::new(flags);
+        *self.changed.write().unwrap_or_else(PoisonError::into_inner) =
+            changed.into_iter().collect();
+    }
+}
+
+impl Definition {
+    fn evaluate(&self, flag: &str, key: &str) -> (bool, Option<String>, EvaluationReason) {
+        let default = match &self.kind {
+            Kind::Variant { default, .. } => default.clone(),
+            Kind::Boolean { .. } | Kind::Percentage { .. } => None,
+        };
+        if !self.enabled {
+            return (false, default, EvaluationReason::Off);
+        }
+        if self.deny.contains(key) {
+            return (false, default, EvaluationReason::Denied);
+        }
+        let bucket = bucket(self.salt.as_deref().unwrap_or(flag), key);
+        match &self.kind {
+            Kind::Boolean { allow } if allow.contains(key) => {
+                (true, None, EvaluationReason::Targeted)
+            }
+            Kind::Boolean { .. } => (true, None, EvaluationReason::On),
+            Kind::Percentage { allow, .. } if allow.contains(key) => {
+                (true, None, EvaluationReason::Targeted)
+            }
+            Kind::Percentage { percentage, .. } => {
+                // Buckets of a thousandth of a percent allow fractional rollouts.
+                let position = u32::try_from(bucket % 100_000).unwrap_or_default();
+                (
+                    f64::from(position) < percentage * 1000.0,
+                    None,
+                    EvaluationReason::Rollout,
+                )
+            }
+            Kind::Variant { targets, .. } if targets.contains_key(key) => {
+                (true, targets.get(key).cloned(), EvaluationReason::Targeted)
+            }
+            Kind::Variant { variants, .. } => {
+                let total: u64 = variants.values().map(|weight| u64::from(*weight)).sum();
+                let mut position = bucket % total;
+                let variant = variants.iter().find(|(_, weight)| {
+                    let weight = u64::from(**weight);
+                    if position < weight {
+                        true
+                    } else {
+                        position -= weight;
+                        false
+                    }
+                });
+                (
+                    true,
+                    variant.map(|(name, _)| name.clone()),
+                    EvaluationReason::Rollout,
+                )
+            }
+        }
+    }
+
+    fn validate(self) -> Result<Self, String> {
+        match &self.kind {
+            Kind::Boolean { .. } => {}
+            Kind::Percentage { percentage, .. } => {
+                if !(0.0..=100.0).contains(percentage) {
+                    return Err(format!("percentage {percentage} is not between 0 and 100"));
+                }
+            }
+            Kind::Variant {
+                variants,
+                default,
+                targets,
+            } => {
+                if variants.values().all(|weight| *weight == 0) {
+                    return Err("a variant flag needs a variant with a positive weight".to_string());
+                }
+                for variant in default.iter().chain(targets.values()) {
+                    if !variants.contains_key(variant) {
+                        return Err(format!("unknown variant {variant:?}"));
+                    }
+                }
+            }
+        }
+        Ok(self)
+    }
+}
+
+/// Returns the bucket of `key`, a number identical on every platform.
+fn bucket(salt: &str, key: &str) -> u64 {
+    let digest = Sha1::digest(format!("{salt}:{key}").as_bytes());
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bytes)
+}
+
+/// Reads the flag definitions from a raw cached value.
+fn parse(namespace: &str, source: &Source, config: &Value) -> Result<FlagSet, String> {
+    match source {
+        Source::Document => {
+            let document = crate::namespace::document_tree(namespace, config)
+                .ok_or_else(|| "the namespace content does not parse".to_string())?;
+            let Value::Object(flags) = document else {
+                return Err("the document is not an object of flag definitions".to_string());
+            };
+            Ok(flags
+                .into_iter()
+                .map(|(flag, definition)| (flag, definition_from_value(definition)))
+                .collect())
+        }
+        Source::Properties(prefix) => {
+            let Value::Object(properties) = config else {
+                return Err("the namespace is not a set of properties".to_string());
+            };
+            Ok(properties
+                .iter()
+                .filter_map(|(key, value)| {
+                    let flag = key.strip_prefix(prefix.as_str())?;
+                    let definition = match value {
+                        Value::String(value) => definition_from_str(value),
+                        value => definition_from_value(value.clone()),
+                    };
+                    Some((flag.to_string(), definition))
+                })
+                .collect())
+        }
+    }
+}
+
+fn definition_from_value(value: Value) -> Result<Definition, String> {
+    serde_json::from_value::<Definition>(value)
+        .map_err(|error| error.to_string())
+        .and_then(Definition::validate)
+}
+
+/// Parses a Properties value: a JSON definition or a shorthand.
+fn definition_from_str(value: &str) -> Result<Definition, String> {
+    let value = value.trim();
+    if value.starts_with('{') {
+        return serde_json::from_str(value)
+            .map_err(|error| error.to_string())
+            .and_then(definition_from_value);
+    }
+    let kind = if let Ok(enabled) = value.to_ascii_lowercase().parse::<bool>() {
+        return Ok(Definition {
+            enabled,
+            deny: BTreeSet::new(),
+            salt: None,
+            kind: Kind::Boolean {
+                allow: BTreeSet::new(),
+            },
+        });
+    } else if let Some(percentage) = value.strip_suffix('%') {
+        Kind::Percentage {
+            percentage: percentage
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid percentage {value:?}"))?,
+            allow: BTreeSet::new(),
+        }
+    } else if value.contains(':') {
+        let variants = value
+            .split(',')
+            .map(|variant| {
+                let (name, weight) = variant
+                    .split_once(':')
+                    .ok_or_else(|| format!("variant {variant:?} has no weight"))?;
+                let weight = weight
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("invalid weight in variant {variant:?}"))?;
+                Ok((name.trim().to_string(), weight))
+            })
+            .collect::<Result<_, String>>()?;
+        Kind::Variant {
+            variants,
+            default: None,
+            targets: BTreeMap::new(),
+        }
+    } else {
+        return Err(format!("unrecognized flag definition {value:?}"));
+    };
+    Definition {
+        enabled: true,
+        deny: BTreeSet::new(),
+        salt: None,
+        kind,
+    }
+    .validate()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn definition(value: Value) -> Definition {
+        definition_from_value(value).unwrap()
+    }
+
+    #[test]
+    fn evaluation_follows_switches_lists_and_buckets() {
+        let boolean = definition(json!({"type": "boolean", "deny": ["blocked"], "allow": ["vip"]}));
+        assert_eq!(
+            boolean.evaluate("f", "anyone"),
+            (true, None, EvaluationReason::On)
+        );
+        assert_eq!(
+            boolean.evaluate("f", "vip"),
+            (true, None, EvaluationReason::Targeted)
+        );
+        assert_eq!(
+            boolean.evaluate("f", "blocked"),
+            (false, None, EvaluationReason::Denied)
+        );
+
+        let off = definition(
+            json!({"type": "variant", "enabled": false, "variants": {"a": 1}, "default": "a"}),
+        );
+        assert_eq!(
+            off.evaluate("f", "anyone"),
+            (false, Some("a".to_string()), EvaluationReason::Off)
+        );
+
+        let half = definition(json!({"type": "percentage", "percentage": 50}));
+        let enabled = (0..10_000)
+            .filter(|user| half.evaluate("f", &format!("user-{user}")).0)
+            .count();
+        assert!(
+            (4_700..5_300).contains(&enabled),
+            "{enabled} of 10000 enabled"
+        );
+        // Buckets are pinned so every platform and release agrees.
+        assert_eq!(bucket("checkout", "user-42"), 0xa541_3f2d_36f6_21f1);
+
+        let variants = definition(json!({
+            "type": "variant",
+            "variants": {"blue": 1, "red": 3},
+            "targets": {"vip": "blue"},
+        }));
+        let blue = (0..10_000)
+            .filter(|user| {
+                variants.evaluate("f", &format!("user-{user}")).1.as_deref() == Some("blue")
+            })
+            .count();
+        assert!((2_200..2_800).contains(&blue), "{blue} of 10000 blue");
+        assert_eq!(
+            variants.evaluate("f", "vip"),
+            (true, Some("blue".to_string()), EvaluationReason::Targeted)
+        );
+    }
+
+    #[test]
+    fn properties_accept_shorthands_and_report_invalid_definitions() {
+        let config = json!({
+            "feature.dark-mode": "true",
+            "feature.ranker": "12.5%",
+            "feature.color": "blue:1, red:1",
+            "feature.beta": r#"{"type": "boolean", "enabled": false}"#,
+            "feature.broken": "150%",
+            "other.key": "true",
+        });
+        let flags = parse(
+            "application",
+            &Source::Properties("feature.".to_string()),
+            &config,
+        )
+        .unwrap();
+        assert_eq!(
+            flags.keys().collect::<Vec<_>>(),
+            ["beta", "broken", "color", "dark-mode", "ranker"]
+        );
+        assert!(
+            matches!(flags["ranker"], Ok(Definition { kind: Kind::Percentage { percentage, .. }, .. }) if (percentage - 12.5).abs() < f64::EPSILON)
+        );
+        assert!(
+            matches!(&flags["color"], Ok(Definition { kind: Kind::Variant { variants, .. }, .. }) if variants.len() == 2)
+        );
+        assert!(matches!(
+            flags["beta"],
+            Ok(Definition { enabled: false, .. })
+        ));
+        assert_eq!(
+            flags["broken"],
+            Err("percentage 150 is not between 0 and 100".to_string())
+        );
+        assert!(
+            definition_from_value(json!({"type": "variant", "variants": {"a": 1}, "default": "b"}))
+                .is_err()
+        );
+    }
+}
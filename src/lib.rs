@@ -15,7 +15,7 @@
 //! - **Multiple Configuration Formats**: Support for Properties, JSON, YAML, and Text formats with automatic detection
 //! - **Cross-Platform**: Native Rust and WebAssembly targets with platform-specific optimizations
 //! - **Periodic Updates**: Background polling with configurable intervals and event listeners
-//! - **Comprehensive Caching**: Multi-level caching with file persistence (native) and persistent localStorage caching with high-performance Node.js in-memory fallback (WASM)
+//! - **Comprehensive Caching**: Multi-level caching with file persistence (native and Node.js) and persistent localStorage caching (browsers)
 //! - **Type-Safe API**: Compile-time guarantees and runtime type conversion
 //! - **Error Handling**: Detailed error diagnostics with comprehensive error types
 //! - **Grayscale Release Support**: IP and label-based configuration targeting
@@ -28,7 +28,7 @@
 //! # #[tokio::main(flavor = "current_thread")]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! let config = ClientConfig::builder("my-app", "http://apollo-server:8080").build()?;
-//! let mut client = Client::new(config)?;
+//! let client = Client::new(config)?;
 //! client.start().await?;
 //!
 //! let namespace = client.namespace("application").await?;
@@ -41,11 +41,11 @@
 //! The library supports different behavior for wasm32 and non-wasm32 targets:
 //!
 //! - **Native Rust**: Full feature set with file caching, background tasks, and threading
-//! - **WebAssembly**: Persistent localStorage caching with high-performance Node.js in-memory fallback, single-threaded execution, JavaScript interop
+//! - **WebAssembly**: Persistent localStorage caching in browsers, `fs` file caching under Node.js, single-threaded execution, JavaScript interop
 
 use crate::namespace::Namespace;
 use cache::Cache;
-use client_config::ClientConfig;
+use client_config::{ClientConfig, Coordinates};
 use futures::{StreamExt, stream};
 use log::{error, trace};
 use std::{
@@ -81,11 +81,40 @@ cfg_if::cfg_if! {
 
 mod cache;
 
-#[cfg(all(test, not(target_arch = "wasm32")))]
+#[cfg(all(any(test, feature = "test-util"), not(target_arch = "wasm32")))]
 mod test_support;
 
+pub mod change;
 pub mod client_config;
+pub mod composite;
+pub mod diagnostics;
+#[cfg(all(any(test, feature = "emulator"), not(target_arch = "wasm32")))]
+pub mod emulator;
+#[cfg(all(feature = "exec", not(target_arch = "wasm32")))]
+pub mod exec;
+pub mod flags;
+pub mod live;
 pub mod namespace;
+pub mod overrides;
+mod placeholders;
+pub mod readiness;
+pub mod subscription;
+#[cfg(all(any(test, feature = "test-util"), not(target_arch = "wasm32")))]
+pub mod test_util;
+pub mod typed;
+mod watch;
+
+#[cfg(feature = "derive")]
+pub use apollo_rust_client_derive::ApolloConfig;
+pub use change::{ConfigChange, ConfigChangeEvent, KeyChange, PropertyChangeType};
+pub use composite::{CompositeConfig, CompositeSnapshot};
+pub use diagnostics::{Diagnosis, SignatureStatus};
+pub use flags::{Evaluation, EvaluationReason, FeatureFlags};
+pub use live::LiveConfig;
+pub use overrides::{ActiveOverride, OverrideSource};
+pub use readiness::{NamespaceReadiness, NamespaceStatus, ReadinessPolicy, ReadinessReport};
+pub use subscription::{ListenerOptions, Subscription};
+pub use typed::ApolloConfig;
 
 /// Comprehensive error types that can occur when using the Apollo client.
 ///
@@ -166,6 +195,57 @@ pub enum Error {
     /// own owned error value without erasing the primary operation's error.
     #[error("Background refresh failed: {0}")]
     Refresh(String),
+
+    /// A [`Client::watch`] stream skipped intermediate updates.
+    ///
+    /// The stream yields this before the latest value when the consumer fell
+    /// behind; the count covers skipped values and refresh errors.
+    #[error("Watch stream skipped {0} intermediate updates")]
+    Lagged(u64),
+
+    /// A namespace could not be deserialized into a bound type.
+    ///
+    /// Returned by [`Client::bind`] and delivered to [`LiveConfig`] listeners
+    /// when an update is rejected.
+    #[error("Failed to deserialize bound configuration: {0}")]
+    Deserialize(String),
+
+    /// [`Client::shutdown`] cancelled work that was still running at its
+    /// deadline.
+    #[error("Client shutdown did not finish within {0:?}")]
+    ShutdownTimeout(std::time::Duration),
+
+    /// A local override key cannot address a value of its namespace.
+    #[error("Invalid override of {key:?} in namespace {namespace}: {reason}")]
+    InvalidOverride {
+        /// The namespace name.
+        namespace: String,
+        /// The rejected key.
+        key: String,
+        /// Why the key was rejected.
+        reason: String,
+    },
+
+    /// A namespace does not hold feature flag definitions.
+    #[error("Invalid feature flags in namespace {namespace}: {reason}")]
+    InvalidFlags {
+        /// The namespace name.
+        namespace: String,
+        /// Why the definitions were rejected.
+        reason: String,
+    },
+
+    /// A namespace could not be loaded into an [`ApolloConfig`] struct.
+    ///
+    /// Returned by [`Client::load_config`]; the error lists every missing or
+    /// invalid key.
+    #[error("Invalid configuration in namespace {namespace}: {source}")]
+    InvalidConfig {
+        /// The namespace name.
+        namespace: String,
+        /// The keys that could not be read.
+        source: typed::Error,
+    },
 }
 
 impl From<Error> for wasm_bindgen::JsValue {
@@ -189,6 +269,9 @@ cfg_if::cfg_if! {
         /// For WASM targets, listeners don't need to be Send + Sync since WASM is single-threaded.
         /// Listeners receive `Result<Namespace, Error>`.
         pub type EventListener = Arc<dyn Fn(Result<Namespace, Error>)>;
+
+        /// Type alias for change listeners, which receive the keys that changed.
+        pub type ChangeListener = Arc<dyn Fn(Result<ConfigChangeEvent, Error>)>;
     } else {
         /// Type alias for event listeners that can be registered with the client.
         /// For native targets, listeners need to be `Send` and `Sync` to be safely
@@ -197,19 +280,29 @@ cfg_if::cfg_if! {
         /// Listeners receive `Result<Namespace, Error>`, containing either a fresh
         /// typed namespace or an owned refresh error.
         pub type EventListener = Arc<dyn Fn(Result<Namespace, Error>) + Send + Sync>;
+
+        /// Type alias for change listeners that can be registered with the client.
+        ///
+        /// Listeners receive `Result<ConfigChangeEvent, Error>`, listing the keys
+        /// that changed, or an owned refresh error.
+        pub type ChangeListener = Arc<dyn Fn(Result<ConfigChangeEvent, Error>) + Send + Sync>;
     }
 }
 
 #[cfg(target_arch = "wasm32")]
-fn check_local_storage_availability() -> bool {
+pub(crate) fn check_local_storage_availability() -> bool {
     let global = js_sys::global();
-    let Ok(storage) = js_sys::Reflect::get(&global, &wasm_bindgen::JsValue::from_str("localStorage")) else {
+    let Ok(storage) =
+        js_sys::Reflect::get(&global, &wasm_bindgen::JsValue::from_str("localStorage"))
+    else {
         return false;
     };
     if storage.is_undefined() || storage.is_null() {
         return false;
     }
-    let Ok(get_item_fn) = js_sys::Reflect::get(&storage, &wasm_bindgen::JsValue::from_str("getItem")) else {
+    let Ok(get_item_fn) =
+        js_sys::Reflect::get(&storage, &wasm_bindgen::JsValue::from_str("getItem"))
+    else {
         return false;
     };
     get_item_fn.is_function()
@@ -228,6 +321,8 @@ fn check_local_storage_availability() -> bool {
 /// - **Event Listeners**: Support for registering callbacks on configuration changes
 /// - **Cross-Platform**: Works on both native Rust and WebAssembly targets
 /// - **Thread Safety**: All operations are thread-safe and async-friendly
+/// - **Cheap Clones**: Clones share namespaces and background polling, which
+///   stops when the last clone is dropped or [`Client::shutdown`] is called
 ///
 /// # Examples
 ///
@@ -254,6 +349,7 @@ fn check_local_storage_availability() -> bool {
 /// ```
 #[cfg_attr(target_arch = "wasm32", allow(clippy::arc_with_non_send_sync))]
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct Client {
     /// The configuration settings for this Apollo client instance.
     ///
@@ -268,28 +364,173 @@ pub struct Client {
     /// The outer `Arc` allows the background refresh task to safely access the namespaces.
     namespaces: Arc<RwLock<HashMap<String, Arc<Cache>>>>,
 
-    /// Handle to the background refresh task (native targets only).
+    /// Background polling state shared by every clone.
+    background: Arc<Background>,
+
+    /// Prefix of this handle's keys in `namespaces`, or `None` for the
+    /// client's own coordinates.
     ///
-    /// On native targets, this holds the `JoinHandle` for periodic polling. WASM
-    /// stores a separate `AbortHandle` for its `spawn_local` future.
-    #[cfg(not(target_arch = "wasm32"))]
-    handle: Option<tokio::task::JoinHandle<()>>,
+    /// Scoped keys take the form `{app_id}+{cluster}+{namespace}`, as in Java
+    /// cache file names. Apollo identifiers cannot contain `+`, so scoped keys
+    /// never collide with plain namespace names.
+    scope: Option<Arc<str>>,
 
-    /// Cancellation handle for the WASM refresh future.
-    #[cfg(target_arch = "wasm32")]
-    abort_handle: Option<futures::future::AbortHandle>,
+    /// HTTP client for making network requests.
+    ///
+    /// Shared across all caches to allow connection pooling and reduce overhead.
+    #[allow(clippy::struct_field_names)]
+    http_client: reqwest::Client,
+
+    /// Local overrides shared by every clone and coordinate handle.
+    overrides: Arc<overrides::Overrides>,
+
+    /// Placeholder resolution shared by every clone and coordinate handle.
+    placeholders: Arc<placeholders::Placeholders>,
+
+    /// Watcher applying edits of the override file (native targets with the
+    /// `override-watch` feature only).
+    #[cfg(all(not(target_arch = "wasm32"), feature = "override-watch"))]
+    _override_watcher: Option<Arc<overrides::OverrideWatcher>>,
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        type TaskAbortHandle = futures::future::AbortHandle;
+    } else {
+        type TaskAbortHandle = tokio::task::AbortHandle;
+    }
+}
 
-    /// Flag indicating whether the background refresh task is active.
+/// Background polling shared by the clones of a [`Client`].
+///
+/// Polling is cancelled when the last clone is dropped.
+struct Background {
+    /// Whether the polling task should keep running.
     ///
     /// Wrapped in `Arc<AtomicBool>` for lock-free coordination between the client
     /// and its background task.
     running: Arc<AtomicBool>,
 
-    /// HTTP client for making network requests.
+    /// Namespaces whose shared-cache leadership is released when polling stops
+    /// (native targets with the `shared-cache` feature only).
+    #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+    namespaces: Arc<RwLock<HashMap<String, Arc<Cache>>>>,
+
+    /// The active polling task, if any.
+    task: std::sync::Mutex<Option<BackgroundTask>>,
+}
+
+/// Handles for one run of the polling task.
+struct BackgroundTask {
+    /// Wakes the task from its interval sleep so it can exit.
+    wake: Arc<tokio::sync::Notify>,
+
+    /// Cancels the task outright.
+    abort_handle: TaskAbortHandle,
+
+    /// Resolves when the task exits (native targets only).
+    #[cfg(not(target_arch = "wasm32"))]
+    handle: tokio::task::JoinHandle<()>,
+
+    /// Resolves when the `spawn_local` future exits (wasm32 targets only).
+    #[cfg(target_arch = "wasm32")]
+    finished: tokio::sync::oneshot::Receiver<()>,
+
+    /// Cache-directory watcher active while polling in shared-cache mode
+    /// (native targets with the `shared-cache` feature only).
+    #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+    _cache_watcher: Option<cache::CacheWatcher>,
+}
+
+impl Background {
+    #[cfg_attr(
+        any(target_arch = "wasm32", not(feature = "shared-cache")),
+        allow(unused_variables, clippy::needless_pass_by_value)
+    )]
+    fn new(namespaces: Arc<RwLock<HashMap<String, Arc<Cache>>>>) -> Self {
+        Self {
+            running: Arc::new(AtomicBool::new(false)),
+            #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+            namespaces,
+            task: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Marks polling as stopped and takes the task handles.
+    fn take(&self) -> Option<BackgroundTask> {
+        self.running.store(false, Ordering::Release);
+        self.task
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take()
+    }
+
+    /// Stops polling immediately, cancelling any refresh in flight.
+    fn abort(&self) {
+        if let Some(task) = self.take() {
+            task.abort_handle.abort();
+            #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+            self.release_leadership();
+        }
+    }
+
+    /// Lets sibling processes take over polling in shared-cache mode.
     ///
-    /// Shared across all caches to allow connection pooling and reduce overhead.
-    #[allow(clippy::struct_field_names)]
-    http_client: reqwest::Client,
+    /// If the namespace map is being updated, the release finishes on a
+    /// runtime task once the update is done.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+    fn release_leadership(&self) {
+        if let Ok(namespaces) = self.namespaces.try_read() {
+            for cache in namespaces.values() {
+                cache.release_leadership();
+            }
+            return;
+        }
+        let namespaces = self.namespaces.clone();
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    for cache in namespaces.read().await.values() {
+                        cache.release_leadership();
+                    }
+                });
+            }
+            Err(_) => log::warn!(
+                "Not releasing shared-cache leadership while namespaces are being updated; \
+                 sibling processes take over when this process exits"
+            ),
+        }
+    }
+}
+
+impl Drop for Background {
+    fn drop(&mut self) {
+        self.abort();
+    }
+}
+
+impl BackgroundTask {
+    /// Asks the task to exit after its current refreshes.
+    ///
+    /// Returns a handle that cancels the task outright and a future that
+    /// resolves once it has exited.
+    fn stop(self) -> (TaskAbortHandle, impl Future<Output = ()>) {
+        self.wake.notify_one();
+        cfg_if::cfg_if! {
+            if #[cfg(target_arch = "wasm32")] {
+                let finished = self.finished;
+                let finished = async move {
+                    let _ = finished.await;
+                };
+            } else {
+                let handle = self.handle;
+                let finished = async move {
+                    let _ = handle.await;
+                };
+            }
+        }
+        (self.abort_handle, finished)
+    }
 }
 
 impl Client {
@@ -303,30 +544,395 @@ impl Client {
     ///
     /// A cache for the given namespace.
     pub(crate) async fn cache(&self, namespace: &str) -> Arc<Cache> {
-        if let Some(cache) = self.namespaces.read().await.get(namespace).cloned() {
+        let key = self.cache_key(namespace);
+        if let Some(cache) = self.namespaces.read().await.get(key.as_ref()).cloned() {
+            cache.touch();
             return cache;
         }
 
         let mut namespaces = self.namespaces.write().await;
-        let cache = namespaces.entry(namespace.to_string()).or_insert_with(|| {
+        let cache = namespaces.entry(key.into_owned()).or_insert_with(|| {
             trace!("Cache miss, creating cache for namespace {namespace}");
-            Arc::new(Cache::new(
-                self.config.clone(),
-                namespace,
-                self.http_client.clone(),
-            ))
+            Arc::new(
+                Cache::new(self.config.clone(), namespace, self.http_client.clone())
+                    .with_overrides(self.overrides.clone())
+                    .with_placeholders(self.placeholders.clone()),
+            )
         });
+        cache.touch();
         cache.clone()
     }
 
+    /// Returns the key of `namespace` in the shared namespace map.
+    fn cache_key<'a>(&self, namespace: &'a str) -> std::borrow::Cow<'a, str> {
+        match &self.scope {
+            Some(scope) => std::borrow::Cow::Owned(format!("{scope}{namespace}")),
+            None => std::borrow::Cow::Borrowed(namespace),
+        }
+    }
+
+    /// Returns a handle that addresses namespaces by other Apollo coordinates.
+    ///
+    /// The handle reads namespaces published under another app id or pinned to
+    /// another cluster, such as a public namespace owned by a platform team. It
+    /// shares this client's HTTP connection pool, background polling, and
+    /// lifecycle, and its namespaces are cached under their own coordinates.
+    /// Every namespace method works on the handle as on the client. Handles
+    /// with this client's app id and cluster share its caches.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use apollo_rust_client::{Client, client_config::{ClientConfig, Coordinates}};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = ClientConfig::builder("my-app", "http://apollo-server:8080").build()?;
+    /// let client = Client::new(config)?;
+    ///
+    /// let platform = client.with_coordinates(Coordinates::new("platform-app"))?;
+    /// let shared = platform.namespace("platform.common").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] if the app id or cluster is empty.
+    pub fn with_coordinates(&self, coordinates: Coordinates) -> Result<Self, Error> {
+        let config = self.config.with_coordinates(coordinates)?;
+        let scope = if config.app_id == self.config.app_id && config.cluster == self.config.cluster
+        {
+            self.scope.clone()
+        } else {
+            Some(format!("{}+{}+", config.app_id, config.cluster).into())
+        };
+        Ok(Self {
+            config,
+            scope,
+            ..self.clone()
+        })
+    }
+
+    /// Stops tracking a namespace.
+    ///
+    /// The namespace is no longer polled and its listeners are unregistered,
+    /// so outstanding [`Subscription`] handles become inactive and
+    /// [`Client::watch`] streams, [`LiveConfig`] handles and
+    /// [`CompositeConfig`] views stop receiving its updates. The in-memory
+    /// value is released once no such handle holds it. The persisted entry is
+    /// kept, so a later access loads it again; use
+    /// [`Client::forget_and_delete`] to remove it as well.
+    ///
+    /// Returns `true` if the namespace was tracked.
+    pub async fn forget(&self, namespace: &str) -> bool {
+        self.remove_namespace(namespace).await.is_some()
+    }
+
+    /// Stops tracking a namespace like [`Client::forget`] and deletes its
+    /// persisted cache entry.
+    ///
+    /// The entry is deleted even if the namespace was not tracked. Returns
+    /// `true` if the namespace was tracked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Cache`] if the persisted entry exists but cannot be
+    /// deleted.
+    pub async fn forget_and_delete(&self, namespace: &str) -> Result<bool, Error> {
+        let (forgotten, cache) = match self.remove_namespace(namespace).await {
+            Some(cache) => (true, cache),
+            None => (
+                false,
+                Arc::new(Cache::new(
+                    self.config.clone(),
+                    namespace,
+                    self.http_client.clone(),
+                )),
+            ),
+        };
+        cache.delete_persisted().await?;
+        Ok(forgotten)
+    }
+
+    async fn remove_namespace(&self, namespace: &str) -> Option<Arc<Cache>> {
+        let cache = self
+            .namespaces
+            .write()
+            .await
+            .remove(self.cache_key(namespace).as_ref())?;
+        cache.release();
+        log::debug!("Forgot namespace {namespace}");
+        Some(cache)
+    }
+
     /// Registers a listener for changes and refresh errors in one namespace.
     ///
-    /// Listeners run synchronously, in registration order, after internal locks
-    /// are released. A panic in one listener is caught and logged so it cannot
+    /// Listeners run synchronously, in registration order, after the cache data
+    /// locks are released. Deliveries for one namespace are serialized, so a
+    /// listener must not block on work that delivers to the same namespace,
+    /// such as refreshing it or adding a replaying listener to it; spawn that
+    /// work instead. A panic in one listener is caught and logged so it cannot
     /// stop cache refresh or prevent later listeners from running.
-    pub async fn add_listener(&self, namespace: &str, listener: EventListener) {
+    ///
+    /// The listener stays registered while the returned [`Subscription`] is
+    /// alive. Drop it or call [`Subscription::unsubscribe`] to stop
+    /// notifications, or call [`Subscription::detach`] to keep the listener for
+    /// the lifetime of the client.
+    pub async fn add_listener(&self, namespace: &str, listener: EventListener) -> Subscription {
+        self.add_listener_with_options(namespace, listener, ListenerOptions::default())
+            .await
+    }
+
+    /// Registers a listener with [`ListenerOptions`].
+    ///
+    /// With [`ListenerOptions::replay_current`], the listener first receives
+    /// the namespace's current value, loading it if needed, and then every
+    /// later change without gaps or reordering. This replaces a separate
+    /// [`Client::namespace`] call that could race with an update.
+    pub async fn add_listener_with_options(
+        &self,
+        namespace: &str,
+        listener: EventListener,
+        options: ListenerOptions,
+    ) -> Subscription {
+        let cache = self.cache(namespace).await;
+        cache.add_listener(listener, options).await
+    }
+
+    /// Registers a listener for key-level changes and refresh errors in one
+    /// namespace.
+    ///
+    /// Each change is diffed once and the same [`ConfigChangeEvent`] is shared by
+    /// all change listeners. Updates that change no key, such as reformatted
+    /// JSON, are not reported. Delivery and [`Subscription`] semantics match
+    /// [`Client::add_listener`].
+    pub async fn add_change_listener(
+        &self,
+        namespace: &str,
+        listener: ChangeListener,
+    ) -> Subscription {
+        self.add_change_listener_with_options(namespace, listener, ListenerOptions::default())
+            .await
+    }
+
+    /// Registers a change listener with [`ListenerOptions`].
+    ///
+    /// A replayed current value reports every key as
+    /// [`PropertyChangeType::Added`].
+    pub async fn add_change_listener_with_options(
+        &self,
+        namespace: &str,
+        listener: ChangeListener,
+        options: ListenerOptions,
+    ) -> Subscription {
+        let cache = self.cache(namespace).await;
+        cache
+            .subscribe(subscription::change_notifier(listener), options)
+            .await
+    }
+
+    /// Returns a stream of updates to one namespace.
+    ///
+    /// The stream yields the current value first, loading it if needed, and
+    /// then each change or refresh error as `Result<Namespace, Error>`. Only the
+    /// latest update is buffered: a consumer that falls behind receives
+    /// [`Error::Lagged`] with the number of skipped updates, followed by the
+    /// newest one. Values are converted when polled, so skipped updates cost
+    /// nothing. Dropping the stream unregisters it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use apollo_rust_client::{Client, Error, client_config::ClientConfig};
+    /// use futures::StreamExt;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = ClientConfig::builder("my-app", "http://localhost:8080").build()?;
+    /// let client = Client::new(config)?;
+    /// let mut updates = std::pin::pin!(client.watch("application").await);
+    /// while let Some(update) = updates.next().await {
+    ///     match update {
+    ///         Ok(namespace) => println!("reconfiguring with {namespace:?}"),
+    ///         Err(Error::Lagged(skipped)) => println!("skipped {skipped} updates"),
+    ///         Err(error) => eprintln!("refresh failed: {error}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn watch(
+        &self,
+        namespace: &str,
+    ) -> impl futures::Stream<Item = Result<Namespace, Error>> + use<> {
+        let (notifier, receiver) = watch::channel();
+        let subscription = self
+            .cache(namespace)
+            .await
+            .subscribe(notifier, ListenerOptions::default().replay_current(true))
+            .await;
+        watch::stream(namespace.to_string(), receiver, subscription)
+    }
+
+    /// Creates a layered view over `namespaces`, lowest precedence first.
+    ///
+    /// Later namespaces override earlier ones: Properties keys resolve to the
+    /// last layer defining them and JSON and YAML documents are deep-merged.
+    /// The view reports which namespace supplied each value and stays current
+    /// as layers change. See [`CompositeConfig`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from loading a layer.
+    pub async fn composite(&self, namespaces: &[&str]) -> Result<CompositeConfig, Error> {
+        let mut caches = Vec::with_capacity(namespaces.len());
+        for namespace in namespaces {
+            caches.push(self.cache(namespace).await);
+        }
+        CompositeConfig::new(namespaces.iter().map(ToString::to_string).collect(), caches).await
+    }
+
+    /// Binds a namespace to a typed, automatically updated value.
+    ///
+    /// The namespace is loaded and deserialized into `T` now, then again on
+    /// every change; [`LiveConfig::load`] returns the latest value without
+    /// conversion. Properties namespaces deserialize like
+    /// [`Properties::to_object`](namespace::properties::Properties::to_object),
+    /// with dotted keys nesting and string values converting to the field
+    /// types; JSON and YAML namespaces from their document, and text
+    /// namespaces from their content string. An update that fails to deserialize leaves the
+    /// last good value in place and is reported to
+    /// [`LiveConfig::add_listener`] listeners.
+    ///
+    /// # Errors
+    ///
+    /// Returns the load error, or [`Error::Deserialize`] if the current value
+    /// does not deserialize into `T`.
+    pub async fn bind<T>(&self, namespace: &str) -> Result<LiveConfig<T>, Error>
+    where
+        T: serde::de::DeserializeOwned + Send + Sync + 'static,
+    {
+        LiveConfig::bind(self.cache(namespace).await).await
+    }
+
+    /// Loads a namespace into an [`ApolloConfig`] struct.
+    ///
+    /// Every field is read before returning, so a failure lists all missing
+    /// and invalid keys at once. See [`typed`] for deriving the struct.
+    ///
+    /// # Errors
+    ///
+    /// Returns the load error, or [`Error::InvalidConfig`] if keys are
+    /// missing or invalid, or the namespace is a text namespace.
+    pub async fn load_config<T: ApolloConfig>(&self, namespace: &str) -> Result<T, Error> {
+        T::from_namespace(&self.namespace(namespace).await?).map_err(|source| {
+            Error::InvalidConfig {
+                namespace: namespace.to_string(),
+                source,
+            }
+        })
+    }
+
+    /// Binds a namespace to an automatically updated [`ApolloConfig`]
+    /// struct.
+    ///
+    /// Works like [`Client::bind`]: the struct is loaded again on every
+    /// change, an update with missing or invalid keys leaves the last good
+    /// value in place, and [`LiveConfig::add_listener`] listeners are told
+    /// about each attempt.
+    ///
+    /// # Errors
+    ///
+    /// Returns the load error, or [`Error::Deserialize`] listing the missing
+    /// and invalid keys of the current value.
+    pub async fn bind_config<T>(&self, namespace: &str) -> Result<LiveConfig<T>, Error>
+    where
+        T: ApolloConfig + Send + Sync + 'static,
+    {
+        LiveConfig::bind_with(self.cache(namespace).await, live::load_config::<T>).await
+    }
+
+    /// Reads feature flags from a JSON or YAML namespace.
+    ///
+    /// The document maps flag names to definitions; see [`flags`] for the
+    /// format and evaluation rules. The returned handle follows namespace
+    /// changes until its last clone is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the load error, or [`Error::InvalidFlags`] if the namespace
+    /// is not a JSON or YAML namespace or its document is not an object.
+    /// Individual invalid definitions are reported by
+    /// [`FeatureFlags::errors`] instead.
+    pub async fn feature_flags(&self, namespace: &str) -> Result<FeatureFlags, Error> {
+        FeatureFlags::from_document(self.cache(namespace).await).await
+    }
+
+    /// Reads feature flags from the keys of a Properties namespace that
+    /// start with `prefix`.
+    ///
+    /// Flag names are the keys with the prefix removed, and values hold a
+    /// JSON definition or a shorthand such as `true`, `25%` or
+    /// `blue:50,red:50`; see [`flags`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the load error, or [`Error::InvalidFlags`] if the namespace
+    /// is not a Properties namespace.
+    pub async fn feature_flags_with_prefix(
+        &self,
+        namespace: &str,
+        prefix: &str,
+    ) -> Result<FeatureFlags, Error> {
+        FeatureFlags::from_properties(self.cache(namespace).await, prefix).await
+    }
+
+    /// Calls `callback` when a key, or any key below a prefix, changes.
+    ///
+    /// Properties namespaces match property keys, so `db.pool` covers
+    /// `db.pool.size`. JSON and YAML namespaces match JSON Pointer paths, so
+    /// `/db` covers `/db/port`. Prefixes match whole segments. The callback runs
+    /// once per matching key with the old and new values parsed as `T` using
+    /// [`Properties::get_property`](namespace::properties::Properties::get_property)
+    /// rules. Refresh errors are only delivered to [`Client::add_listener`]
+    /// callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use apollo_rust_client::{Client, client_config::ClientConfig};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = ClientConfig::builder("my-app", "http://localhost:8080").build()?;
+    /// let client = Client::new(config)?;
+    /// let subscription = client
+    ///     .on_key_change::<u32, _>("application", "db.pool.size", |change| {
+    ///         println!("pool size: {:?} -> {:?}", change.old_value, change.new_value);
+    ///     })
+    ///     .await;
+    /// # drop(subscription);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn on_key_change<T, F>(
+        &self,
+        namespace: &str,
+        key_or_prefix: &str,
+        callback: F,
+    ) -> Subscription
+    where
+        T: std::str::FromStr + 'static,
+        F: Fn(KeyChange<T>) + Send + Sync + 'static,
+    {
         let cache = self.cache(namespace).await;
-        cache.add_listener(listener).await;
+        cache
+            .subscribe(
+                subscription::key_notifier(key_or_prefix.to_string(), callback),
+                ListenerOptions::default(),
+            )
+            .await
     }
 
     /// Retrieves a namespace configuration from the Apollo server.
@@ -401,6 +1007,10 @@ impl Client {
     /// 3. Logs errors and applies bounded exponential backoff with jitter.
     /// 4. Sleeps for the configured interval before the next refresh cycle.
     ///
+    /// With [`ClientConfig::shared_cache`] enabled on native targets, only
+    /// namespaces whose advisory lock this process holds are polled, and a
+    /// cache-directory watcher loads entries written by sibling processes.
+    ///
     /// # Returns
     ///
     /// * `Ok(())` if the background task was successfully started.
@@ -411,8 +1021,14 @@ impl Client {
     /// This method will return an error if:
     /// - The background task is already running (`Error::AlreadyRunning`)
     /// - Task spawning fails (though this is rare and typically indicates system resource issues)
-    fn start_background(&mut self) -> Result<(), Error> {
+    fn start_background(&self) -> Result<(), Error> {
+        let mut task = self
+            .background
+            .task
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
         if self
+            .background
             .running
             .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
             .is_err()
@@ -420,23 +1036,53 @@ impl Client {
             return Err(Error::AlreadyRunning);
         }
 
+        let wake = Arc::new(tokio::sync::Notify::new());
+        let refresh = refresh_loop(
+            self.namespaces.clone(),
+            self.background.running.clone(),
+            wake.clone(),
+            self.config.effective_refresh_interval(),
+            self.config.namespace_idle_timeout,
+        );
         cfg_if::cfg_if! {
             if #[cfg(target_arch = "wasm32")] {
                 let (abort_handle, abort_registration) = futures::future::AbortHandle::new_pair();
-                let namespaces = self.namespaces.clone();
-                let running = self.running.clone();
-                let refresh_interval = self.config.effective_refresh_interval();
+                let (finished_sender, finished) = tokio::sync::oneshot::channel();
                 wasm_bindgen_futures::spawn_local(async move {
-                    let task = refresh_loop(namespaces, running, refresh_interval);
-                    let _ = futures::future::Abortable::new(task, abort_registration).await;
+                    let _ = futures::future::Abortable::new(refresh, abort_registration).await;
+                    let _ = finished_sender.send(());
+                });
+                *task = Some(BackgroundTask {
+                    wake,
+                    abort_handle,
+                    finished,
                 });
-                self.abort_handle = Some(abort_handle);
             } else {
-                let running = self.running.clone();
-                let namespaces = self.namespaces.clone();
-                let refresh_interval = self.config.effective_refresh_interval();
-                let handle = spawn(refresh_loop(namespaces, running, refresh_interval));
-                self.handle = Some(handle);
+                #[cfg(feature = "shared-cache")]
+                let cache_watcher = if self.config.effective_shared_cache() {
+                    match cache::CacheWatcher::spawn(
+                        &self.config.get_cache_dir(),
+                        self.namespaces.clone(),
+                    ) {
+                        Ok(watcher) => Some(watcher),
+                        Err(error) => {
+                            log::warn!(
+                                "Unable to watch the shared cache directory; sibling updates arrive only through polling: {error}"
+                            );
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+                let handle = spawn(refresh);
+                *task = Some(BackgroundTask {
+                    wake,
+                    abort_handle: handle.abort_handle(),
+                    handle,
+                    #[cfg(feature = "shared-cache")]
+                    _cache_watcher: cache_watcher,
+                });
             }
         }
 
@@ -450,39 +1096,73 @@ impl Client {
     /// Returns [`Error::AlreadyRunning`] if polling is already active.
     #[cfg(not(target_arch = "wasm32"))]
     #[allow(clippy::unused_async)]
-    pub async fn start(&mut self) -> Result<(), Error> {
+    pub async fn start(&self) -> Result<(), Error> {
         self.start_background()
     }
 
     /// Stops the background cache refresh task.
     ///
-    /// This method sets the `running` flag to `false`, signaling the background task
-    /// to terminate its refresh loop.
-    ///
-    /// Native and WASM targets both abort their task handle, so shutdown does not
-    /// wait for a network request or refresh interval to complete.
-    fn stop_background(&mut self) {
-        self.running.store(false, Ordering::Release);
-        cfg_if::cfg_if! {
-            if #[cfg(not(target_arch = "wasm32"))] {
-                if let Some(handle) = self.handle.take() {
-                    handle.abort();
-                }
-            } else {
-                if let Some(handle) = self.abort_handle.take() {
-                    handle.abort();
-                }
-            }
-        }
+    /// This method sets the `running` flag to `false` and aborts the task
+    /// handle on native and WASM targets, so it does not wait for a network
+    /// request or refresh interval to complete. Use [`Client::shutdown`] to
+    /// let in-flight work finish instead.
+    fn stop_background(&self) {
+        self.background.abort();
     }
 
     /// Stops native background polling promptly.
     #[cfg(not(target_arch = "wasm32"))]
     #[allow(clippy::unused_async)]
-    pub async fn stop(&mut self) {
+    pub async fn stop(&self) {
         self.stop_background();
     }
 
+    /// Stops background polling gracefully.
+    ///
+    /// Polling stops taking new work at once, and the shared-cache watcher is
+    /// closed. The call then waits for in-flight refreshes, including their
+    /// persistence writes and listener callbacks, and for any other listener
+    /// delivery to finish. Work still running when `timeout` elapses is
+    /// cancelled. Shutdown affects every clone of the client; namespaces stay
+    /// readable and polling can be started again.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ShutdownTimeout`] if in-flight work did not finish
+    /// within `timeout`.
+    pub async fn shutdown(&self, timeout: std::time::Duration) -> Result<(), Error> {
+        let (abort_handle, finished) = match self.background.take() {
+            Some(task) => {
+                let (abort_handle, finished) = task.stop();
+                (Some(abort_handle), Some(finished))
+            }
+            None => (None, None),
+        };
+        let drain = async {
+            if let Some(finished) = finished {
+                finished.await;
+            }
+            let caches: Vec<_> = self.namespaces.read().await.values().cloned().collect();
+            for cache in caches {
+                cache.drain().await;
+            }
+        };
+        let deadline = platform_sleep(timeout);
+        futures::pin_mut!(drain, deadline);
+        let result = match futures::future::select(drain, deadline).await {
+            futures::future::Either::Left(((), _)) => Ok(()),
+            futures::future::Either::Right(((), _)) => {
+                if let Some(abort_handle) = abort_handle {
+                    abort_handle.abort();
+                }
+                Err(Error::ShutdownTimeout(timeout))
+            }
+        };
+        #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+        self.background.release_leadership();
+        result
+    }
+
     /// Preloads critical namespaces during client initialization to reduce startup delays.
     ///
     /// This method fetches configuration for the specified namespaces in parallel,
@@ -531,34 +1211,203 @@ impl Client {
         Ok(())
     }
 
-    /// Forces one namespace to refresh from Apollo.
+    /// Waits until `namespaces` are loaded, from Apollo or the persistent
+    /// cache, and reports each namespace's availability.
     ///
-    /// Existing values remain readable while the request is in flight and are
-    /// retained if the refresh fails.
+    /// Namespaces load concurrently. Unlike [`Client::preload`], one failure
+    /// does not hide the others: the report lists every namespace as
+    /// [`NamespaceStatus::Fresh`], [`NamespaceStatus::Stale`],
+    /// [`NamespaceStatus::Failed`], or [`NamespaceStatus::Pending`], and
+    /// [`ReadinessReport::is_ready`] applies `policy`. The wait ends when every
+    /// namespace has settled, when a required namespace turns out not to be
+    /// ready, or at `deadline`, whichever comes first.
     ///
-    /// # Errors
+    /// # Example
     ///
-    /// Returns transport, HTTP, parsing, or configuration errors from the refresh.
-    pub async fn refresh(&self, namespace: &str) -> Result<(), Error> {
-        self.cache(namespace).await.refresh().await?;
-        Ok(())
-    }
-}
-
-impl Drop for Client {
-    fn drop(&mut self) {
-        self.stop_background();
-    }
-}
-
-async fn refresh_loop(
-    namespaces: Arc<RwLock<HashMap<String, Arc<Cache>>>>,
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use apollo_rust_client::{Client, ReadinessPolicy, client_config::ClientConfig};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = ClientConfig::builder("my-app", "http://apollo-server:8080").build()?;
+    /// let client = Client::new(config)?;
+    ///
+    /// // Require a value confirmed by Apollo for "application"
+    /// let policy = ReadinessPolicy::default()
+    ///     .optional("feature.flags")
+    ///     .accept_stale(false);
+    /// let report = client
+    ///     .wait_until_ready(&["application", "feature.flags"], Duration::from_secs(10), &policy)
+    ///     .await;
+    /// assert!(report.is_ready(), "{report:?}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_until_ready(
+        &self,
+        namespaces: &[impl AsRef<str>],
+        deadline: std::time::Duration,
+        policy: &ReadinessPolicy,
+    ) -> ReadinessReport {
+        let mut statuses = vec![NamespaceStatus::Pending; namespaces.len()];
+        let mut checks: futures::stream::FuturesUnordered<_> = namespaces
+            .iter()
+            .enumerate()
+            .map(|(index, namespace)| async move {
+                let cache = self.cache(namespace.as_ref()).await;
+                (index, readiness::check(&cache, policy).await)
+            })
+            .collect();
+        let deadline = platform_sleep(deadline);
+        futures::pin_mut!(deadline);
+        while let futures::future::Either::Left((Some((index, status)), _)) =
+            futures::future::select(checks.next(), deadline.as_mut()).await
+        {
+            let blocking =
+                !status.is_ready(policy) && policy.is_required(namespaces[index].as_ref());
+            statuses[index] = status;
+            if blocking {
+                break;
+            }
+        }
+
+        ReadinessReport::new(
+            namespaces
+                .iter()
+                .zip(statuses)
+                .map(|(namespace, status)| NamespaceReadiness {
+                    namespace: namespace.as_ref().to_string(),
+                    required: policy.is_required(namespace.as_ref()),
+                    ready: status.is_ready(policy),
+                    status,
+                })
+                .collect(),
+        )
+    }
+
+    /// Fetches a namespace from Apollo and reports how the request went.
+    ///
+    /// The fetch takes the regular refresh path, so a success updates the
+    /// cached value and notifies listeners. The [`Diagnosis`] tells whether
+    /// Apollo could be reached, whether it accepted the request signature,
+    /// and where the namespace is persisted.
+    pub async fn diagnose(&self, namespace: &str) -> Diagnosis {
+        diagnostics::diagnose(&*self.cache(namespace).await).await
+    }
+
+    /// Forces one namespace to refresh from Apollo.
+    ///
+    /// Existing values remain readable while the request is in flight and are
+    /// retained if the refresh fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns transport, HTTP, parsing, or configuration errors from the refresh.
+    pub async fn refresh(&self, namespace: &str) -> Result<(), Error> {
+        self.cache(namespace).await.refresh().await?;
+        Ok(())
+    }
+
+    /// Forces `key` of `namespace` to `value` without publishing to Apollo.
+    ///
+    /// `key` is a property key in Properties namespaces and a JSON Pointer
+    /// path, such as `/database/port`, in JSON and YAML namespaces. The
+    /// override takes precedence over Apollo, the environment and the
+    /// override file, applies under every app id and cluster, and lasts until
+    /// it is removed or the last clone of the client is dropped. Listeners of
+    /// loaded namespaces are notified if a value changes. See
+    /// [`overrides`] for how values are applied.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use apollo_rust_client::{Client, client_config::ClientConfig};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = ClientConfig::builder("my-app", "http://apollo-server:8080").build()?;
+    /// let client = Client::new(config)?;
+    ///
+    /// client.set_override("application", "checkout.enabled", false).await?;
+    /// client.set_override("database.yaml", "/pool/size", 2).await?;
+    /// // ...
+    /// client.remove_override("application", "checkout.enabled").await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidOverride`] if the key does not suit the
+    /// namespace format or the namespace is neither Properties, JSON nor YAML.
+    pub async fn set_override(
+        &self,
+        namespace: &str,
+        key: &str,
+        value: impl Into<serde_json::Value>,
+    ) -> Result<(), Error> {
+        overrides::validate(namespace, key).map_err(|reason| Error::InvalidOverride {
+            namespace: namespace.to_string(),
+            key: key.to_string(),
+            reason,
+        })?;
+        let value = value.into();
+        log::info!("Overriding {key} of namespace {namespace} locally");
+        self.overrides
+            .update(&self.namespaces, |layers| layers.set(namespace, key, value))
+            .await;
+        Ok(())
+    }
+
+    /// Removes an override set with [`Client::set_override`].
+    ///
+    /// Returns `true` if the override existed.
+    pub async fn remove_override(&self, namespace: &str, key: &str) -> bool {
+        self.overrides
+            .update(&self.namespaces, |layers| layers.remove(namespace, key))
+            .await
+    }
+
+    /// Removes every override set with [`Client::set_override`].
+    ///
+    /// Environment and file overrides are kept.
+    pub async fn clear_overrides(&self) {
+        self.overrides
+            .update(&self.namespaces, overrides::OverrideLayers::clear)
+            .await;
+    }
+
+    /// Lists the overrides in effect for a namespace, loading it if needed.
+    ///
+    /// Each entry names the overridden key, the value served in its place,
+    /// Apollo's value and the source of the override, so diagnostics can flag
+    /// values that do not come from Apollo.
+    ///
+    /// # Errors
+    ///
+    /// Returns transport, HTTP, parsing, or configuration errors when the
+    /// namespace cannot be loaded.
+    pub async fn overrides(&self, namespace: &str) -> Result<Vec<ActiveOverride>, Error> {
+        let cache = self.cache(namespace).await;
+        cache.get_value().await?;
+        Ok(cache.active_overrides().await)
+    }
+}
+
+async fn refresh_loop(
+    namespaces: Arc<RwLock<HashMap<String, Arc<Cache>>>>,
     running: Arc<AtomicBool>,
+    wake: Arc<tokio::sync::Notify>,
     refresh_interval: u64,
+    idle_timeout: Option<u64>,
 ) {
     let base_interval = refresh_interval.max(1);
 
     while running.load(Ordering::Acquire) {
+        if let Some(idle_timeout) = idle_timeout {
+            expire_idle(&namespaces, idle_timeout).await;
+        }
         let cache_refs: Vec<_> = {
             let namespaces = namespaces.read().await;
             namespaces
@@ -567,9 +1416,20 @@ async fn refresh_loop(
                 .map(|(name, cache)| (name.clone(), cache.clone()))
                 .collect()
         };
-
+        // In shared-cache mode, namespaces led by a sibling process are
+        // updated through the cache-directory watcher instead.
+        let cache_refs: Vec<_> = cache_refs
+            .into_iter()
+            .filter(|(_, cache)| cache.should_poll())
+            .collect();
+
+        let running = &running;
         let _results = stream::iter(cache_refs)
             .map(|(namespace, cache)| async move {
+                // Namespaces not yet started when shutdown begins are skipped.
+                if !running.load(Ordering::Acquire) {
+                    return Ok(());
+                }
                 let result = cache.refresh().await;
                 if let Err(error) = &result {
                     error!("Failed to refresh cache for namespace {namespace}: {error}");
@@ -582,10 +1442,49 @@ async fn refresh_loop(
             .collect::<Vec<_>>()
             .await;
 
-        platform_sleep(std::time::Duration::from_secs(base_interval)).await;
+        if !running.load(Ordering::Acquire) {
+            break;
+        }
+        let sleep = platform_sleep(std::time::Duration::from_secs(base_interval));
+        let woken = wake.notified();
+        futures::pin_mut!(sleep, woken);
+        futures::future::select(sleep, woken).await;
     }
 }
 
+/// Forgets namespaces with no listeners that have not been accessed for
+/// `idle_timeout` seconds.
+async fn expire_idle(namespaces: &RwLock<HashMap<String, Arc<Cache>>>, idle_timeout: u64) {
+    let mut namespaces = namespaces.write().await;
+    namespaces.retain(|namespace, cache| {
+        if cache.is_idle(idle_timeout) {
+            log::debug!("Forgetting namespace {namespace} after {idle_timeout} idle seconds");
+            cache.release();
+            false
+        } else {
+            true
+        }
+    });
+}
+
+/// Converts a JavaScript array of strings.
+#[cfg(target_arch = "wasm32")]
+fn string_array(name: &str, array: &js_sys::Array) -> Result<Vec<String>, Error> {
+    array
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            value.as_string().ok_or_else(|| {
+                Error::Config(client_config::Error::InvalidValue {
+                    name: format!("{name}[{index}]"),
+                    value: format!("{value:?}"),
+                    reason: "expected a string".to_string(),
+                })
+            })
+        })
+        .collect()
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 async fn platform_sleep(duration: std::time::Duration) {
     tokio::time::sleep(duration).await;
@@ -620,8 +1519,15 @@ impl Client {
         {
             if check_local_storage_availability() {
                 log::info!("localStorage is available for persistent configuration caching.");
+            } else if let Some(cache_dir) = cache::node::cache_dir(&config) {
+                log::info!(
+                    "localStorage is not available. Persisting configuration under {cache_dir}."
+                );
+                cache::node::cleanup_stale_temp_files(&cache_dir);
             } else {
-                log::info!("localStorage is not available. Falling back to in-memory configuration caching.");
+                log::info!(
+                    "localStorage is not available. Falling back to in-memory configuration caching."
+                );
             }
         }
         #[cfg(not(target_arch = "wasm32"))]
@@ -657,15 +1563,33 @@ impl Client {
             }
         };
 
+        let namespaces = Arc::new(RwLock::new(HashMap::new()));
+        let overrides = Arc::new(overrides::Overrides::new(&config));
+        let placeholders = Arc::new(placeholders::Placeholders::new(
+            &config,
+            Arc::downgrade(&namespaces),
+        ));
+        #[cfg(all(not(target_arch = "wasm32"), feature = "override-watch"))]
+        let override_watcher = config.override_file.as_ref().and_then(|path| {
+            overrides::OverrideWatcher::spawn(
+                std::path::Path::new(path),
+                overrides.clone(),
+                namespaces.clone(),
+            )
+            .map_err(|error| log::warn!("Unable to watch override file {path}: {error}"))
+            .ok()
+            .map(Arc::new)
+        });
         Ok(Self {
             config,
-            namespaces: Arc::new(RwLock::new(HashMap::new())),
-            #[cfg(not(target_arch = "wasm32"))]
-            handle: None,
-            #[cfg(target_arch = "wasm32")]
-            abort_handle: None,
-            running: Arc::new(AtomicBool::new(false)),
+            background: Arc::new(Background::new(namespaces.clone())),
+            namespaces,
+            scope: None,
             http_client,
+            overrides,
+            placeholders,
+            #[cfg(all(not(target_arch = "wasm32"), feature = "override-watch"))]
+            _override_watcher: override_watcher,
         })
     }
 
@@ -676,17 +1600,54 @@ impl Client {
     /// Returns [`Error::AlreadyRunning`] if polling is already active.
     #[cfg(target_arch = "wasm32")]
     #[wasm_bindgen(js_name = "start")]
-    pub fn start_wasm(&mut self) -> Result<(), Error> {
+    pub fn start_wasm(&self) -> Result<(), Error> {
         self.start_background()
     }
 
     /// Stops JavaScript/WASM background polling promptly.
     #[cfg(target_arch = "wasm32")]
     #[wasm_bindgen(js_name = "stop")]
-    pub fn stop_wasm(&mut self) {
+    pub fn stop_wasm(&self) {
         self.stop_background();
     }
 
+    /// Stops JavaScript/WASM background polling gracefully, waiting up to
+    /// `timeout_millis` for in-flight refreshes and listener callbacks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if in-flight work did not finish in time.
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen(js_name = "shutdown")]
+    pub async fn shutdown_wasm(&self, timeout_millis: u32) -> Result<(), Error> {
+        self.shutdown(std::time::Duration::from_millis(u64::from(timeout_millis)))
+            .await
+    }
+
+    /// Returns a JavaScript/WASM handle for namespaces published under another
+    /// app id or cluster. See [`Client::with_coordinates`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the app id or cluster is empty.
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen(js_name = "with_coordinates")]
+    pub fn with_coordinates_wasm(
+        &self,
+        app_id: String,
+        cluster: Option<String>,
+        secret: Option<String>,
+    ) -> Result<Client, Error> {
+        let mut coordinates = Coordinates::new(app_id);
+        if let Some(cluster) = cluster {
+            coordinates = coordinates.cluster(cluster);
+        }
+        if let Some(secret) = secret {
+            coordinates = coordinates.secret(secret);
+        }
+        self.with_coordinates(coordinates)
+    }
+
     /// Forces one JavaScript/WASM namespace refresh.
     ///
     /// # Errors
@@ -698,6 +1659,107 @@ impl Client {
         self.refresh(namespace).await
     }
 
+    /// Stops tracking a JavaScript/WASM namespace, optionally deleting its
+    /// persisted entry.
+    ///
+    /// Returns `true` if the namespace was tracked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the persisted entry cannot be deleted.
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen(js_name = "forget")]
+    pub async fn forget_wasm(
+        &self,
+        namespace: &str,
+        delete_persisted: Option<bool>,
+    ) -> Result<bool, Error> {
+        if delete_persisted.unwrap_or(false) {
+            self.forget_and_delete(namespace).await
+        } else {
+            Ok(self.forget(namespace).await)
+        }
+    }
+
+    /// Overrides a JavaScript/WASM namespace value locally. See
+    /// [`Client::set_override`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key does not suit the namespace format or the
+    /// value is not JSON-compatible.
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen(js_name = "set_override")]
+    pub async fn set_override_wasm(
+        &self,
+        namespace: &str,
+        key: &str,
+        value: wasm_bindgen::JsValue,
+    ) -> Result<(), Error> {
+        let value: serde_json::Value =
+            serde_wasm_bindgen::from_value(value).map_err(|error| Error::InvalidOverride {
+                namespace: namespace.to_string(),
+                key: key.to_string(),
+                reason: error.to_string(),
+            })?;
+        self.set_override(namespace, key, value).await
+    }
+
+    /// Removes a JavaScript/WASM override, returning whether it existed.
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen(js_name = "remove_override")]
+    pub async fn remove_override_wasm(&self, namespace: &str, key: &str) -> bool {
+        self.remove_override(namespace, key).await
+    }
+
+    /// Removes every JavaScript/WASM override set through the API.
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen(js_name = "clear_overrides")]
+    pub async fn clear_overrides_wasm(&self) {
+        self.clear_overrides().await;
+    }
+
+    /// Resolves to an array of plain objects describing the overrides in
+    /// effect for a JavaScript/WASM namespace. See [`Client::overrides`].
+    ///
+    /// # Errors
+    ///
+    /// Returns transport, HTTP, parsing, or configuration errors.
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen(js_name = "overrides")]
+    pub async fn overrides_wasm(&self, namespace: &str) -> Result<wasm_bindgen::JsValue, Error> {
+        use serde::Serialize;
+
+        let overrides = self.overrides(namespace).await?;
+        Ok(overrides
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .unwrap_or_else(|error| {
+                log::error!("Unable to serialize overrides for JavaScript: {error}");
+                wasm_bindgen::JsValue::NULL
+            }))
+    }
+
+    /// Reads feature flags for JavaScript/WASM from a JSON or YAML namespace,
+    /// or from the keys of a Properties namespace starting with `prefix`.
+    /// See [`Client::feature_flags`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the load error, or an error if the namespace does not hold
+    /// flag definitions.
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen(js_name = "feature_flags")]
+    pub async fn feature_flags_wasm(
+        &self,
+        namespace: &str,
+        prefix: Option<String>,
+    ) -> Result<FeatureFlags, Error> {
+        match prefix {
+            Some(prefix) => self.feature_flags_with_prefix(namespace, &prefix).await,
+            None => self.feature_flags(namespace).await,
+        }
+    }
+
     /// Preloads JavaScript/WASM namespaces concurrently.
     ///
     /// # Errors
@@ -707,92 +1769,137 @@ impl Client {
     #[cfg(target_arch = "wasm32")]
     #[wasm_bindgen(js_name = "preload")]
     pub async fn preload_wasm(&self, namespaces: js_sys::Array) -> Result<(), Error> {
-        let namespaces = namespaces
-            .iter()
-            .enumerate()
-            .map(|(index, value)| {
-                value.as_string().ok_or_else(|| {
-                    Error::Config(client_config::Error::InvalidValue {
-                        name: format!("namespaces[{index}]"),
-                        value: format!("{value:?}"),
-                        reason: "expected a string".to_string(),
-                    })
-                })
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        let namespaces = string_array("namespaces", &namespaces)?;
         self.preload(&namespaces).await
     }
 
+    /// Waits until JavaScript/WASM namespaces are loaded and resolves to a
+    /// plain-object readiness report. See [`Client::wait_until_ready`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an array element is not a string.
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen(js_name = "wait_until_ready")]
+    pub async fn wait_until_ready_wasm(
+        &self,
+        namespaces: js_sys::Array,
+        deadline_millis: u32,
+        optional: Option<js_sys::Array>,
+        accept_stale: Option<bool>,
+    ) -> Result<wasm_bindgen::JsValue, Error> {
+        let namespaces = string_array("namespaces", &namespaces)?;
+        let mut policy = ReadinessPolicy::default().accept_stale(accept_stale.unwrap_or(true));
+        if let Some(optional) = optional {
+            for namespace in string_array("optional", &optional)? {
+                policy = policy.optional(namespace);
+            }
+        }
+        let report = self
+            .wait_until_ready(
+                &namespaces,
+                std::time::Duration::from_millis(u64::from(deadline_millis)),
+                &policy,
+            )
+            .await;
+        Ok(report.into())
+    }
+
     /// Registers a JavaScript function as an event listener for this cache (WASM only).
     ///
     /// This method is exposed to JavaScript as `add_listener`.
     /// The provided JavaScript function will be called when the cache is refreshed.
     ///
     /// The JavaScript listener function is expected to have a signature like:
-    /// `function(data, error)`
+    /// `function(data, error, changes)`
     /// - `data`: A plain JavaScript object/string for the namespace if refresh succeeded,
     ///           or `undefined` if an error occurred. Serialization failures produce `null`.
     /// - `error`: A string describing the error if one occurred during the configuration
     ///            fetch or processing. On success this is `undefined`.
+    /// - `changes`: The key-level change event, shaped like
+    ///              `{ namespace, changes: { [key]: { oldValue, newValue, changeType } } }`
+    ///              with `changeType` one of `"added"`, `"modified"`, or `"deleted"`.
+    ///              On error this is `undefined`.
     ///
     /// # Arguments
     ///
     /// * `js_listener` - A JavaScript `Function` to be called on cache events.
+    /// * `replay_current` - When `true`, the listener first receives the current value.
+    ///
+    /// # Returns
+    ///
+    /// A `Subscription` whose `unsubscribe()` method removes the listener. Unlike
+    /// Rust handles, JavaScript handles never unsubscribe implicitly.
     ///
     /// # Example (JavaScript)
     ///
     /// ```javascript
-    /// await client.add_listener("application", (data, error) => {
+    /// const subscription = await client.add_listener("application", (data, error, changes) => {
     ///   if (error) {
     ///     console.error('Cache update error:', error);
     ///   } else {
-    ///     console.log('Cache updated:', data);
+    ///     console.log('Cache updated:', data, Object.keys(changes.changes));
     ///   }
-    /// });
+    /// }, true);
+    /// // Later:
+    /// subscription.unsubscribe();
     /// ```
     #[cfg(target_arch = "wasm32")]
     #[wasm_bindgen(js_name = "add_listener")]
-    pub async fn add_listener_wasm(&self, namespace: &str, js_listener: js_sys::Function) {
-        let js_listener_clone = js_listener.clone();
-
-        let event_listener: EventListener = Arc::new(move |result: Result<Namespace, Error>| {
-            let err_js_val: wasm_bindgen::JsValue;
-            let data_js_val: wasm_bindgen::JsValue;
-
-            match result {
-                Ok(value) => {
-                    data_js_val = match value {
-                        // Listener values are ephemeral. A plain object avoids
-                        // allocating a Properties class that the callback would
-                        // otherwise have to free after every notification.
-                        Namespace::Properties(properties) => properties.into_js_value(),
-                        value => value.into(),
-                    };
-                    err_js_val = wasm_bindgen::JsValue::UNDEFINED;
-                }
-                Err(cache_error) => {
-                    err_js_val = cache_error.into();
-                    data_js_val = wasm_bindgen::JsValue::UNDEFINED;
-                }
-            }
-
-            // Call the JavaScript listener: listener(data, error)
-            match js_listener_clone.call2(
-                &wasm_bindgen::JsValue::UNDEFINED,
-                &data_js_val,
-                &err_js_val,
-            ) {
-                Ok(_) => {
-                    // JS function called successfully
-                }
-                Err(e) => {
+    pub async fn add_listener_wasm(
+        &self,
+        namespace: &str,
+        js_listener: js_sys::Function,
+        replay_current: Option<bool>,
+    ) -> Subscription {
+        let notifier: subscription::Notifier =
+            Arc::new(move |notification: &subscription::Notification<'_>| {
+                let (data_js_val, err_js_val, changes_js_val) = match notification {
+                    subscription::Notification::Changed { config, event } => {
+                        match namespace::get_namespace(event.namespace(), (*config).clone()) {
+                            // Listener values are ephemeral. A plain object avoids
+                            // allocating a Properties class that the callback would
+                            // otherwise have to free after every notification.
+                            Ok(value) => (
+                                match value {
+                                    Namespace::Properties(properties) => properties.into_js_value(),
+                                    value => value.into(),
+                                },
+                                wasm_bindgen::JsValue::UNDEFINED,
+                                (*event).clone().into(),
+                            ),
+                            Err(error) => (
+                                wasm_bindgen::JsValue::UNDEFINED,
+                                Error::Namespace(error).into(),
+                                wasm_bindgen::JsValue::UNDEFINED,
+                            ),
+                        }
+                    }
+                    subscription::Notification::Failed(error) => (
+                        wasm_bindgen::JsValue::UNDEFINED,
+                        Error::Refresh((*error).to_string()).into(),
+                        wasm_bindgen::JsValue::UNDEFINED,
+                    ),
+                };
+
+                // Call the JavaScript listener: listener(data, error, changes)
+                if let Err(e) = js_listener.call3(
+                    &wasm_bindgen::JsValue::UNDEFINED,
+                    &data_js_val,
+                    &err_js_val,
+                    &changes_js_val,
+                ) {
                     // JS function threw an error or call failed
                     log::error!("JavaScript listener threw an error: {e:?}");
                 }
-            }
-        });
+            });
 
-        self.add_listener(namespace, event_listener).await; // Call the renamed Rust method
+        let options = ListenerOptions::default().replay_current(replay_current.unwrap_or(false));
+        self.cache(namespace)
+            .await
+            .subscribe(notifier, options)
+            .await
+            .into_explicit()
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -917,9 +2024,51 @@ return Promise.resolve(response);
             )
             .expect("mock fetch should be installable");
         } else {
-            let _ = env_logger::builder().is_test(true).try_init();
+            let logger = test_logger::CountingLogger(env_logger::builder().is_test(true).build());
+            let level = logger.0.filter().max(log::LevelFilter::Warn);
+            if log::set_boxed_logger(Box::new(logger)).is_ok() {
+                log::set_max_level(level);
+            }
+        }
+    }
+}
+
+/// A test logger that counts the warnings logged on each thread.
+#[cfg(all(test, not(target_arch = "wasm32")))]
+pub(crate) mod test_logger {
+    use std::cell::Cell;
+
+    thread_local! {
+        static WARNINGS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Forwards records to `env_logger`, counting warnings and errors.
+    pub(crate) struct CountingLogger(pub(crate) env_logger::Logger);
+
+    impl log::Log for CountingLogger {
+        fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+            metadata.level() <= log::Level::Warn || self.0.enabled(metadata)
+        }
+
+        fn log(&self, record: &log::Record<'_>) {
+            if record.level() <= log::Level::Warn {
+                WARNINGS.with(|warnings| warnings.set(warnings.get() + 1));
+            }
+            self.0.log(record);
+        }
+
+        fn flush(&self) {
+            self.0.flush();
         }
     }
+
+    /// Returns how many warnings and errors `action` logs on this thread.
+    pub(crate) fn warnings_logged_by<T>(action: impl FnOnce() -> T) -> usize {
+        super::setup();
+        let before = WARNINGS.with(Cell::get);
+        let _ = action();
+        WARNINGS.with(Cell::get) - before
+    }
 }
 
 #[cfg(test)]
@@ -1380,142 +2529,986 @@ mod tests {
             .await
             .unwrap()
         else {
-            panic!("Expected Properties namespace");
+            panic!("Expected Properties namespace");
+        };
+        assert_eq!(
+            properties.get_property::<bool>("grayScaleValue"),
+            Some(true)
+        );
+        let namespace::Namespace::Properties(properties) =
+            client_no_secret().namespace("application").await.unwrap()
+        else {
+            panic!("Expected Properties namespace");
+        };
+        assert_eq!(
+            properties.get_property::<bool>("grayScaleValue"),
+            Some(false)
+        );
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    #[allow(dead_code)]
+    async fn test_bool_value_with_grayscale_ip_wasm() {
+        setup();
+        let client1 = create_client_with_grayscale_ip();
+        let namespace = client1.namespace("application").await;
+        match namespace {
+            Ok(namespace) => match namespace {
+                namespace::Namespace::Properties(properties) => {
+                    assert_eq!(properties.get_bool("grayScaleValue"), Some(true));
+                }
+                _ => panic!("Expected Properties namespace"),
+            },
+            Err(e) => panic!("Expected Properties namespace, got error: {e:?}"),
+        }
+
+        let client2 = create_client_no_secret();
+        let namespace = client2.namespace("application").await;
+        match namespace {
+            Ok(namespace) => match namespace {
+                namespace::Namespace::Properties(properties) => {
+                    assert_eq!(properties.get_bool("grayScaleValue"), Some(false));
+                }
+                _ => panic!("Expected Properties namespace"),
+            },
+            Err(e) => panic!("Expected Properties namespace, got error: {e:?}"),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn test_bool_value_with_grayscale_label() {
+        setup();
+        let namespace::Namespace::Properties(properties) = client_with_grayscale_label()
+            .namespace("application")
+            .await
+            .unwrap()
+        else {
+            panic!("Expected Properties namespace");
+        };
+        assert_eq!(
+            properties.get_property::<bool>("grayScaleValue"),
+            Some(true)
+        );
+        let namespace::Namespace::Properties(properties) =
+            client_no_secret().namespace("application").await.unwrap()
+        else {
+            panic!("Expected Properties namespace");
+        };
+        assert_eq!(
+            properties.get_property::<bool>("grayScaleValue"),
+            Some(false)
+        );
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    #[allow(dead_code)]
+    async fn test_bool_value_with_grayscale_label_wasm() {
+        setup();
+        let client1 = create_client_with_grayscale_label();
+        let namespace = client1.namespace("application").await;
+        match namespace {
+            Ok(namespace) => match namespace {
+                namespace::Namespace::Properties(properties) => {
+                    assert_eq!(properties.get_bool("grayScaleValue"), Some(true));
+                }
+                _ => panic!("Expected Properties namespace"),
+            },
+            Err(e) => panic!("Expected Properties namespace, got error: {e:?}"),
+        }
+
+        let client2 = create_client_no_secret();
+        let namespace = client2.namespace("application").await;
+        match namespace {
+            Ok(namespace) => match namespace {
+                namespace::Namespace::Properties(properties) => {
+                    assert_eq!(properties.get_bool("grayScaleValue"), Some(false));
+                }
+                _ => panic!("Expected Properties namespace"),
+            },
+            Err(e) => panic!("Expected Properties namespace, got error: {e:?}"),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn create_client_no_secret() -> Client {
+        let config = ClientConfig::builder("101010101", test_server_url())
+            .build()
+            .expect("test client configuration should be valid");
+        Client::new(config).expect("test client configuration should be valid")
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn create_client_with_secret() -> Client {
+        let config = ClientConfig::builder("101010102", test_server_url())
+            .secret("53bf47631db540ac9700f0020d2192c8")
+            .build()
+            .expect("test client configuration should be valid");
+        Client::new(config).expect("test client configuration should be valid")
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn create_client_with_grayscale_ip() -> Client {
+        let config = ClientConfig::builder("101010101", test_server_url())
+            .ip("1.2.3.4")
+            .build()
+            .expect("test client configuration should be valid");
+        Client::new(config).expect("test client configuration should be valid")
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn create_client_with_grayscale_label() -> Client {
+        let config = ClientConfig::builder("101010101", test_server_url())
+            .label("GrayScale")
+            .build()
+            .expect("test client configuration should be valid");
+        Client::new(config).expect("test client configuration should be valid")
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn subscriptions_replay_current_value_and_stop_after_unsubscribe() {
+        use crate::test_support::{MockHttpsServer, MockResponse};
+
+        fn recording(received: &Arc<Mutex<Vec<String>>>) -> EventListener {
+            let received = received.clone();
+            Arc::new(move |result| {
+                if let Ok(Namespace::Properties(properties)) = result {
+                    received
+                        .lock()
+                        .unwrap()
+                        .extend(properties.get_string("version"));
+                }
+            })
+        }
+
+        let server = MockHttpsServer::new(Arc::new(|index, _| {
+            MockResponse::json(200, format!(r#"{{"version":"{index}"}}"#))
+        }));
+        let temp_dir = TempDir::new("subscription_replay");
+        let config = ClientConfig::builder("subscription-app", server.url())
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .allow_insecure_https(true)
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
+
+        let replayed = Arc::new(Mutex::new(Vec::new()));
+        let plain = Arc::new(Mutex::new(Vec::new()));
+        let replaying = client
+            .add_listener_with_options(
+                "application",
+                recording(&replayed),
+                ListenerOptions::default().replay_current(true),
+            )
+            .await;
+        let plain_subscription = client.add_listener("application", recording(&plain)).await;
+        assert_eq!(*replayed.lock().unwrap(), ["1"]);
+        assert!(plain.lock().unwrap().is_empty());
+
+        client.refresh("application").await.unwrap();
+        assert_eq!(*replayed.lock().unwrap(), ["1", "2"]);
+        assert_eq!(*plain.lock().unwrap(), ["2"]);
+
+        assert!(replaying.unsubscribe());
+        drop(plain_subscription);
+        client.refresh("application").await.unwrap();
+        assert_eq!(*replayed.lock().unwrap(), ["1", "2"]);
+        assert_eq!(*plain.lock().unwrap(), ["2"]);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn watch_streams_current_value_then_reports_lag_and_errors() {
+        use crate::test_support::{MockHttpsServer, MockResponse};
+
+        let server = MockHttpsServer::new(Arc::new(|index, _| {
+            if index == 5 {
+                MockResponse::json(500, "unavailable")
+            } else {
+                MockResponse::json(200, format!(r#"{{"version":"{index}"}}"#))
+            }
+        }));
+        let temp_dir = TempDir::new("watch_stream");
+        let config = ClientConfig::builder("watch-app", server.url())
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .allow_insecure_https(true)
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
+        let version = |update: Option<Result<Namespace, Error>>| match update {
+            Some(Ok(Namespace::Properties(properties))) => properties.get_string("version"),
+            other => panic!("unexpected update: {other:?}"),
+        };
+
+        let mut updates = Box::pin(client.watch("application").await);
+        assert_eq!(version(updates.next().await).as_deref(), Some("1"));
+        client.refresh("application").await.unwrap();
+        assert_eq!(version(updates.next().await).as_deref(), Some("2"));
+
+        client.refresh("application").await.unwrap();
+        client.refresh("application").await.unwrap();
+        assert!(matches!(updates.next().await, Some(Err(Error::Lagged(1)))));
+        assert_eq!(version(updates.next().await).as_deref(), Some("4"));
+
+        assert!(client.refresh("application").await.is_err());
+        assert!(matches!(updates.next().await, Some(Err(Error::Refresh(_)))));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn bound_values_update_and_keep_the_last_good_value_on_failure() {
+        use crate::test_support::{MockHttpsServer, MockResponse};
+
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        struct Pool {
+            size: u32,
+        }
+
+        let server = MockHttpsServer::new(Arc::new(|index, _| {
+            let content = match index {
+                1 => r#"{\"size\":10}"#,
+                2 => r#"{\"size\":\"large\"}"#,
+                _ => r#"{\"size\":30}"#,
+            };
+            MockResponse::json(200, format!(r#"{{"content":"{content}"}}"#))
+        }));
+        let temp_dir = TempDir::new("live_config");
+        let config = ClientConfig::builder("live-config-app", server.url())
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .allow_insecure_https(true)
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
+
+        let pool = client.bind::<Pool>("pool.json").await.unwrap();
+        let reader = pool.clone();
+        assert_eq!(*reader.load(), Pool { size: 10 });
+        let outcomes = Arc::new(Mutex::new(Vec::new()));
+        let outcomes_in_listener = outcomes.clone();
+        let _subscription = pool
+            .add_listener(Arc::new(move |result| {
+                outcomes_in_listener.lock().unwrap().push(
+                    result
+                        .map(|pool| pool.size)
+                        .map_err(|error| error.to_string()),
+                );
+            }))
+            .await;
+
+        client.refresh("pool.json").await.unwrap();
+        assert_eq!(*reader.load(), Pool { size: 10 });
+        assert!(pool.last_error().unwrap().contains("pool.json"));
+
+        client.refresh("pool.json").await.unwrap();
+        assert_eq!(*reader.load(), Pool { size: 30 });
+        assert_eq!(pool.last_error(), None);
+        assert!(matches!(&outcomes.lock().unwrap()[..], [Err(_), Ok(30)]));
+        assert!(client.bind::<Vec<String>>("pool.json").await.is_err());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn properties_bind_to_numeric_and_nested_fields() {
+        use crate::test_support::{MockHttpsServer, MockResponse};
+
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        struct Settings {
+            db: Database,
+        }
+
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        struct Database {
+            host: String,
+            port: u16,
+            ssl: bool,
+            pool: Pool,
+        }
+
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        struct Pool {
+            max_size: u32,
+        }
+
+        let server = MockHttpsServer::new(Arc::new(|index, _| {
+            let port = if index == 1 { "5432" } else { "6432" };
+            MockResponse::json(
+                200,
+                format!(
+                    r#"{{"db.host":"localhost","db.port":"{port}","db.ssl":"true","db.pool.max-size":"16"}}"#
+                ),
+            )
+        }));
+        let temp_dir = TempDir::new("live_config_properties");
+        let config = ClientConfig::builder("live-config-app", server.url())
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .allow_insecure_https(true)
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
+
+        let settings = client.bind::<Settings>("application").await.unwrap();
+        assert_eq!(
+            settings.load().db,
+            Database {
+                host: "localhost".to_string(),
+                port: 5432,
+                ssl: true,
+                pool: Pool { max_size: 16 },
+            }
+        );
+
+        client.refresh("application").await.unwrap();
+        assert_eq!(settings.load().db.port, 6432);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn config_structs_load_and_rebind_with_every_key_error() {
+        use crate::test_support::{MockHttpsServer, MockResponse};
+
+        #[derive(Debug, PartialEq)]
+        struct Pool {
+            size: u32,
+            name: String,
+        }
+
+        impl ApolloConfig for Pool {
+            fn from_source(source: &typed::Source) -> Result<Self, typed::Error> {
+                let mut fields = source.fields();
+                let size = fields.required("pool.size");
+                let name = fields.required("pool.name");
+                fields.finish(size.zip(name).map(|(size, name)| Self { size, name }))
+            }
+        }
+
+        let server = MockHttpsServer::new(Arc::new(|index, _| match index {
+            1 => MockResponse::json(200, r#"{"pool.size":"10","pool.name":"main"}"#),
+            2 => MockResponse::json(200, r#"{"pool.size":"large"}"#),
+            _ => MockResponse::json(200, r#"{"pool.size":"30","pool.name":"main"}"#),
+        }));
+        let temp_dir = TempDir::new("config_struct");
+        let config = ClientConfig::builder("config-struct-app", server.url())
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .allow_insecure_https(true)
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
+
+        let expected = Pool {
+            size: 10,
+            name: "main".to_string(),
+        };
+        assert_eq!(
+            client.load_config::<Pool>("application").await.unwrap(),
+            expected
+        );
+        let pool = client.bind_config::<Pool>("application").await.unwrap();
+        assert_eq!(*pool.load(), expected);
+
+        client.refresh("application").await.unwrap();
+        let Err(Error::InvalidConfig { source, .. }) =
+            client.load_config::<Pool>("application").await
+        else {
+            panic!("missing and invalid keys were accepted");
+        };
+        assert_eq!(source.keys().len(), 2);
+        assert_eq!(*pool.load(), expected);
+        let last_error = pool.last_error().unwrap();
+        assert!(last_error.contains("pool.size") && last_error.contains("pool.name is missing"));
+
+        client.refresh("application").await.unwrap();
+        assert_eq!(pool.load().size, 30);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn composite_listeners_fire_only_when_the_effective_view_changes() {
+        use crate::test_support::{MockHttpsServer, MockResponse};
+
+        let server = MockHttpsServer::new(Arc::new(|_, request| {
+            static SHARED: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+            if request.contains("/shared") {
+                let version = SHARED.fetch_add(1, Ordering::Relaxed).min(2);
+                MockResponse::json(200, format!(r#"{{"timeout":"{}"}}"#, 30 + version))
+            } else {
+                MockResponse::json(200, r#"{"timeout":"10","name":"app"}"#)
+            }
+        }));
+        let temp_dir = TempDir::new("composite");
+        let config = ClientConfig::builder("composite-app", server.url())
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .allow_insecure_https(true)
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
+
+        let composite = client.composite(&["application", "shared"]).await.unwrap();
+        assert_eq!(
+            composite.snapshot().get_string("timeout").as_deref(),
+            Some("30")
+        );
+        assert_eq!(composite.snapshot().source("timeout"), Some("shared"));
+        let timeouts = Arc::new(Mutex::new(Vec::new()));
+        let timeouts_in_listener = timeouts.clone();
+        let _subscription = composite
+            .add_listener(Arc::new(move |result| {
+                timeouts_in_listener
+                    .lock()
+                    .unwrap()
+                    .extend(result.unwrap().get_property::<u32>("timeout"));
+            }))
+            .await;
+
+        client.refresh("application").await.unwrap();
+        client.refresh("shared").await.unwrap();
+        client.refresh("shared").await.unwrap();
+        client.refresh("shared").await.unwrap();
+        assert_eq!(*timeouts.lock().unwrap(), [31, 32]);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn forgotten_namespaces_release_listeners_and_optionally_their_cache_file() {
+        use crate::test_support::{MockHttpsServer, MockResponse};
+
+        let server = MockHttpsServer::new(Arc::new(|_, _| {
+            MockResponse::json(200, r#"{"key":"value"}"#)
+        }));
+        let temp_dir = TempDir::new("forget");
+        let config = ClientConfig::builder("forget-app", server.url())
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .allow_insecure_https(true)
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
+        let cache_files = || {
+            std::fs::read_dir(client.config.get_cache_dir())
+                .map(Iterator::count)
+                .unwrap_or_default()
+        };
+
+        // Namespaces with listeners are never idle.
+        client.namespace("application").await.unwrap();
+        let subscription = client.add_listener("application", Arc::new(|_| {})).await;
+        expire_idle(&client.namespaces, 0).await;
+        assert!(client.namespaces.read().await.contains_key("application"));
+        drop(subscription);
+        expire_idle(&client.namespaces, 0).await;
+        assert!(!client.namespaces.read().await.contains_key("application"));
+
+        let subscription = client.add_listener("application", Arc::new(|_| {})).await;
+        assert!(client.forget("application").await);
+        assert!(!subscription.is_active());
+        assert!(!client.forget("application").await);
+        assert_eq!(cache_files(), 1);
+
+        client.namespace("application").await.unwrap();
+        assert!(client.forget_and_delete("application").await.unwrap());
+        assert_eq!(cache_files(), 0);
+        assert!(!client.forget_and_delete("application").await.unwrap());
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "shared-cache"))]
+    #[tokio::test]
+    async fn leadership_is_released_after_a_concurrent_namespace_update() {
+        let temp_dir = TempDir::new("release_leadership");
+        let config = ClientConfig::builder("release-app", "http://127.0.0.1:9")
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .shared_cache(true)
+            .build()
+            .unwrap();
+        let leader = Arc::new(Cache::new(
+            config.clone(),
+            "application",
+            reqwest::Client::new(),
+        ));
+        let sibling = Cache::new(config, "application", reqwest::Client::new());
+        assert!(leader.should_poll());
+        assert!(!sibling.should_poll());
+
+        let namespaces = Arc::new(RwLock::new(HashMap::from([(
+            "application".to_string(),
+            leader,
+        )])));
+        let background = Background::new(namespaces.clone());
+        let update = namespaces.write().await;
+        background.release_leadership();
+        assert!(!sibling.should_poll());
+        drop(update);
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while !sibling.should_poll() {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("leadership was not released");
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn overrides_from_the_file_and_api_replace_remote_values_and_notify() {
+        use crate::test_support::{MockHttpsServer, MockResponse};
+
+        let server = MockHttpsServer::new(Arc::new(|_, request: &str| {
+            if request.contains("/config.yaml") {
+                MockResponse::json(200, r#"{"content":"db:\n  host: remote\n  port: 5432\n"}"#)
+            } else {
+                MockResponse::json(200, r#"{"db.host":"remote","timeout":"30"}"#)
+            }
+        }));
+        let temp_dir = TempDir::new("overrides");
+        let override_file = temp_dir.path().join("overrides.yaml");
+        std::fs::write(&override_file, "application:\n  db.host: file-host\n").unwrap();
+        let config = ClientConfig::builder("override-app", server.url())
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .allow_insecure_https(true)
+            .override_file(override_file.to_string_lossy())
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
+        let Namespace::Properties(properties) = client.namespace("application").await.unwrap()
+        else {
+            panic!("application is a Properties namespace");
+        };
+        assert_eq!(
+            properties.get_string("db.host").as_deref(),
+            Some("file-host")
+        );
+        assert_eq!(properties.get_int("timeout"), Some(30));
+
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let _subscription = client
+            .add_change_listener(
+                "application",
+                Arc::new(move |result| {
+                    let _ = sender.send(result.unwrap());
+                }),
+            )
+            .await;
+        client
+            .set_override("application", "timeout", 5)
+            .await
+            .unwrap();
+        let event = receiver.recv().await.unwrap();
+        assert_eq!(event.changed_keys().collect::<Vec<_>>(), ["timeout"]);
+        assert_eq!(
+            event.change("timeout").unwrap().new_value(),
+            Some(&serde_json::json!("5"))
+        );
+        let overrides = client.overrides("application").await.unwrap();
+        assert_eq!(
+            overrides
+                .iter()
+                .map(|active| (active.key.as_str(), active.source))
+                .collect::<Vec<_>>(),
+            [
+                ("db.host", OverrideSource::File),
+                ("timeout", OverrideSource::Api)
+            ]
+        );
+        assert_eq!(overrides[1].remote_value, Some(serde_json::json!("30")));
+
+        #[cfg(feature = "override-watch")]
+        {
+            std::fs::write(&override_file, "application:\n  db.host: edited-host\n").unwrap();
+            let event = tokio::time::timeout(std::time::Duration::from_secs(5), receiver.recv())
+                .await
+                .expect("override file edit was not observed")
+                .unwrap();
+            assert_eq!(
+                event.change("db.host").unwrap().new_value(),
+                Some(&serde_json::json!("edited-host"))
+            );
+        }
+        assert!(client.remove_override("application", "timeout").await);
+        assert!(receiver.recv().await.unwrap().is_changed("timeout"));
+
+        client
+            .set_override("config.yaml", "/db/port", 6543)
+            .await
+            .unwrap();
+        let Namespace::Yaml(yaml) = client.namespace("config.yaml").await.unwrap() else {
+            panic!("config.yaml is a YAML namespace");
+        };
+        let document: serde_json::Value = yaml.to_object().unwrap();
+        assert_eq!(
+            document,
+            serde_json::json!({"db": {"host": "remote", "port": 6543}})
+        );
+        assert!(matches!(
+            client.set_override("config.yaml", "db.port", 1).await,
+            Err(Error::InvalidOverride { .. })
+        ));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn placeholders_resolve_across_namespaces_and_follow_their_changes() {
+        use crate::test_support::{MockHttpsServer, MockResponse};
+
+        let common = Arc::new(std::sync::Mutex::new(r#"{"db.host":"primary"}"#));
+        let server = MockHttpsServer::new(Arc::new({
+            let common = common.clone();
+            move |_, request: &str| {
+                if request.contains("/common") {
+                    MockResponse::json(200, *common.lock().unwrap())
+                } else if request.contains("/cyclic") {
+                    MockResponse::json(200, r#"{"a":"${b}","b":"${a}"}"#)
+                } else {
+                    MockResponse::json(200, r#"{"db.url":"jdbc://${db.host}/${db.name:orders}"}"#)
+                }
+            }
+        }));
+        let temp_dir = TempDir::new("placeholders");
+        let config = ClientConfig::builder("placeholder-app", server.url())
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .allow_insecure_https(true)
+            .resolve_placeholders(true)
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
+        client.namespace("common").await.unwrap();
+        let Namespace::Properties(properties) = client.namespace("application").await.unwrap()
+        else {
+            panic!("application is a Properties namespace");
         };
         assert_eq!(
-            properties.get_property::<bool>("grayScaleValue"),
-            Some(true)
+            properties.get_string("db.url").as_deref(),
+            Some("jdbc://primary/orders")
         );
-        let namespace::Namespace::Properties(properties) =
-            client_no_secret().namespace("application").await.unwrap()
-        else {
-            panic!("Expected Properties namespace");
-        };
+
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let _subscription = client
+            .add_change_listener(
+                "application",
+                Arc::new(move |result| {
+                    let _ = sender.send(result.unwrap());
+                }),
+            )
+            .await;
+        *common.lock().unwrap() = r#"{"db.host":"replica"}"#;
+        client.refresh("common").await.unwrap();
+        let event = receiver.recv().await.unwrap();
+        assert_eq!(event.changed_keys().collect::<Vec<_>>(), ["db.url"]);
         assert_eq!(
-            properties.get_property::<bool>("grayScaleValue"),
-            Some(false)
+            event.change("db.url").unwrap().new_value(),
+            Some(&serde_json::json!("jdbc://replica/orders"))
         );
+
+        let Err(Error::Cache(cache::Error::PlaceholderCycle { chain, .. })) =
+            client.namespace("cyclic").await
+        else {
+            panic!("a placeholder cycle is an error");
+        };
+        assert_eq!(chain, "cyclic/a -> cyclic/b -> cyclic/a");
     }
 
-    #[cfg(target_arch = "wasm32")]
-    #[wasm_bindgen_test::wasm_bindgen_test]
-    #[allow(dead_code)]
-    async fn test_bool_value_with_grayscale_ip_wasm() {
-        setup();
-        let client1 = create_client_with_grayscale_ip();
-        let namespace = client1.namespace("application").await;
-        match namespace {
-            Ok(namespace) => match namespace {
-                namespace::Namespace::Properties(properties) => {
-                    assert_eq!(properties.get_bool("grayScaleValue"), Some(true));
-                }
-                _ => panic!("Expected Properties namespace"),
-            },
-            Err(e) => panic!("Expected Properties namespace, got error: {e:?}"),
-        }
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn feature_flags_evaluate_report_exposures_and_follow_changes() {
+        use crate::test_support::{MockHttpsServer, MockResponse};
+
+        let server = MockHttpsServer::new(Arc::new(|_, request: &str| {
+            if request.contains("/flags.json") {
+                MockResponse::json(
+                    200,
+                    r#"{"content":"{\"checkout\":{\"type\":\"boolean\",\"deny\":[\"user-13\"]},\"broken\":{\"type\":\"percentage\"}}"}"#,
+                )
+            } else {
+                MockResponse::json(200, r#"{"feature.dark-mode":"100%","db.host":"localhost"}"#)
+            }
+        }));
+        let temp_dir = TempDir::new("flags");
+        let config = ClientConfig::builder("flags-app", server.url())
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .allow_insecure_https(true)
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
 
-        let client2 = create_client_no_secret();
-        let namespace = client2.namespace("application").await;
-        match namespace {
-            Ok(namespace) => match namespace {
-                namespace::Namespace::Properties(properties) => {
-                    assert_eq!(properties.get_bool("grayScaleValue"), Some(false));
-                }
-                _ => panic!("Expected Properties namespace"),
-            },
-            Err(e) => panic!("Expected Properties namespace, got error: {e:?}"),
-        }
+        let flags = client.feature_flags("flags.json").await.unwrap();
+        let exposures = Arc::new(Mutex::new(Vec::new()));
+        let _exposures = flags.add_exposure_listener(Arc::new({
+            let exposures = exposures.clone();
+            move |evaluation| exposures.lock().unwrap().push(evaluation.clone())
+        }));
+        assert!(flags.is_enabled("checkout", "user-1"));
+        let denied = flags.evaluate("checkout", "user-13");
+        assert_eq!(
+            (denied.enabled, denied.reason),
+            (false, EvaluationReason::Denied)
+        );
+        assert_eq!(
+            flags.evaluate("missing", "user-1").reason,
+            EvaluationReason::NotFound
+        );
+        assert_eq!(
+            flags.evaluate("broken", "user-1").reason,
+            EvaluationReason::Invalid
+        );
+        assert!(flags.errors().contains_key("broken"));
+        assert_eq!(exposures.lock().unwrap().len(), 4);
+
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let _subscription = flags
+            .add_listener(Arc::new(move |changed| {
+                let _ = sender.send(changed.to_vec());
+            }))
+            .await;
+        client
+            .set_override("flags.json", "/checkout/enabled", false)
+            .await
+            .unwrap();
+        assert_eq!(receiver.recv().await.unwrap(), ["checkout"]);
+        assert_eq!(
+            flags.evaluate("checkout", "user-1").reason,
+            EvaluationReason::Off
+        );
+
+        let properties = client
+            .feature_flags_with_prefix("application", "feature.")
+            .await
+            .unwrap();
+        assert_eq!(properties.flag_names(), ["dark-mode"]);
+        assert!(properties.is_enabled("dark-mode", "user-1"));
+        assert!(matches!(
+            client.feature_flags("application").await,
+            Err(Error::InvalidFlags { .. })
+        ));
     }
 
     #[cfg(not(target_arch = "wasm32"))]
     #[tokio::test]
-    async fn test_bool_value_with_grayscale_label() {
-        setup();
-        let namespace::Namespace::Properties(properties) = client_with_grayscale_label()
-            .namespace("application")
-            .await
-            .unwrap()
-        else {
-            panic!("Expected Properties namespace");
+    async fn coordinate_handles_share_the_client_but_cache_separately() {
+        use crate::test_support::{MockHttpsServer, MockResponse};
+
+        let server = MockHttpsServer::new(Arc::new(|_, request| {
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+            let owner = path
+                .split('?')
+                .next()
+                .unwrap_or_default()
+                .replace("/configfiles/json/", "");
+            MockResponse::json(200, format!(r#"{{"owner":"{owner}"}}"#))
+        }));
+        let temp_dir = TempDir::new("coordinates");
+        let config = ClientConfig::builder("root-app", server.url())
+            .secret("root-secret")
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .allow_insecure_https(true)
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
+        let platform = client
+            .with_coordinates(Coordinates::new("platform-app"))
+            .unwrap();
+        let pinned = client
+            .with_coordinates(Coordinates::new("root-app").cluster("beijing"))
+            .unwrap();
+        let same = client
+            .with_coordinates(Coordinates::new("root-app"))
+            .unwrap();
+        assert!(client.with_coordinates(Coordinates::new(" ")).is_err());
+
+        let owner = |namespace: Namespace| match namespace {
+            Namespace::Properties(properties) => properties.get_string("owner").unwrap(),
+            other => panic!("unexpected namespace {other:?}"),
         };
         assert_eq!(
-            properties.get_property::<bool>("grayScaleValue"),
-            Some(true)
+            owner(client.namespace("application").await.unwrap()),
+            "root-app/default/application"
         );
-        let namespace::Namespace::Properties(properties) =
-            client_no_secret().namespace("application").await.unwrap()
-        else {
-            panic!("Expected Properties namespace");
+        assert_eq!(
+            owner(platform.namespace("application").await.unwrap()),
+            "platform-app/default/application"
+        );
+        assert_eq!(
+            owner(pinned.namespace("application").await.unwrap()),
+            "root-app/beijing/application"
+        );
+        same.namespace("application").await.unwrap();
+        assert_eq!(server.request_count(), 3);
+
+        // Another app's namespaces are not signed with this app's secret.
+        let requests = server.captured_requests();
+        let signed = |fragment: &str| {
+            requests
+                .iter()
+                .find(|request| request.contains(fragment))
+                .unwrap()
+                .to_ascii_lowercase()
+                .contains("authorization:")
+        };
+        assert!(signed("/root-app/default/"));
+        assert!(!signed("/platform-app/"));
+        assert!(signed("/root-app/beijing/"));
+
+        let keys: Vec<_> = {
+            let namespaces = client.namespaces.read().await;
+            let mut keys: Vec<_> = namespaces.keys().cloned().collect();
+            keys.sort();
+            keys
         };
         assert_eq!(
-            properties.get_property::<bool>("grayScaleValue"),
-            Some(false)
+            keys,
+            [
+                "application",
+                "platform-app+default+application",
+                "root-app+beijing+application"
+            ]
         );
+        assert!(platform.forget("application").await);
+        assert!(client.namespaces.read().await.contains_key("application"));
     }
 
-    #[cfg(target_arch = "wasm32")]
-    #[wasm_bindgen_test::wasm_bindgen_test]
-    #[allow(dead_code)]
-    async fn test_bool_value_with_grayscale_label_wasm() {
-        setup();
-        let client1 = create_client_with_grayscale_label();
-        let namespace = client1.namespace("application").await;
-        match namespace {
-            Ok(namespace) => match namespace {
-                namespace::Namespace::Properties(properties) => {
-                    assert_eq!(properties.get_bool("grayScaleValue"), Some(true));
-                }
-                _ => panic!("Expected Properties namespace"),
-            },
-            Err(e) => panic!("Expected Properties namespace, got error: {e:?}"),
-        }
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn readiness_reports_each_namespace_and_applies_the_policy() {
+        use crate::test_support::{MockHttpsServer, MockResponse};
+        use std::time::Duration;
+
+        static CACHED_SERVED: AtomicBool = AtomicBool::new(false);
+        let server = MockHttpsServer::new(Arc::new(|_, request| {
+            if request.contains("/broken") {
+                MockResponse::json(500, "unavailable")
+            } else if request.contains("/slow") {
+                MockResponse::json(200, "{}").delayed_body(Duration::from_secs(5))
+            } else if request.contains("/cached") && CACHED_SERVED.swap(true, Ordering::AcqRel) {
+                MockResponse::json(500, "unavailable")
+            } else {
+                MockResponse::json(200, r#"{"key":"value"}"#)
+            }
+        }));
+        let temp_dir = TempDir::new("readiness");
+        let builder = ClientConfig::builder("readiness-app", server.url())
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .allow_insecure_https(true);
+        Client::new(builder.clone().build().unwrap())
+            .unwrap()
+            .namespace("cached")
+            .await
+            .unwrap();
+        // With a zero TTL the persisted entry is stale until Apollo confirms it.
+        let client = Client::new(builder.cache_ttl(0).build().unwrap()).unwrap();
+
+        let policy = ReadinessPolicy::default().optional("broken");
+        let report = client
+            .wait_until_ready(
+                &["application", "cached", "broken"],
+                Duration::from_secs(5),
+                &policy,
+            )
+            .await;
+        assert!(report.is_ready());
+        let status = |name: &str| report.namespace(name).unwrap().status.clone();
+        assert_eq!(status("application"), NamespaceStatus::Fresh);
+        assert_eq!(
+            status("cached"),
+            NamespaceStatus::Stale {
+                refresh_error: None
+            }
+        );
+        assert!(matches!(status("broken"), NamespaceStatus::Failed { .. }));
+        assert!(!report.namespace("broken").unwrap().required);
 
-        let client2 = create_client_no_secret();
-        let namespace = client2.namespace("application").await;
-        match namespace {
-            Ok(namespace) => match namespace {
-                namespace::Namespace::Properties(properties) => {
-                    assert_eq!(properties.get_bool("grayScaleValue"), Some(false));
-                }
-                _ => panic!("Expected Properties namespace"),
-            },
-            Err(e) => panic!("Expected Properties namespace, got error: {e:?}"),
-        }
-    }
+        let strict = ReadinessPolicy::default().accept_stale(false);
+        let report = client
+            .wait_until_ready(&["cached"], Duration::from_secs(5), &strict)
+            .await;
+        assert!(!report.is_ready());
+        assert!(matches!(
+            &report.namespaces()[0].status,
+            NamespaceStatus::Stale {
+                refresh_error: Some(_)
+            }
+        ));
 
-    #[cfg(target_arch = "wasm32")]
-    fn create_client_no_secret() -> Client {
-        let config = ClientConfig::builder("101010101", test_server_url())
-            .build()
-            .expect("test client configuration should be valid");
-        Client::new(config).expect("test client configuration should be valid")
+        // Optional namespaces still loading at the deadline do not block.
+        let policy = ReadinessPolicy::default().optional("slow");
+        let report = client
+            .wait_until_ready(
+                &["slow", "application"],
+                Duration::from_millis(200),
+                &policy,
+            )
+            .await;
+        assert!(report.is_ready());
+        assert_eq!(report.namespaces()[0].status, NamespaceStatus::Pending);
+
+        // A required failure ends the wait before the deadline.
+        let started = std::time::Instant::now();
+        let report = client
+            .wait_until_ready(
+                &["broken", "slow"],
+                Duration::from_secs(30),
+                &ReadinessPolicy::default(),
+            )
+            .await;
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert!(!report.is_ready());
+        assert_eq!(
+            report.namespace("slow").unwrap().status,
+            NamespaceStatus::Pending
+        );
+        assert_eq!(
+            serde_json::to_value(report.namespace("slow").unwrap()).unwrap(),
+            serde_json::json!({
+                "namespace": "slow",
+                "required": true,
+                "ready": false,
+                "status": {"status": "pending"}
+            })
+        );
     }
 
-    #[cfg(target_arch = "wasm32")]
-    fn create_client_with_secret() -> Client {
-        let config = ClientConfig::builder("101010102", test_server_url())
-            .secret("53bf47631db540ac9700f0020d2192c8")
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn key_listeners_fire_only_for_matching_keys_with_typed_values() {
+        use crate::test_support::{MockHttpsServer, MockResponse};
+
+        let server = MockHttpsServer::new(Arc::new(|index, _| {
+            MockResponse::json(
+                200,
+                format!(
+                    r#"{{"db.pool.size":"{}","db.pool.timeout":"5","other":"{index}"}}"#,
+                    index.min(2) * 10
+                ),
+            )
+        }));
+        let temp_dir = TempDir::new("key_listener");
+        let config = ClientConfig::builder("key-listener-app", server.url())
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .allow_insecure_https(true)
             .build()
-            .expect("test client configuration should be valid");
-        Client::new(config).expect("test client configuration should be valid")
-    }
+            .unwrap();
+        let client = Client::new(config).unwrap();
+        client.namespace("application").await.unwrap();
 
-    #[cfg(target_arch = "wasm32")]
-    fn create_client_with_grayscale_ip() -> Client {
-        let config = ClientConfig::builder("101010101", test_server_url())
-            .ip("1.2.3.4")
-            .build()
-            .expect("test client configuration should be valid");
-        Client::new(config).expect("test client configuration should be valid")
-    }
+        let sizes = Arc::new(Mutex::new(Vec::new()));
+        let sizes_in_callback = sizes.clone();
+        let _size = client
+            .on_key_change::<u32, _>("application", "db.pool.size", move |change| {
+                sizes_in_callback
+                    .lock()
+                    .unwrap()
+                    .push((change.old_value, change.new_value));
+            })
+            .await;
+        let pool_keys = Arc::new(Mutex::new(Vec::new()));
+        let pool_keys_in_callback = pool_keys.clone();
+        let _pool = client
+            .on_key_change::<String, _>("application", "db.pool", move |change| {
+                pool_keys_in_callback.lock().unwrap().push(change.key);
+            })
+            .await;
 
-    #[cfg(target_arch = "wasm32")]
-    fn create_client_with_grayscale_label() -> Client {
-        let config = ClientConfig::builder("101010101", test_server_url())
-            .label("GrayScale")
-            .build()
-            .expect("test client configuration should be valid");
-        Client::new(config).expect("test client configuration should be valid")
+        client.refresh("application").await.unwrap();
+        client.refresh("application").await.unwrap();
+        assert_eq!(*sizes.lock().unwrap(), [(Some(10), Some(20))]);
+        assert_eq!(*pool_keys.lock().unwrap(), ["db.pool.size"]);
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -1545,8 +3538,17 @@ mod tests {
             #[cfg(not(target_arch = "wasm32"))]
             refresh_interval: None,
             request_timeout: None,
+            namespace_idle_timeout: None,
+            override_env_prefix: None,
+            resolve_placeholders: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            override_file: None,
             #[cfg(not(target_arch = "wasm32"))]
             http_client: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            cache_layout: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            shared_cache: None,
         };
 
         let client = Client::new(config).expect("test client configuration should be valid");
@@ -1572,7 +3574,7 @@ mod tests {
             // For a test, this is acceptable to signal issues.
         });
 
-        client.add_listener("application", listener).await;
+        let _subscription = client.add_listener("application", listener).await;
 
         let cache = client.cache("application").await;
 
@@ -1638,7 +3640,9 @@ mod tests {
         );
 
         let client = create_client_no_secret();
-        client.add_listener_wasm("application", js_listener).await;
+        let _subscription = client
+            .add_listener_wasm("application", js_listener, None)
+            .await;
 
         let cache = client.cache("application").await;
         match cache.refresh().await {
@@ -1697,7 +3701,14 @@ mod tests {
             cache_ttl: None,
             refresh_interval: None,
             request_timeout: None,
+            namespace_idle_timeout: None,
+            override_env_prefix: None,
+            resolve_placeholders: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            override_file: None,
             http_client: None,
+            cache_layout: None,
+            shared_cache: None,
         };
 
         let client =
@@ -1725,7 +3736,7 @@ mod tests {
             });
         });
 
-        client.add_listener("application", listener).await;
+        let _subscription = client.add_listener("application", listener).await;
 
         let test_body = async {
             let _ = client.namespace("application").await;
@@ -1755,12 +3766,19 @@ mod tests {
             cache_ttl: None,
             refresh_interval: Some(1), // 1 second interval for fast testing
             request_timeout: None,
+            namespace_idle_timeout: None,
+            override_env_prefix: None,
+            resolve_placeholders: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            override_file: None,
             http_client: None,
+            cache_layout: None,
+            shared_cache: None,
         };
 
         let path = "/configfiles/json/101010101/default/application";
         let before = mock_request_count(path);
-        let mut client = Client::new(config).expect("test client configuration should be valid");
+        let client = Client::new(config).expect("test client configuration should be valid");
         // Preload namespace so it's registered
         let _ = client.namespace("application").await;
 
@@ -1785,23 +3803,97 @@ mod tests {
             .refresh_interval(60)
             .build()
             .unwrap();
-        let mut client = Client::new(config).unwrap();
+        let client = Client::new(config).unwrap();
         client.start().await.unwrap();
         assert!(matches!(client.start().await, Err(Error::AlreadyRunning)));
         tokio::time::timeout(std::time::Duration::from_millis(50), client.stop())
             .await
             .expect("stop waited for the refresh interval");
-        assert!(!client.running.load(Ordering::Acquire));
+        assert!(!client.background.running.load(Ordering::Acquire));
 
-        client.start().await.unwrap();
-        let running = client.running.clone();
-        let abort_handle = client.handle.as_ref().unwrap().abort_handle();
+        // Clones share polling, which stops with the last clone.
+        let clone = client.clone();
+        clone.start().await.unwrap();
+        assert!(matches!(client.start().await, Err(Error::AlreadyRunning)));
+        let running = client.background.running.clone();
+        let abort_handle = client
+            .background
+            .task
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .abort_handle
+            .clone();
         drop(client);
         tokio::task::yield_now().await;
+        assert!(running.load(Ordering::Acquire));
+        drop(clone);
+        tokio::task::yield_now().await;
         assert!(!running.load(Ordering::Acquire));
         assert!(abort_handle.is_finished());
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn shutdown_drains_in_flight_refreshes_and_cancels_at_the_deadline() {
+        use crate::test_support::{MockHttpsServer, MockResponse};
+        use std::time::Duration;
+
+        let server = MockHttpsServer::new(Arc::new(|index, _| {
+            let delay = match index {
+                1 => Duration::ZERO,
+                2 => Duration::from_millis(300),
+                _ => Duration::from_secs(5),
+            };
+            MockResponse::json(200, format!(r#"{{"version":"{index}"}}"#)).delayed_body(delay)
+        }));
+        let temp_dir = TempDir::new("shutdown");
+        let config = ClientConfig::builder("shutdown-app", server.url())
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .allow_insecure_https(true)
+            .refresh_interval(60)
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
+        client.namespace("application").await.unwrap();
+        let versions = Arc::new(Mutex::new(Vec::new()));
+        let versions_in_listener = versions.clone();
+        let _subscription = client
+            .add_listener(
+                "application",
+                Arc::new(move |result| {
+                    if let Ok(Namespace::Properties(properties)) = result {
+                        versions_in_listener
+                            .lock()
+                            .unwrap()
+                            .extend(properties.get_string("version"));
+                    }
+                }),
+            )
+            .await;
+
+        // The first poll is in flight; shutdown waits for it and its listener,
+        // then wakes the loop from its 60-second interval.
+        client.clone().start().await.unwrap();
+        server.wait_for_requests(2).await;
+        client.shutdown(Duration::from_secs(5)).await.unwrap();
+        assert_eq!(*versions.lock().unwrap(), ["2"]);
+        assert!(!client.background.running.load(Ordering::Acquire));
+
+        // Work still running at the deadline is cancelled.
+        client.start().await.unwrap();
+        server.wait_for_requests(3).await;
+        let started = std::time::Instant::now();
+        assert!(matches!(
+            client.shutdown(Duration::from_millis(100)).await,
+            Err(Error::ShutdownTimeout(_))
+        ));
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(*versions.lock().unwrap(), ["2"]);
+        client.shutdown(Duration::from_secs(1)).await.unwrap();
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     #[tokio::test]
     async fn preload_supports_parallel_duplicates_and_propagates_http_errors() {
@@ -1920,6 +4012,11 @@ mod tests {
             cache_ttl: Some(600),
             refresh_interval: Some(30),
             request_timeout: Some(10),
+            namespace_idle_timeout: None,
+            override_env_prefix: None,
+            resolve_placeholders: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            override_file: None,
         };
 
         // Construct mock config data in cache format directly using JSON value
@@ -1977,6 +4074,11 @@ mod tests {
             cache_ttl: Some(600),
             refresh_interval: Some(30),
             request_timeout: Some(10),
+            namespace_idle_timeout: None,
+            override_env_prefix: None,
+            resolve_placeholders: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            override_file: None,
         };
         let cache1 = cache::Cache::new(config1, "application", reqwest::Client::new());
         assert!(cache1.wasm_cache_key().starts_with("apollo_cache_v2_"));
@@ -1994,6 +4096,11 @@ mod tests {
             cache_ttl: Some(600),
             refresh_interval: Some(30),
             request_timeout: Some(10),
+            namespace_idle_timeout: None,
+            override_env_prefix: None,
+            resolve_placeholders: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            override_file: None,
         };
         let cache2 = cache::Cache::new(config2, "application", reqwest::Client::new());
         assert_ne!(cache1.wasm_cache_key(), cache2.wasm_cache_key());
@@ -2011,6 +4118,11 @@ mod tests {
             cache_ttl: Some(600),
             refresh_interval: Some(30),
             request_timeout: Some(10),
+            namespace_idle_timeout: None,
+            override_env_prefix: None,
+            resolve_placeholders: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            override_file: None,
         };
         let cache3 = cache::Cache::new(config3, "other_namespace", reqwest::Client::new());
         assert_ne!(cache1.wasm_cache_key(), cache3.wasm_cache_key());
@@ -2028,6 +4140,11 @@ mod tests {
             cache_ttl: Some(600),
             refresh_interval: Some(30),
             request_timeout: Some(10),
+            namespace_idle_timeout: None,
+            override_env_prefix: None,
+            resolve_placeholders: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            override_file: None,
         };
         let cache4 = cache::Cache::new(config4, "application", reqwest::Client::new());
         assert_ne!(cache1.wasm_cache_key(), cache4.wasm_cache_key());
@@ -2037,14 +4154,14 @@ mod tests {
     #[wasm_bindgen_test::wasm_bindgen_test]
     fn test_wasm_lifecycle_starts_stops_and_rejects_duplicate_start() {
         setup();
-        let mut client = create_client_no_secret();
+        let client = create_client_no_secret();
         client.start_wasm().unwrap();
-        assert!(client.running.load(Ordering::Acquire));
-        assert!(client.abort_handle.is_some());
+        assert!(client.background.running.load(Ordering::Acquire));
+        assert!(client.background.task.lock().unwrap().is_some());
         assert!(matches!(client.start_wasm(), Err(Error::AlreadyRunning)));
         client.stop_wasm();
-        assert!(!client.running.load(Ordering::Acquire));
-        assert!(client.abort_handle.is_none());
+        assert!(!client.background.running.load(Ordering::Acquire));
+        assert!(client.background.task.lock().unwrap().is_none());
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -2055,7 +4172,7 @@ mod tests {
             .refresh_interval(1)
             .build()
             .unwrap();
-        let mut client = Client::new(config).unwrap();
+        let client = Client::new(config).unwrap();
         client.namespace("application").await.unwrap();
 
         client.start_wasm().unwrap();
@@ -2103,6 +4220,11 @@ mod tests {
             cache_ttl: Some(600),
             refresh_interval: Some(30),
             request_timeout: Some(10),
+            namespace_idle_timeout: None,
+            override_env_prefix: None,
+            resolve_placeholders: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            override_file: None,
         };
 
         // Construct client. This will trigger the log::warn! call.
@@ -2133,7 +4255,14 @@ mod tests {
             cache_ttl: None,
             refresh_interval: None,
             request_timeout: None,
+            namespace_idle_timeout: None,
+            override_env_prefix: None,
+            resolve_placeholders: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            override_file: None,
             http_client: Some(custom_client),
+            cache_layout: None,
+            shared_cache: None,
         };
 
         let client = Client::new(config).expect("test client configuration should be valid");
@@ -2182,29 +4311,49 @@ mod tests {
             cache_ttl: None,
             refresh_interval: Some(1), // 1 second interval
             request_timeout: None,
+            namespace_idle_timeout: None,
+            override_env_prefix: None,
+            resolve_placeholders: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            override_file: None,
             http_client: None,
+            cache_layout: None,
+            shared_cache: None,
         };
 
-        let mut client = Client::new(config).expect("test client configuration should be valid");
+        let client = Client::new(config).expect("test client configuration should be valid");
         let _ = client.namespace("application").await;
         let _ = client.namespace("failing").await;
 
-        let before_app = server.request_count_for_path("/configfiles/json/101010101/default/application");
-        let before_fail = server.request_count_for_path("/configfiles/json/101010101/default/failing");
+        let before_app =
+            server.request_count_for_path("/configfiles/json/101010101/default/application");
+        let before_fail =
+            server.request_count_for_path("/configfiles/json/101010101/default/failing");
 
-        client.start().await.expect("Failed to start client background task");
+        client
+            .start()
+            .await
+            .expect("Failed to start client background task");
 
         tokio::time::sleep(std::time::Duration::from_millis(2500)).await;
 
         client.stop().await;
 
-        let after_app = server.request_count_for_path("/configfiles/json/101010101/default/application");
-        let after_fail = server.request_count_for_path("/configfiles/json/101010101/default/failing");
+        let after_app =
+            server.request_count_for_path("/configfiles/json/101010101/default/application");
+        let after_fail =
+            server.request_count_for_path("/configfiles/json/101010101/default/failing");
 
         let app_refreshes = after_app.saturating_sub(before_app);
         let fail_refreshes = after_fail.saturating_sub(before_fail);
 
-        assert!(app_refreshes >= 2, "Expected at least 2 refreshes for healthy namespace, got {app_refreshes}");
-        assert!(fail_refreshes <= 1, "Expected at most 1 refresh for failing namespace due to backoff, got {fail_refreshes}");
+        assert!(
+            app_refreshes >= 2,
+            "Expected at least 2 refreshes for healthy namespace, got {app_refreshes}"
+        );
+        assert!(
+            fail_refreshes <= 1,
+            "Expected at most 1 refresh for failing namespace due to backoff, got {fail_refreshes}"
+        );
     }
 }
@@ -0,0 +1,248 @@
+//! Live typed configuration handles.
+//!
+//! [`crate::Client::bind`] deserializes a namespace once per change into a
+//! [`LiveConfig`], so hot paths read a shared `Arc<T>` instead of converting
+//! the namespace on every access.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use apollo_rust_client::{Client, client_config::ClientConfig};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Database {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = ClientConfig::builder("my-app", "http://apollo-server:8080").build()?;
+//! let client = Client::new(config)?;
+//!
+//! let database = client.bind::<Database>("database.json").await?;
+//! let current = database.load();
+//! println!("{}:{}", current.host, current.port);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    ApolloConfig, Error, Subscription,
+    cache::Cache,
+    namespace::{Namespace, get_namespace},
+    subscription::{Notification, Notifier},
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::sync::{Arc, PoisonError, RwLock};
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        /// Listener for a [`LiveConfig`], called after each update attempt.
+        pub type LiveConfigListener<T> = Arc<dyn Fn(Result<Arc<T>, Error>)>;
+    } else {
+        /// Listener for a [`LiveConfig`], called after each update attempt.
+        ///
+        /// Listeners receive the newly bound value, or the error that left the
+        /// previous value in place.
+        pub type LiveConfigListener<T> = Arc<dyn Fn(Result<Arc<T>, Error>) + Send + Sync>;
+    }
+}
+
+/// Latest bound value and the outcome of the most recent update.
+struct Slot<T> {
+    value: RwLock<Arc<T>>,
+    last_error: RwLock<Option<String>>,
+}
+
+struct Inner<T> {
+    slot: Arc<Slot<T>>,
+    cache: Arc<Cache>,
+    _subscription: Subscription,
+}
+
+/// A namespace deserialized into `T` and kept up to date.
+///
+/// Cloning is cheap and clones share the same value. The handle stays bound
+/// until its last clone is dropped.
+pub struct LiveConfig<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Clone for LiveConfig<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for LiveConfig<T> {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("LiveConfig")
+            .field("last_error", &self.last_error())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Converts a raw cached value of a namespace into a bound value.
+type Convert<T> = fn(&str, &Value) -> Result<T, String>;
+
+impl<T> LiveConfig<T>
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+{
+    /// Loads and deserializes `cache`, then keeps the value current.
+    pub(crate) async fn bind(cache: Arc<Cache>) -> Result<Self, Error> {
+        Self::bind_with(cache, deserialize::<T>).await
+    }
+}
+
+impl<T> LiveConfig<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Loads and converts `cache` with `convert`, then keeps the value
+    /// current.
+    pub(crate) async fn bind_with(cache: Arc<Cache>, convert: Convert<T>) -> Result<Self, Error> {
+        let initial =
+            convert(cache.namespace(), &cache.get_value().await?).map_err(Error::Deserialize)?;
+        let slot = Arc::new(Slot {
+            value: RwLock::new(Arc::new(initial)),
+            last_error: RwLock::new(None),
+        });
+
+        // Replaying the current value closes the gap between the load above
+        // and registration.
+        let updater = slot.clone();
+        let notifier: Notifier = Arc::new(move |notification| {
+            if let Notification::Changed { config, event } = notification {
+                updater.update(convert(event.namespace(), config));
+            }
+        });
+        let subscription = cache
+            .subscribe(
+                notifier,
+                crate::ListenerOptions::default().replay_current(true),
+            )
+            .await;
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                slot,
+                cache,
+                _subscription: subscription,
+            }),
+        })
+    }
+
+    /// Returns the current value.
+    ///
+    /// This is a synchronous pointer copy; the value is only deserialized when
+    /// the namespace changes.
+    #[must_use]
+    pub fn load(&self) -> Arc<T> {
+        self.inner
+            .slot
+            .value
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Registers a listener that runs after each update attempt.
+    ///
+    /// Successful updates deliver the new value. An update that does not
+    /// deserialize into `T` delivers [`Error::Deserialize`] while
+    /// [`LiveConfig::load`] keeps returning the last good value, and refresh
+    /// failures deliver [`Error::Refresh`]. The listener stays registered
+    /// while the returned [`Subscription`] is alive.
+    pub async fn add_listener(&self, listener: LiveConfigListener<T>) -> Subscription {
+        let slot = self.inner.slot.clone();
+        let notifier: Notifier = Arc::new(move |notification| match notification {
+            Notification::Changed { .. } => match slot.last_error() {
+                Some(error) => listener(Err(Error::Deserialize(error))),
+                None => listener(Ok(slot
+                    .value
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .clone())),
+            },
+            Notification::Failed(error) => listener(Err(Error::Refresh((*error).to_string()))),
+        });
+        self.inner
+            .cache
+            .subscribe(notifier, crate::ListenerOptions::default())
+            .await
+    }
+}
+
+impl<T> LiveConfig<T> {
+    /// Returns why the most recent update was rejected, or `None` if it was
+    /// applied.
+    #[must_use]
+    pub fn last_error(&self) -> Option<String> {
+        self.inner.slot.last_error()
+    }
+}
+
+impl<T> Slot<T> {
+    fn update(&self, result: Result<T, String>) {
+        let mut last_error = self
+            .last_error
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        match result {
+            Ok(value) => {
+                *self.value.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(value);
+                *last_error = None;
+            }
+            Err(error) => {
+                log::warn!("Keeping the previous bound configuration: {error}");
+                *last_error = Some(error);
+            }
+        }
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.last_error
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+/// Deserializes a raw cached value with the conversion of its namespace format.
+///
+/// Properties bind through [`Properties::to_object`], so dotted keys nest and
+/// string values convert to the field types.
+///
+/// [`Properties::to_object`]: crate::namespace::properties::Properties::to_object
+pub(crate) fn deserialize<T: DeserializeOwned + 'static>(
+    namespace: &str,
+    config: &Value,
+) -> Result<T, String> {
+    let result = match get_namespace(namespace, config.clone()) {
+        Ok(Namespace::Properties(properties)) => {
+            properties.to_object().map_err(|error| error.to_string())
+        }
+        Ok(Namespace::Json(json)) => json.to_object().map_err(|error| error.to_string()),
+        Ok(Namespace::Yaml(yaml)) => yaml.to_object().map_err(|error| error.to_string()),
+        Ok(Namespace::Text(text)) => {
+            T::deserialize(Value::String(text)).map_err(|error| error.to_string())
+        }
+        Err(error) => Err(error.to_string()),
+    };
+    result.map_err(|error| format!("namespace {namespace}: {error}"))
+}
+
+/// Loads a raw cached value into an [`ApolloConfig`] struct.
+pub(crate) fn load_config<T: ApolloConfig>(namespace: &str, config: &Value) -> Result<T, String> {
+    get_namespace(namespace, config.clone())
+        .map_err(|error| error.to_string())
+        .and_then(|converted| T::from_namespace(&converted).map_err(|error| error.to_string()))
+        .map_err(|error| format!("namespace {namespace}: {error}"))
+}
@@ -0,0 +1,629 @@
+//! Relaxed binding of Properties keys to nested types.
+//!
+//! Flat keys are split into a tree: `db.pool.max` nests maps, and `[n]` or
+//! `.n` segments such as `servers[0].host` or `servers.0.host` nest
+//! sequences, whose indices must run from 0 without gaps. A branch bound to
+//! a map stays a map even when its keys are numbers. The tree deserializes by the rules of Spring's relaxed binding:
+//! string values convert to whatever scalar the target asks for, a single
+//! value holding commas fills a sequence, and struct fields match keys written
+//! in kebab case, camel case or snake case.
+
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, Error as _,
+    IntoDeserializer, MapAccess, Unexpected, VariantAccess, Visitor,
+};
+use serde_json::{Error, Value};
+use std::collections::BTreeMap;
+
+/// A Properties key tree.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Node {
+    /// The value of a key.
+    Leaf(String),
+    /// The keys under a common prefix, by their next segment.
+    Branch(BTreeMap<String, Node>),
+}
+
+impl Node {
+    /// Builds the tree of a Properties object.
+    ///
+    /// When a key is both set and a prefix of other keys, as `db` is with
+    /// `db.host`, the nested keys win.
+    pub(crate) fn from_properties(properties: &Value) -> Self {
+        let mut root = Self::Branch(BTreeMap::new());
+        for (key, value) in properties.as_object().into_iter().flatten() {
+            let value = match value {
+                Value::String(text) => text.clone(),
+                Value::Number(_) | Value::Bool(_) => value.to_string(),
+                Value::Null | Value::Array(_) | Value::Object(_) => continue,
+            };
+            root.insert(&segments(key), value);
+        }
+        root
+    }
+
+    /// Returns the subtree under `prefix`, or an empty branch if no key has
+    /// the prefix.
+    pub(crate) fn into_subtree(self, prefix: &str) -> Self {
+        segments(prefix)
+            .iter()
+            .try_fold(self, |node, segment| match node {
+                Self::Branch(mut children) => children.remove(*segment),
+                Self::Leaf(_) => None,
+            })
+            .unwrap_or_else(|| Self::Branch(BTreeMap::new()))
+    }
+
+    fn insert(&mut self, segments: &[&str], value: String) {
+        let Some((first, rest)) = segments.split_first() else {
+            if !matches!(self, Self::Branch(children) if !children.is_empty()) {
+                *self = Self::Leaf(value);
+            }
+            return;
+        };
+        if let Self::Leaf(_) = self {
+            *self = Self::Branch(BTreeMap::new());
+        }
+        if let Self::Branch(children) = self {
+            children
+                .entry((*first).to_string())
+                .or_insert_with(|| Self::Branch(BTreeMap::new()))
+                .insert(rest, value);
+        }
+    }
+
+    /// Returns the children in index order if every segment is an index.
+    ///
+    /// Indices must run from 0 without gaps, as Spring requires; otherwise
+    /// the error names the first missing index.
+    fn elements(children: &BTreeMap<String, Self>) -> Option<Result<Vec<&Self>, Error>> {
+        let mut elements = children
+            .iter()
+            .map(|(segment, node)| Some((segment.parse::<usize>().ok()?, node)))
+            .collect::<Option<Vec<_>>>()?;
+        elements.sort_by_key(|(index, _)| *index);
+        if let Some(missing) = elements
+            .iter()
+            .enumerate()
+            .find_map(|(position, (index, _))| (*index != position).then_some(position))
+        {
+            return Some(Err(Error::custom(format!(
+                "list index {missing} is missing"
+            ))));
+        }
+        Some(Ok(elements.into_iter().map(|(_, node)| node).collect()))
+    }
+}
+
+/// Splits a key into segments, treating `[n]` like `.n`.
+fn segments(key: &str) -> Vec<&str> {
+    key.split(['.', '[', ']'])
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// Parses a boolean the way Spring does: `true`, `on`, `yes` and `1`, or
+/// `false`, `off`, `no` and `0`, ignoring case.
+pub(crate) fn parse_bool(text: &str) -> Option<bool> {
+    match text.trim().to_ascii_lowercase().as_str() {
+        "true" | "on" | "yes" | "1" => Some(true),
+        "false" | "off" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Deserializes one Properties value by the relaxed rules.
+pub(crate) fn from_str<T: DeserializeOwned>(text: &str) -> Result<T, Error> {
+    T::deserialize(Scalar(text))
+}
+
+/// Lowercases a name and drops separators, so `pool-size`, `poolSize` and
+/// `pool_size` compare equal.
+fn canonical(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|character| character.to_ascii_lowercase())
+        .collect()
+}
+
+/// Returns the variant `text` names, ignoring case and separators, or `text`
+/// itself if it names none.
+fn variant<'a>(text: &'a str, variants: &'static [&'static str]) -> &'a str {
+    let text = text.trim();
+    let name = canonical(text);
+    variants
+        .iter()
+        .find(|variant| canonical(variant) == name)
+        .map_or(text, |variant| variant)
+}
+
+/// Deserializes one string value into the scalar the target asks for.
+struct Scalar<'a>(&'a str);
+
+macro_rules! parse_number {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                match self.0.trim().parse() {
+                    Ok(number) => visitor.$visit(number),
+                    Err(_) => Err(Error::invalid_value(Unexpected::Str(self.0), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Scalar<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_borrowed_str(self.0)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match parse_bool(self.0) {
+            Some(value) => visitor.visit_bool(value),
+            None => Err(Error::invalid_value(Unexpected::Str(self.0), &visitor)),
+        }
+    }
+
+    parse_number! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let items = self
+            .0
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty());
+        visitor.visit_seq(de::value::SeqDeserializer::<_, Error>::new(
+            items.map(Scalar),
+        ))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(variant(self.0, variants).into_deserializer())
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit_struct tuple tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Scalar<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for &'de Node {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Node::Leaf(text) => visitor.visit_borrowed_str(text),
+            Node::Branch(children) => match Node::elements(children) {
+                Some(Ok(elements)) if !elements.is_empty() => visitor.visit_seq(
+                    de::value::SeqDeserializer::<_, Error>::new(elements.into_iter()),
+                ),
+                _ => visitor.visit_map(Entries::new(children, None)),
+            },
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Node::Leaf(text) => Scalar(text).deserialize_bool(visitor),
+            Node::Branch(_) => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.scalar(visitor, Scalar::deserialize_i64)
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.scalar(visitor, Scalar::deserialize_i128)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.scalar(visitor, Scalar::deserialize_u64)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.scalar(visitor, Scalar::deserialize_u128)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.scalar(visitor, Scalar::deserialize_f64)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.scalar(visitor, Scalar::deserialize_i8)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.scalar(visitor, Scalar::deserialize_i16)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.scalar(visitor, Scalar::deserialize_i32)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.scalar(visitor, Scalar::deserialize_u8)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.scalar(visitor, Scalar::deserialize_u16)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.scalar(visitor, Scalar::deserialize_u32)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.scalar(visitor, Scalar::deserialize_f32)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Node::Leaf(text) => Scalar(text).deserialize_seq(visitor),
+            Node::Branch(children) => match Node::elements(children) {
+                Some(Ok(elements)) => visitor.visit_seq(
+                    de::value::SeqDeserializer::<_, Error>::new(elements.into_iter()),
+                ),
+                Some(Err(error)) => Err(error),
+                None => Err(Error::invalid_type(Unexpected::Map, &visitor)),
+            },
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Node::Leaf(_) => self.deserialize_any(visitor),
+            Node::Branch(children) => visitor.visit_map(Entries::new(children, None)),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self {
+            Node::Leaf(text) => Err(Error::invalid_type(Unexpected::Str(text), &visitor)),
+            Node::Branch(children) => visitor.visit_map(Entries::new(children, Some(fields))),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self {
+            Node::Leaf(text) => visitor.visit_enum(variant(text, variants).into_deserializer()),
+            Node::Branch(children) => match children.iter().next() {
+                Some((variant, value)) if children.len() == 1 => {
+                    visitor.visit_enum(Variant { variant, value })
+                }
+                _ => Err(Error::invalid_value(
+                    Unexpected::Map,
+                    &"a map with a single variant key",
+                )),
+            },
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct identifier
+    }
+}
+
+impl Node {
+    /// Parses a leaf as a scalar; branches are left to the visitor to reject.
+    fn scalar<'de, V: Visitor<'de>>(
+        &'de self,
+        visitor: V,
+        parse: fn(Scalar<'de>, V) -> Result<V::Value, Error>,
+    ) -> Result<V::Value, Error> {
+        match self {
+            Node::Leaf(text) => parse(Scalar(text), visitor),
+            Node::Branch(_) => self.deserialize_any(visitor),
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for &'de Node {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// The entries of a branch, with keys matched to struct fields.
+struct Entries<'de> {
+    children: std::collections::btree_map::Iter<'de, String, Node>,
+    fields: Option<&'static [&'static str]>,
+    value: Option<&'de Node>,
+}
+
+impl<'de> Entries<'de> {
+    fn new(children: &'de BTreeMap<String, Node>, fields: Option<&'static [&'static str]>) -> Self {
+        Self {
+            children: children.iter(),
+            fields,
+            value: None,
+        }
+    }
+
+    /// Returns the field `key` binds to: itself if it is a field, otherwise
+    /// the field it matches after dropping separators and case.
+    fn field(&self, key: &'de str) -> &'de str {
+        let Some(fields) = self.fields else {
+            return key;
+        };
+        if fields.contains(&key) {
+            return key;
+        }
+        let key_name = canonical(key);
+        fields
+            .iter()
+            .find(|field| canonical(field) == key_name)
+            .copied()
+            .unwrap_or(key)
+    }
+}
+
+impl<'de> MapAccess<'de> for Entries<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some((key, value)) = self.children.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+        seed.deserialize(Scalar(self.field(key))).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(value),
+            None => Err(Error::custom("value requested before its key")),
+        }
+    }
+}
+
+/// A single-key branch read as an enum variant and its content.
+struct Variant<'de> {
+    variant: &'de str,
+    value: &'de Node,
+}
+
+impl<'de> EnumAccess<'de> for Variant<'de> {
+    type Error = Error;
+    type Variant = &'de Node;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Error> {
+        Ok((seed.deserialize(Scalar(self.variant))?, self.value))
+    }
+}
+
+impl<'de> VariantAccess<'de> for &'de Node {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_struct("", fields, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Mode {
+        Primary,
+        Replica,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Server {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Database {
+        url: String,
+        pool_size: u32,
+        ssl: bool,
+        ratio: f64,
+        mode: Mode,
+        tags: Vec<String>,
+        servers: Vec<Server>,
+        timeout: Option<u64>,
+    }
+
+    #[test]
+    fn binds_nested_keys_sequences_and_relaxed_names() {
+        let tree = Node::from_properties(&json!({
+            "db": "shadowed",
+            "db.url": "postgres://db",
+            "db.pool-size": "16",
+            "db.ssl": "on",
+            "db.ratio": "0.5",
+            "db.mode": "replica",
+            "db.tags": "a, b,c",
+            "db.servers[1].host": "second",
+            "db.servers[1].port": "5433",
+            "db.servers.0.host": "first",
+            "db.servers.0.port": 5432,
+            "other": "ignored",
+        }));
+        let database = Database::deserialize(&tree.into_subtree("db.")).unwrap();
+        assert_eq!(
+            database,
+            Database {
+                url: "postgres://db".to_string(),
+                pool_size: 16,
+                ssl: true,
+                ratio: 0.5,
+                mode: Mode::Replica,
+                tags: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                servers: vec![
+                    Server {
+                        host: "first".to_string(),
+                        port: 5432,
+                    },
+                    Server {
+                        host: "second".to_string(),
+                        port: 5433,
+                    },
+                ],
+                timeout: None,
+            }
+        );
+        assert_eq!(
+            Mode::deserialize(&Node::Leaf("primary".to_string())).unwrap(),
+            Mode::Primary
+        );
+    }
+
+    #[test]
+    fn binds_branches_with_numeric_keys_to_maps() {
+        let tree = Node::from_properties(&json!({"codes.404": "nf", "codes.500": "err"}));
+        let codes = tree.into_subtree("codes");
+        assert_eq!(
+            BTreeMap::<u16, String>::deserialize(&codes).unwrap(),
+            BTreeMap::from([(404, "nf".to_string()), (500, "err".to_string())])
+        );
+        assert_eq!(
+            std::collections::HashMap::<String, String>::deserialize(&codes).unwrap()["404"],
+            "nf"
+        );
+        assert_eq!(
+            Value::deserialize(&codes).unwrap(),
+            json!({"404": "nf", "500": "err"})
+        );
+    }
+
+    #[test]
+    fn reports_values_that_do_not_convert() {
+        let tree = Node::from_properties(&json!({"port": "http", "host": "localhost"}));
+        let error = Server::deserialize(&tree).unwrap_err();
+        assert!(error.to_string().contains("\"http\""), "{error}");
+
+        let sparse = Node::from_properties(&json!({"servers[0]": "a", "servers[5]": "b"}));
+        let error = Vec::<String>::deserialize(&sparse.into_subtree("servers")).unwrap_err();
+        assert!(
+            error.to_string().contains("list index 1 is missing"),
+            "{error}"
+        );
+
+        let missing = Node::from_properties(&json!({})).into_subtree("absent");
+        assert!(Option::<u8>::deserialize(&missing).is_err());
+        assert_eq!(
+            BTreeMap::<String, String>::deserialize(&missing).unwrap(),
+            BTreeMap::new()
+        );
+    }
+}
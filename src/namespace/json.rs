@@ -24,6 +24,8 @@
 //! assert_eq!(value["name"], "MyApp");
 //! ```
 
+use super::path::lookup;
+use super::properties::parse_scalar;
 use log::trace;
 use serde::de::DeserializeOwned;
 
@@ -185,6 +187,74 @@ impl Json {
     pub fn to_object<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
         serde_json::from_value(self.value.clone())
     }
+
+    /// Gets the value at `path` as a string.
+    ///
+    /// `path` is a JSON Pointer such as `/database/hosts/0`, or a dotted path
+    /// such as `database.hosts[0]` or `database.hosts.0`. Numbers and
+    /// booleans are converted to text; objects, arrays and `null` return
+    /// `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use apollo_rust_client::namespace::json::Json;
+    ///
+    /// let response = json!({
+    ///     "content": r#"{"database":{"hosts":["db-1","db-2"],"port":5432}}"#
+    /// });
+    /// let json_namespace = Json::try_from(response).unwrap();
+    ///
+    /// assert_eq!(json_namespace.get_string("database.hosts[1]"), Some("db-2".to_string()));
+    /// assert_eq!(json_namespace.get_string("/database/port"), Some("5432".to_string()));
+    /// ```
+    #[must_use]
+    pub fn get_string(&self, path: &str) -> Option<String> {
+        parse_scalar(lookup(&self.value, path)?)
+    }
+
+    /// Gets the value at `path` as an integer, parsing strings such as
+    /// `"8080"`. See [`Json::get_string`] for the path syntax.
+    #[must_use]
+    pub fn get_int(&self, path: &str) -> Option<i64> {
+        parse_scalar(lookup(&self.value, path)?)
+    }
+
+    /// Gets the value at `path` as a boolean, parsing the strings `"true"`
+    /// and `"false"`. See [`Json::get_string`] for the path syntax.
+    #[must_use]
+    pub fn get_bool(&self, path: &str) -> Option<bool> {
+        parse_scalar(lookup(&self.value, path)?)
+    }
+
+    /// Deserializes the value at `path`, returning `None` if there is no
+    /// value there or it does not deserialize into `T`. See
+    /// [`Json::get_string`] for the path syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use apollo_rust_client::namespace::json::Json;
+    ///
+    /// let response = json!({"content": r#"{"database":{"hosts":["db-1","db-2"]}}"#});
+    /// let json_namespace = Json::try_from(response).unwrap();
+    ///
+    /// let hosts: Option<Vec<String>> = json_namespace.get("database.hosts");
+    /// assert_eq!(hosts, Some(vec!["db-1".to_string(), "db-2".to_string()]));
+    /// ```
+    #[must_use]
+    pub fn get<T: DeserializeOwned>(&self, path: &str) -> Option<T> {
+        T::deserialize(lookup(&self.value, path)?).ok()
+    }
+
+    /// Returns whether `path` addresses a value, `null` included. See
+    /// [`Json::get_string`] for the path syntax.
+    #[must_use]
+    pub fn exists(&self, path: &str) -> bool {
+        lookup(&self.value, path).is_some()
+    }
 }
 
 /// Converts a `serde_json::Value` into a `Json` instance.
@@ -266,6 +336,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn path_queries_read_typed_values() {
+        let json_namespace = crate::namespace::json::Json::try_from(serde_json::json!({
+            "content": r#"{"server": {"port": "8080", "tls": true, "hosts": ["a", "b"], "proxy": null}}"#
+        }))
+        .unwrap();
+
+        assert_eq!(json_namespace.get_int("server.port"), Some(8080));
+        assert_eq!(json_namespace.get_bool("/server/tls"), Some(true));
+        assert_eq!(
+            json_namespace.get_string("server.hosts[0]"),
+            Some("a".to_string())
+        );
+        assert_eq!(json_namespace.get_string("server.hosts"), None);
+        assert_eq!(
+            json_namespace.get::<Vec<String>>("server.hosts"),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(json_namespace.get::<u16>("server.tls"), None);
+        assert!(json_namespace.exists("server.proxy"));
+        assert!(!json_namespace.exists("server.hosts[2]"));
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     #[tokio::test]
     async fn test_namespace_to_object() {
@@ -26,8 +26,11 @@ use json::Json;
 use properties::Properties;
 use yaml::Yaml;
 
+pub(crate) mod binding;
 pub mod json;
+pub mod path;
 pub mod properties;
+mod units;
 pub mod yaml;
 
 /// Comprehensive error types that can occur when working with namespaces.
@@ -229,6 +232,73 @@ fn get_namespace_type(namespace: &str) -> NamespaceType {
     }
 }
 
+/// Returns whether a namespace holds Properties key-value pairs.
+pub(crate) fn is_properties(namespace: &str) -> bool {
+    get_namespace_type(namespace) == NamespaceType::Properties
+}
+
+/// Returns whether a namespace holds a JSON or YAML document.
+pub(crate) fn is_document(namespace: &str) -> bool {
+    matches!(
+        get_namespace_type(namespace),
+        NamespaceType::Json | NamespaceType::Yaml
+    )
+}
+
+/// Returns the parsed document of a JSON or YAML namespace.
+///
+/// `config` is the raw cached value whose `content` field holds the document.
+/// Returns `None` for other formats or when the content does not parse.
+pub(crate) fn document_tree(
+    namespace: &str,
+    config: &serde_json::Value,
+) -> Option<serde_json::Value> {
+    let content = config.get("content")?.as_str()?;
+    match get_namespace_type(namespace) {
+        NamespaceType::Json => serde_json::from_str(content).ok(),
+        NamespaceType::Yaml => yaml::to_json_value(content).ok(),
+        NamespaceType::Properties | NamespaceType::Text | NamespaceType::Xml => None,
+    }
+}
+
+/// Renders a configuration value in `java.util.Properties` format.
+///
+/// Objects, including parsed JSON and YAML documents, are flattened to one
+/// entry per leaf keyed by dotted path, so `{"db": {"hosts": ["a"]}}` becomes
+/// `db.hosts.0=a`. Keys and values are escaped as the Java client's cache
+/// files are, with non-ASCII characters written as `\uXXXX`.
+///
+/// # Examples
+///
+/// ```rust
+/// use apollo_rust_client::namespace::to_properties;
+/// use serde_json::json;
+///
+/// let text = to_properties(&json!({"db": {"host": "a b", "ports": [80]}}));
+/// assert_eq!(text, "db.host=a b\ndb.ports.0=80\n");
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub fn to_properties(value: &serde_json::Value) -> String {
+    let mut leaves = std::collections::BTreeMap::new();
+    crate::change::flatten_document(String::new(), value.clone(), &mut leaves);
+    let entries = leaves
+        .into_iter()
+        .map(|(pointer, value)| {
+            let key = pointer
+                .split('/')
+                .skip(1)
+                .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+                .collect::<Vec<_>>()
+                .join(".");
+            (key, value)
+        })
+        .collect::<serde_json::Map<_, _>>();
+    let mut content = String::new();
+    crate::cache::java::write_entries(&mut content, &entries);
+    content
+}
+
 /// Creates a `Namespace` instance from a namespace identifier and JSON value.
 ///
 /// This function serves as the main entry point for converting raw JSON data
@@ -279,7 +349,9 @@ fn get_namespace_type(namespace: &str) -> NamespaceType {
 /// ```
 pub(crate) fn get_namespace(namespace: &str, value: serde_json::Value) -> Result<Namespace, Error> {
     match get_namespace_type(namespace) {
-        NamespaceType::Properties => Ok(Namespace::Properties(properties::Properties::from(value))),
+        NamespaceType::Properties => Ok(Namespace::Properties(
+            properties::Properties::from(value).in_namespace(namespace),
+        )),
         NamespaceType::Json => Ok(Namespace::Json(json::Json::try_from(value)?)),
         NamespaceType::Yaml => Ok(Namespace::Yaml(yaml::Yaml::try_from(value)?)),
         NamespaceType::Text => {
@@ -0,0 +1,45 @@
+//! Path queries into JSON and YAML documents.
+
+use serde_json::Value;
+
+/// Returns the node at `path`.
+///
+/// A path starting with `/`, or the empty path, is a JSON Pointer. Any other
+/// path is dotted, with array indices written `[n]` or `.n`, as in
+/// `servers[0].host` or `servers.0.host`.
+#[must_use]
+pub fn lookup<'a>(root: &'a Value, path: &str) -> Option<&'a Value> {
+    if path.is_empty() || path.starts_with('/') {
+        return root.pointer(path);
+    }
+    path.split('.')
+        .flat_map(|segment| segment.split(['[', ']']))
+        .filter(|segment| !segment.is_empty())
+        .try_fold(root, |node, segment| match node {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lookup;
+    use serde_json::json;
+
+    #[test]
+    fn resolves_pointers_and_dotted_paths_with_indices() {
+        let document = json!({
+            "db": {"servers": [{"host": "a"}, {"host": "b"}], "a/b": 1, "7": "seven"}
+        });
+        assert_eq!(lookup(&document, "db.servers[1].host"), Some(&json!("b")));
+        assert_eq!(lookup(&document, "db.servers.0.host"), Some(&json!("a")));
+        assert_eq!(lookup(&document, "/db/servers/1/host"), Some(&json!("b")));
+        assert_eq!(lookup(&document, "/db/a~1b"), Some(&json!(1)));
+        assert_eq!(lookup(&document, "db.7"), Some(&json!("seven")));
+        assert_eq!(lookup(&document, ""), Some(&document));
+        assert_eq!(lookup(&document, "db.servers[2]"), None);
+        assert_eq!(lookup(&document, "db.servers.first"), None);
+        assert_eq!(lookup(&document, "db.servers[0].host.name"), None);
+    }
+}
@@ -24,9 +24,15 @@
 //! app.debug = true
 //! ```
 
-use log::debug;
+use super::binding::{Node, parse_bool};
+use super::units::{parse_duration, parse_size};
+use log::{debug, warn};
 #[cfg(target_arch = "wasm32")]
 use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{LazyLock, Mutex, PoisonError};
+use std::time::Duration;
 use wasm_bindgen::prelude::wasm_bindgen;
 
 /// A wrapper around `serde_json::Value` for properties-style configuration data.
@@ -68,6 +74,10 @@ use wasm_bindgen::prelude::wasm_bindgen;
 pub struct Properties {
     /// The underlying JSON value containing the properties data
     value: serde_json::Value,
+    /// The namespace the properties were read from, if known
+    namespace: Option<String>,
+    /// The prefix stripped from the keys by [`Properties::with_prefix`]
+    prefix: String,
 }
 
 impl Properties {
@@ -111,16 +121,297 @@ impl Properties {
     pub fn get_property<T: std::str::FromStr>(&self, key: &str) -> Option<T> {
         debug!("Getting property for key {key}");
 
-        let value = self.value.get(key)?;
-        match value {
-            serde_json::Value::String(value) => value.parse::<T>().ok(),
-            serde_json::Value::Number(_) | serde_json::Value::Bool(_) => {
-                value.to_string().parse::<T>().ok()
+        parse_scalar(self.value.get(key)?)
+    }
+
+    /// Gets a property like [`Properties::get_property`], returning `default`
+    /// if the key is missing or its value does not parse.
+    ///
+    /// A value that is set but does not parse is logged as a warning, once
+    /// per namespace and key until the key holds another value.
+    #[must_use]
+    pub fn get_property_or<T: std::str::FromStr>(&self, key: &str, default: T) -> T {
+        self.or_default(
+            key,
+            std::any::type_name::<T>(),
+            self.get_property(key),
+            default,
+        )
+    }
+
+    /// Gets a property as a [`Duration`].
+    ///
+    /// Values are numbers with a unit suffix, `ns`, `us`, `ms`, `s`, `m`,
+    /// `h` or `d`, ignoring case. Components add up, so `1h30m` is ninety
+    /// minutes, and a bare number is in milliseconds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use apollo_rust_client::namespace::properties::Properties;
+    /// use serde_json::json;
+    /// use std::time::Duration;
+    ///
+    /// let properties = Properties::from(json!({"timeout": "30s", "retry.delay": "250"}));
+    /// assert_eq!(properties.get_duration("timeout"), Some(Duration::from_secs(30)));
+    /// assert_eq!(properties.get_duration("retry.delay"), Some(Duration::from_millis(250)));
+    /// ```
+    #[must_use]
+    pub fn get_duration(&self, key: &str) -> Option<Duration> {
+        parse_duration(&self.get_string(key)?)
+    }
+
+    /// Gets a property as a [`Duration`] like [`Properties::get_duration`],
+    /// falling back to `default` like [`Properties::get_property_or`].
+    #[must_use]
+    pub fn get_duration_or(&self, key: &str, default: Duration) -> Duration {
+        self.or_default(key, "duration", self.get_duration(key), default)
+    }
+
+    /// Gets a property naming a variant of `T`, ignoring case and `-` or `_`
+    /// separators, so `read-only`, `READ_ONLY` and `ReadOnly` all name a
+    /// `ReadOnly` variant.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use apollo_rust_client::namespace::properties::Properties;
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    ///
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// enum Level {
+    ///     Debug,
+    ///     Info,
+    /// }
+    ///
+    /// let properties = Properties::from(json!({"log.level": "INFO"}));
+    /// assert_eq!(properties.get_enum("log.level"), Some(Level::Info));
+    /// ```
+    #[must_use]
+    pub fn get_enum<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        T::deserialize(&Node::Leaf(self.get_string(key)?)).ok()
+    }
+
+    /// Gets a property as a map of `key: value` entries.
+    ///
+    /// Entries are separated by `entry_separator` and split at the first
+    /// `key_value_separator`, with surrounding whitespace trimmed and empty
+    /// entries skipped. Returns `None` if an entry has no
+    /// `key_value_separator`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use apollo_rust_client::namespace::properties::Properties;
+    /// use serde_json::json;
+    ///
+    /// let properties = Properties::from(json!({"labels": "team: core, tier: web"}));
+    /// let labels = properties.get_map("labels", ",", ":").unwrap();
+    /// assert_eq!(labels["tier"], "web");
+    /// ```
+    #[must_use]
+    pub fn get_map(
+        &self,
+        key: &str,
+        entry_separator: &str,
+        key_value_separator: &str,
+    ) -> Option<BTreeMap<String, String>> {
+        let value = self.get_string(key)?;
+        split(&value, entry_separator)
+            .map(|entry| {
+                let (key, value) = entry.split_once(key_value_separator)?;
+                Some((key.trim().to_string(), value.trim().to_string()))
+            })
+            .collect()
+    }
+
+    /// Returns `parsed`, or `default` with a warning if `key` is set to a
+    /// value that did not parse as `kind`.
+    fn or_default<T>(&self, key: &str, kind: &str, parsed: Option<T>, default: T) -> T {
+        if parsed.is_none()
+            && let Some(value) = self.value.get(key).filter(|value| !value.is_null())
+        {
+            let value = match value {
+                serde_json::Value::String(text) => text.clone(),
+                value => value.to_string(),
+            };
+            let namespace = self.namespace.as_deref().unwrap_or_default();
+            let key = format!("{}{key}", self.prefix);
+            if first_warning(namespace, &key, &value) {
+                let location = match &self.namespace {
+                    Some(namespace) => format!("{key} of namespace {namespace}"),
+                    None => key,
+                };
+                warn!("Property {location} = {value:?} is not a valid {kind}, using the default");
             }
-            serde_json::Value::Null
-            | serde_json::Value::Array(_)
-            | serde_json::Value::Object(_) => None,
         }
+        parsed.unwrap_or(default)
+    }
+
+    /// Deserializes the properties into a nested type.
+    ///
+    /// Dotted keys nest: `db.host` and `db.pool.max` fill `db: { host,
+    /// pool: { max } }`. Segments written `[n]` or `.n`, as in
+    /// `servers[0].host` or `servers.0.host`, fill sequences, and a single
+    /// value such as `a, b, c` fills a sequence of its comma-separated items.
+    /// Like Spring's relaxed binding, string values convert to the numbers,
+    /// booleans (`true`/`false`, `on`/`off`, `yes`/`no`, `1`/`0`) and enum
+    /// variants, matched ignoring case, the target asks for, and
+    /// `pool-size`, `poolSize` and `pool_size` all bind to a field named
+    /// `pool_size`.
+    ///
+    /// When a key is both set and a prefix of other keys, as `db` is with
+    /// `db.host`, the nested keys win.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the value that does not convert, or the
+    /// field that is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use apollo_rust_client::namespace::properties::Properties;
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     db: Database,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Database {
+    ///     host: String,
+    ///     pool: Pool,
+    ///     replicas: Vec<String>,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Pool {
+    ///     max: u32,
+    ///     fair: bool,
+    /// }
+    ///
+    /// let properties = Properties::from(json!({
+    ///     "db.host": "localhost",
+    ///     "db.pool.max": "16",
+    ///     "db.pool.fair": "yes",
+    ///     "db.replicas[0]": "replica-1",
+    ///     "db.replicas[1]": "replica-2",
+    /// }));
+    ///
+    /// let config: Config = properties.to_object().unwrap();
+    /// assert_eq!(config.db.pool.max, 16);
+    /// assert!(config.db.pool.fair);
+    /// assert_eq!(config.db.replicas, ["replica-1", "replica-2"]);
+    /// ```
+    pub fn to_object<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        T::deserialize(&Node::from_properties(&self.value))
+    }
+
+    /// Deserializes the keys under `prefix` into a nested type, as
+    /// [`Properties::to_object`] does for all keys.
+    ///
+    /// `prefix` is a key path such as `db` or `db.pool`; a trailing `.` is
+    /// ignored. Without keys under the prefix, the value deserializes from an
+    /// empty map.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the value that does not convert, or the
+    /// field that is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use apollo_rust_client::namespace::properties::Properties;
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Pool {
+    ///     max_size: u32,
+    /// }
+    ///
+    /// let properties = Properties::from(json!({"db.pool.max-size": "16"}));
+    /// let pool: Pool = properties.to_object_with_prefix("db.pool").unwrap();
+    /// assert_eq!(pool.max_size, 16);
+    /// ```
+    pub fn to_object_with_prefix<T: DeserializeOwned>(
+        &self,
+        prefix: &str,
+    ) -> Result<T, serde_json::Error> {
+        T::deserialize(&Node::from_properties(&self.value).into_subtree(prefix))
+    }
+
+    /// Returns the keys of the scalar entries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use apollo_rust_client::namespace::properties::Properties;
+    /// use serde_json::json;
+    ///
+    /// let properties = Properties::from(json!({"db.host": "localhost", "db.port": "5432"}));
+    /// let keys: Vec<&str> = properties.keys().collect();
+    /// assert_eq!(keys, ["db.host", "db.port"]);
+    /// ```
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries().map(|(key, _)| key)
+    }
+
+    /// Returns the scalar entries, with numbers and booleans as text as
+    /// [`Properties::get_string`] returns them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use apollo_rust_client::namespace::properties::Properties;
+    /// use serde_json::json;
+    ///
+    /// let properties = Properties::from(json!({"db.host": "localhost", "db.port": 5432}));
+    /// for (key, value) in properties.iter() {
+    ///     println!("{key} = {value}");
+    /// }
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, String)> {
+        self.entries()
+            .filter_map(|(key, value)| Some((key, parse_scalar(value)?)))
+    }
+
+    /// Copies the scalar entries into a map ordered by key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use apollo_rust_client::namespace::properties::Properties;
+    /// use serde_json::json;
+    ///
+    /// let properties = Properties::from(json!({"db.host": "localhost", "db.ssl": true}));
+    /// let map = properties.to_map();
+    /// assert_eq!(map["db.ssl"], "true");
+    /// ```
+    #[must_use]
+    pub fn to_map(&self) -> BTreeMap<String, String> {
+        self.iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect()
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (&str, &serde_json::Value)> {
+        self.value
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter(|(_, value)| is_scalar(value))
+            .map(|(key, value)| (key.as_str(), value))
+    }
+
+    /// Returns the underlying key-value object.
+    pub(crate) fn as_value(&self) -> &serde_json::Value {
+        &self.value
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -264,6 +555,289 @@ impl Properties {
     pub fn get_bool(&self, key: &str) -> Option<bool> {
         self.get_property::<bool>(key)
     }
+
+    /// Get a property as a boolean, accepting `true`/`false`, `yes`/`no`,
+    /// `on`/`off` and `1`/`0`, ignoring case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use apollo_rust_client::namespace::properties::Properties;
+    ///
+    /// let properties = Properties::from(json!({"cache.enabled": "Yes", "debug": "off"}));
+    /// assert_eq!(properties.get_bool_lenient("cache.enabled"), Some(true));
+    /// assert_eq!(properties.get_bool_lenient("debug"), Some(false));
+    /// ```
+    #[must_use]
+    pub fn get_bool_lenient(&self, key: &str) -> Option<bool> {
+        parse_bool(&self.get_string(key)?)
+    }
+
+    /// Get a property as a byte size.
+    ///
+    /// Values are numbers with an optional unit suffix, `B`, `KB`, `MB`, `GB`
+    /// or `TB`, ignoring case; the `B` may be left out and `KiB`-style units
+    /// are accepted too. Every multiple is a power of 1024, as in Spring and
+    /// JVM flags, and a bare number is in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use apollo_rust_client::namespace::properties::Properties;
+    ///
+    /// let properties = Properties::from(json!({"upload.max": "512MB"}));
+    /// assert_eq!(properties.get_size("upload.max"), Some(512 * 1024 * 1024));
+    /// ```
+    #[must_use]
+    pub fn get_size(&self, key: &str) -> Option<u64> {
+        parse_size(&self.get_string(key)?)
+    }
+
+    /// Get a property as a list of the items between `separator`s, with
+    /// surrounding whitespace trimmed and empty items skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use apollo_rust_client::namespace::properties::Properties;
+    ///
+    /// let properties = Properties::from(json!({"hosts": "a.example, b.example,"}));
+    /// assert_eq!(
+    ///     properties.get_list("hosts", ","),
+    ///     Some(vec!["a.example".to_string(), "b.example".to_string()])
+    /// );
+    /// ```
+    #[must_use]
+    pub fn get_list(&self, key: &str, separator: &str) -> Option<Vec<String>> {
+        let value = self.get_string(key)?;
+        Some(split(&value, separator).map(str::to_string).collect())
+    }
+
+    /// Get a property as a string, or `default` if the key is missing.
+    #[must_use]
+    pub fn get_string_or(&self, key: &str, default: String) -> String {
+        self.or_default(key, "string", self.get_string(key), default)
+    }
+
+    /// Get a property as an integer, falling back to `default` like
+    /// [`Properties::get_property_or`].
+    #[must_use]
+    pub fn get_int_or(&self, key: &str, default: i64) -> i64 {
+        self.or_default(key, "integer", self.get_int(key), default)
+    }
+
+    /// Get a property as a float, falling back to `default` like
+    /// [`Properties::get_property_or`].
+    #[must_use]
+    pub fn get_float_or(&self, key: &str, default: f64) -> f64 {
+        self.or_default(key, "float", self.get_float(key), default)
+    }
+
+    /// Get a property as a boolean, falling back to `default` like
+    /// [`Properties::get_property_or`].
+    #[must_use]
+    pub fn get_bool_or(&self, key: &str, default: bool) -> bool {
+        self.or_default(key, "boolean", self.get_bool(key), default)
+    }
+
+    /// Get a property as a byte size, falling back to `default` like
+    /// [`Properties::get_property_or`].
+    #[must_use]
+    pub fn get_size_or(&self, key: &str, default: u64) -> u64 {
+        self.or_default(key, "byte size", self.get_size(key), default)
+    }
+
+    /// Returns the number of scalar entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries().count()
+    }
+
+    /// Returns whether there are no scalar entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries().next().is_none()
+    }
+
+    /// Returns whether `key` holds a scalar value.
+    #[must_use]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.value.get(key).is_some_and(is_scalar)
+    }
+
+    /// Returns the entries whose keys start with `prefix`, with the prefix
+    /// stripped from their keys.
+    ///
+    /// The prefix is matched as written, so `with_prefix("db.")` keeps
+    /// `db.host` as `host` but not `db` itself or `dbname`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use apollo_rust_client::namespace::properties::Properties;
+    /// use serde_json::json;
+    ///
+    /// let properties = Properties::from(json!({
+    ///     "db.host": "localhost",
+    ///     "db.port": "5432",
+    ///     "cache.ttl": "60"
+    /// }));
+    ///
+    /// let db = properties.with_prefix("db.");
+    /// assert_eq!(db.get_int("port"), Some(5432));
+    /// assert_eq!(db.len(), 2);
+    /// assert!(!db.contains_key("cache.ttl"));
+    /// ```
+    #[must_use]
+    pub fn with_prefix(&self, prefix: &str) -> Properties {
+        let entries = self
+            .value
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(key, value)| {
+                let key = key.strip_prefix(prefix)?;
+                (!key.is_empty()).then(|| (key.to_string(), value.clone()))
+            })
+            .collect();
+        Self {
+            value: serde_json::Value::Object(entries),
+            namespace: self.namespace.clone(),
+            prefix: format!("{}{prefix}", self.prefix),
+        }
+    }
+
+    /// Records the namespace the properties were read from, which scopes
+    /// their warnings.
+    pub(crate) fn in_namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl Properties {
+    /// Get a property as a duration in milliseconds, with the units of
+    /// [`Properties::get_duration`].
+    #[must_use]
+    #[wasm_bindgen(js_name = "get_duration")]
+    pub fn get_duration_wasm(&self, key: &str) -> Option<f64> {
+        self.get_duration(key)
+            .map(|duration| duration.as_secs_f64() * 1000.0)
+    }
+
+    /// Get a property as a duration in milliseconds, falling back to
+    /// `default` like [`Properties::get_property_or`].
+    #[must_use]
+    #[wasm_bindgen(js_name = "get_duration_or")]
+    pub fn get_duration_or_wasm(&self, key: &str, default: f64) -> f64 {
+        self.or_default(key, "duration", self.get_duration_wasm(key), default)
+    }
+
+    /// Get a property as a plain object of strings, with the separators of
+    /// [`Properties::get_map`].
+    #[must_use]
+    #[wasm_bindgen(js_name = "get_map")]
+    pub fn get_map_wasm(
+        &self,
+        key: &str,
+        entry_separator: &str,
+        key_value_separator: &str,
+    ) -> wasm_bindgen::JsValue {
+        match self.get_map(key, entry_separator, key_value_separator) {
+            Some(map) => map
+                .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+                .unwrap_or_else(|error| {
+                    log::error!("Unable to serialize a Properties map for JavaScript: {error}");
+                    wasm_bindgen::JsValue::NULL
+                }),
+            None => wasm_bindgen::JsValue::UNDEFINED,
+        }
+    }
+
+    /// Returns the keys of the scalar entries as an array of strings.
+    #[must_use]
+    #[wasm_bindgen(js_name = "keys")]
+    pub fn keys_wasm(&self) -> Vec<String> {
+        self.keys().map(str::to_string).collect()
+    }
+
+    /// Returns the scalar entries as an array of `[key, value]` pairs, like
+    /// `Object.entries`.
+    #[must_use]
+    #[wasm_bindgen(js_name = "entries")]
+    pub fn entries_wasm(&self) -> js_sys::Array {
+        self.iter()
+            .map(|(key, value)| {
+                js_sys::Array::of2(
+                    &wasm_bindgen::JsValue::from_str(key),
+                    &wasm_bindgen::JsValue::from(value),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the scalar entries as a plain object of strings.
+    #[must_use]
+    #[wasm_bindgen(js_name = "to_map")]
+    pub fn to_map_wasm(&self) -> wasm_bindgen::JsValue {
+        self.to_map()
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .unwrap_or_else(|error| {
+                log::error!("Unable to serialize Properties entries for JavaScript: {error}");
+                wasm_bindgen::JsValue::NULL
+            })
+    }
+}
+
+/// Parses a scalar with the [`Properties::get_property`] rules.
+pub(crate) fn parse_scalar<T: std::str::FromStr>(value: &serde_json::Value) -> Option<T> {
+    match value {
+        serde_json::Value::String(value) => value.parse::<T>().ok(),
+        serde_json::Value::Number(_) | serde_json::Value::Bool(_) => {
+            value.to_string().parse::<T>().ok()
+        }
+        serde_json::Value::Null | serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+            None
+        }
+    }
+}
+
+/// Splits `value` at `separator`, trimming items and skipping empty ones.
+fn split<'a>(value: &'a str, separator: &'a str) -> impl Iterator<Item = &'a str> {
+    value
+        .split(separator)
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+/// Records an unparseable value, returning whether it differs from the last
+/// one warned about for `key` of `namespace`.
+///
+/// Only the last value of each key is kept, so the record grows with the keys
+/// read rather than with every value they have held.
+fn first_warning(namespace: &str, key: &str, value: &str) -> bool {
+    static WARNED: LazyLock<Mutex<HashMap<(String, String), String>>> =
+        LazyLock::new(Mutex::default);
+    let mut warned = WARNED.lock().unwrap_or_else(PoisonError::into_inner);
+    let scoped = (namespace.to_string(), key.to_string());
+    if warned.get(&scoped).is_some_and(|last| last == value) {
+        return false;
+    }
+    warned.insert(scoped, value.to_string());
+    true
+}
+
+/// Returns whether `value` is a string, number or boolean.
+fn is_scalar(value: &serde_json::Value) -> bool {
+    matches!(
+        value,
+        serde_json::Value::String(_) | serde_json::Value::Number(_) | serde_json::Value::Bool(_)
+    )
 }
 
 /// Converts a `serde_json::Value` into a `Properties` instance.
@@ -294,13 +868,18 @@ impl Properties {
 /// ```
 impl From<serde_json::Value> for Properties {
     fn from(value: serde_json::Value) -> Self {
-        Self { value }
+        Self {
+            value,
+            namespace: None,
+            prefix: String::new(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Properties;
+    use std::time::Duration;
 
     #[test]
     fn getters_accept_string_number_and_boolean_scalars() {
@@ -317,4 +896,117 @@ mod tests {
         assert_eq!(properties.get_string("boolean"), Some("true".to_string()));
         assert_eq!(properties.get_string("array"), None);
     }
+
+    #[test]
+    fn enumerates_scalar_entries_and_prefix_views() {
+        let properties = Properties::from(serde_json::json!({
+            "db": "primary",
+            "db.host": "localhost",
+            "db.port": 5432,
+            "db.pool.max": "16",
+            "dbname": "orders",
+            "tags": ["a"],
+            "empty": null
+        }));
+
+        assert_eq!(properties.len(), 5);
+        assert!(properties.contains_key("db.port"));
+        assert!(!properties.contains_key("tags"));
+        assert!(!properties.contains_key("empty"));
+        assert_eq!(
+            properties.keys().collect::<Vec<_>>(),
+            ["db", "db.host", "db.pool.max", "db.port", "dbname"]
+        );
+        assert_eq!(properties.to_map()["db.port"], "5432");
+
+        let db = properties.with_prefix("db.");
+        assert_eq!(
+            db.iter().collect::<Vec<_>>(),
+            [
+                ("host", "localhost".to_string()),
+                ("pool.max", "16".to_string()),
+                ("port", "5432".to_string())
+            ]
+        );
+        assert_eq!(db.with_prefix("pool.").get_int("max"), Some(16));
+        assert!(properties.with_prefix("cache.").is_empty());
+    }
+
+    #[test]
+    fn rich_getters_parse_units_lists_maps_and_enums() {
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        enum Mode {
+            ReadOnly,
+            ReadWrite,
+        }
+
+        let properties = Properties::from(serde_json::json!({
+            "timeout": "1m 30s",
+            "buffer": "64KiB",
+            "hosts": "a; b;;c ",
+            "labels": "team=core,tier = web",
+            "broken.labels": "team=core,tier",
+            "enabled": "ON",
+            "mode": "read-only",
+            "port": "http"
+        }));
+
+        assert_eq!(
+            properties.get_duration("timeout"),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(properties.get_size("buffer"), Some(65_536));
+        assert_eq!(
+            properties.get_list("hosts", ";"),
+            Some(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+        );
+        let labels = properties.get_map("labels", ",", "=").unwrap();
+        assert_eq!(labels["tier"], "web");
+        assert_eq!(properties.get_map("broken.labels", ",", "="), None);
+        assert_eq!(properties.get_bool_lenient("enabled"), Some(true));
+        assert_eq!(properties.get_bool("enabled"), None);
+        assert_eq!(properties.get_enum("mode"), Some(Mode::ReadOnly));
+        assert_eq!(properties.get_enum::<Mode>("port"), None);
+
+        assert_eq!(properties.get_int_or("port", 8080), 8080);
+        assert_eq!(properties.get_int_or("missing", 1), 1);
+        assert_eq!(properties.get_property_or("buffer", 0_u32), 0);
+        assert_eq!(
+            properties.get_duration_or("missing", Duration::from_secs(5)),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn invalid_values_are_warned_about_once_per_namespace_key_and_value() {
+        use crate::test_logger::warnings_logged_by;
+
+        let properties = |port: &str| {
+            Properties::from(serde_json::json!({"port": port, "db.port": port}))
+                .in_namespace("warnings.first")
+        };
+        let http = || properties("http").get_int_or("port", 80);
+        assert_eq!(warnings_logged_by(http), 1);
+        assert_eq!(warnings_logged_by(http), 0);
+        assert_eq!(
+            warnings_logged_by(|| {
+                let https = properties("https");
+                (
+                    https.get_property_or("port", 443_u16),
+                    https.get_int_or("port", 443),
+                )
+            }),
+            1
+        );
+        assert_eq!(warnings_logged_by(http), 1);
+
+        let other =
+            Properties::from(serde_json::json!({"port": "http"})).in_namespace("warnings.second");
+        assert_eq!(warnings_logged_by(|| other.get_int_or("port", 80)), 1);
+        assert_eq!(
+            warnings_logged_by(|| properties("http").with_prefix("db.").get_int_or("port", 80)),
+            1
+        );
+    }
 }
@@ -0,0 +1,95 @@
+//! Parsing of durations and byte sizes written with unit suffixes.
+
+use std::time::Duration;
+
+/// Parses a duration such as `30s`, `500ms` or `1h30m`.
+///
+/// Units are `ns`, `us` (or `µs`), `ms`, `s`, `m` (or `min`), `h` and `d`,
+/// ignoring case, and a bare number is in milliseconds. Components add up.
+pub(crate) fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    if let Ok(millis) = text.parse::<u64>() {
+        return Some(Duration::from_millis(millis));
+    }
+    let mut total = Duration::ZERO;
+    for (amount, unit) in components(text)? {
+        let component = match unit.to_ascii_lowercase().as_str() {
+            "ns" => Duration::from_nanos(amount),
+            "us" | "µs" => Duration::from_micros(amount),
+            "ms" => Duration::from_millis(amount),
+            "s" => Duration::from_secs(amount),
+            "m" | "min" => Duration::from_secs(amount.checked_mul(60)?),
+            "h" => Duration::from_secs(amount.checked_mul(60 * 60)?),
+            "d" => Duration::from_secs(amount.checked_mul(24 * 60 * 60)?),
+            _ => return None,
+        };
+        total = total.checked_add(component)?;
+    }
+    Some(total)
+}
+
+/// Parses a byte size such as `512`, `64KB` or `1GiB`.
+///
+/// Units are `B`, `KB`, `MB`, `GB` and `TB`, ignoring case, with or without
+/// the `B` and with or without an `i` (`KiB`, `Mi`). Like Spring and JVM
+/// flags, every multiple is a power of 1024. A bare number is in bytes.
+pub(crate) fn parse_size(text: &str) -> Option<u64> {
+    let [(amount, unit)] = components(text.trim())?[..] else {
+        return None;
+    };
+    let unit = unit.to_ascii_lowercase();
+    let unit = unit.strip_suffix('b').unwrap_or(&unit);
+    let unit = unit.strip_suffix('i').unwrap_or(unit);
+    let shift = match unit {
+        "" => 0,
+        "k" => 10,
+        "m" => 20,
+        "g" => 30,
+        "t" => 40,
+        _ => return None,
+    };
+    amount.checked_mul(1 << shift)
+}
+
+/// Splits text into `<number><unit>` pairs, allowing spaces between them.
+fn components(text: &str) -> Option<Vec<(u64, &str)>> {
+    let mut components = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|character: char| !character.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let amount = rest[..digits].parse().ok()?;
+        rest = rest[digits..].trim_start();
+        let letters = rest
+            .find(|character: char| !character.is_alphabetic())
+            .unwrap_or(rest.len());
+        components.push((amount, &rest[..letters]));
+        rest = rest[letters..].trim_start();
+    }
+    (!components.is_empty()).then_some(components)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations_and_sizes_with_units() {
+        assert_eq!(parse_duration("250"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_duration("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration(" 1h 30M "), Some(Duration::from_mins(90)));
+        assert_eq!(parse_duration("100us"), Some(Duration::from_micros(100)));
+        assert_eq!(parse_duration("2d"), Some(Duration::from_hours(48)));
+        assert_eq!(parse_duration("30 seconds"), None);
+        assert_eq!(parse_duration("s"), None);
+        assert_eq!(parse_duration(""), None);
+
+        assert_eq!(parse_size("512"), Some(512));
+        assert_eq!(parse_size("512MB"), Some(512 << 20));
+        assert_eq!(parse_size("64 kib"), Some(64 << 10));
+        assert_eq!(parse_size("2g"), Some(2 << 30));
+        assert_eq!(parse_size("1KB 1B"), None);
+        assert_eq!(parse_size("1PB"), None);
+    }
+}
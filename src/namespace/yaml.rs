@@ -22,10 +22,13 @@
 //! assert_eq!(value["name"].as_str(), Some("MyApp"));
 //! ```
 
+use super::path::lookup;
+use super::properties::parse_scalar;
 use log::trace;
 use noyalib::{ParserConfig, YamlVersion};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
+use std::sync::{Arc, OnceLock};
 
 /// Comprehensive error types that can occur when working with YAML namespaces.
 ///
@@ -113,20 +116,22 @@ pub enum Error {
 pub struct Yaml {
     /// The underlying YAML string containing the configuration data
     string: String,
+    /// The parsed document for path queries, shared by clones and parsed on
+    /// first use; `None` if the YAML does not parse.
+    tree: Arc<OnceLock<Option<serde_json::Value>>>,
 }
 
 impl From<Yaml> for wasm_bindgen::JsValue {
     fn from(val: Yaml) -> Self {
         // Deserialize through serde_json::Value so serde-wasm-bindgen sees plain
         // maps, sequences, and scalars rather than noyalib's internal Value enum.
-        let value: serde_json::Value =
-            match noyalib::from_str_with_config(&val.string, &yaml_1_1_parser_config()) {
-                Ok(value) => value,
-                Err(error) => {
-                    log::error!("Unable to parse YAML for JavaScript conversion: {error}");
-                    return wasm_bindgen::JsValue::NULL;
-                }
-            };
+        let value = match to_json_value(&val.string) {
+            Ok(value) => value,
+            Err(error) => {
+                log::error!("Unable to parse YAML for JavaScript conversion: {error}");
+                return wasm_bindgen::JsValue::NULL;
+            }
+        };
         match value.serialize(&serde_wasm_bindgen::Serializer::json_compatible()) {
             Ok(value) => value,
             Err(error) => {
@@ -141,6 +146,11 @@ fn yaml_1_1_parser_config() -> ParserConfig {
     ParserConfig::new().version(YamlVersion::V1_1)
 }
 
+/// Parses YAML content into a plain JSON tree of maps, sequences, and scalars.
+pub(crate) fn to_json_value(content: &str) -> Result<serde_json::Value, noyalib::Error> {
+    noyalib::from_str_with_config(content, &yaml_1_1_parser_config())
+}
+
 impl Yaml {
     /// Deserializes the YAML data into a custom type.
     ///
@@ -191,6 +201,85 @@ impl Yaml {
         trace!("string: {:?}", self.string);
         noyalib::from_str_with_config(&self.string, &yaml_1_1_parser_config())
     }
+
+    /// Gets the value at `path` as a string.
+    ///
+    /// `path` is a JSON Pointer such as `/database/hosts/0`, or a dotted path
+    /// such as `database.hosts[0]` or `database.hosts.0`. Numbers and
+    /// booleans are converted to text; mappings, sequences and `null` return
+    /// `None`. The document is parsed once, on the first query.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use apollo_rust_client::namespace::yaml::Yaml;
+    ///
+    /// let response = json!({
+    ///     "content": "database:\n  hosts: [db-1, db-2]\n  port: 5432"
+    /// });
+    /// let yaml_namespace = Yaml::try_from(response).unwrap();
+    ///
+    /// assert_eq!(yaml_namespace.get_string("database.hosts[1]"), Some("db-2".to_string()));
+    /// assert_eq!(yaml_namespace.get_int("/database/port"), Some(5432));
+    /// ```
+    #[must_use]
+    pub fn get_string(&self, path: &str) -> Option<String> {
+        parse_scalar(self.node(path)?)
+    }
+
+    /// Gets the value at `path` as an integer, parsing strings such as
+    /// `"8080"`. See [`Yaml::get_string`] for the path syntax.
+    #[must_use]
+    pub fn get_int(&self, path: &str) -> Option<i64> {
+        parse_scalar(self.node(path)?)
+    }
+
+    /// Gets the value at `path` as a boolean. YAML 1.1 booleans such as `yes`
+    /// and `off` are read as booleans. See [`Yaml::get_string`] for the path
+    /// syntax.
+    #[must_use]
+    pub fn get_bool(&self, path: &str) -> Option<bool> {
+        parse_scalar(self.node(path)?)
+    }
+
+    /// Deserializes the value at `path`, returning `None` if there is no
+    /// value there or it does not deserialize into `T`. See
+    /// [`Yaml::get_string`] for the path syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use apollo_rust_client::namespace::yaml::Yaml;
+    ///
+    /// let response = json!({"content": "database:\n  hosts: [db-1, db-2]"});
+    /// let yaml_namespace = Yaml::try_from(response).unwrap();
+    ///
+    /// let hosts: Option<Vec<String>> = yaml_namespace.get("database.hosts");
+    /// assert_eq!(hosts, Some(vec!["db-1".to_string(), "db-2".to_string()]));
+    /// ```
+    #[must_use]
+    pub fn get<T: DeserializeOwned>(&self, path: &str) -> Option<T> {
+        T::deserialize(self.node(path)?).ok()
+    }
+
+    /// Returns whether `path` addresses a value, `null` included. See
+    /// [`Yaml::get_string`] for the path syntax.
+    #[must_use]
+    pub fn exists(&self, path: &str) -> bool {
+        self.node(path).is_some()
+    }
+
+    /// Returns the node at `path` in the parsed document.
+    fn node(&self, path: &str) -> Option<&serde_json::Value> {
+        let tree = self.tree.get_or_init(|| {
+            to_json_value(&self.string)
+                .inspect_err(|error| log::error!("Unable to parse YAML for path queries: {error}"))
+                .ok()
+        });
+        lookup(tree.as_ref()?, path)
+    }
 }
 
 /// Converts a `serde_json::Value` into a `Yaml` instance.
@@ -235,6 +324,7 @@ impl TryFrom<serde_json::Value> for Yaml {
 
         Ok(Self {
             string: content_string.clone(),
+            tree: Arc::default(),
         })
     }
 }
@@ -290,6 +380,32 @@ mod tests {
         assert!(!value.disabled);
     }
 
+    #[test]
+    fn path_queries_parse_the_document_once() {
+        let yaml = crate::namespace::yaml::Yaml::try_from(serde_json::json!({
+            "content": "server:\n  port: '8080'\n  tls: on\n  hosts:\n    - a\n    - b\n  proxy: ~"
+        }))
+        .unwrap();
+        let clone = yaml.clone();
+
+        assert_eq!(yaml.get_int("server.port"), Some(8080));
+        assert!(clone.tree.get().is_some());
+        assert_eq!(clone.get_bool("/server/tls"), Some(true));
+        assert_eq!(yaml.get_string("server.hosts[1]"), Some("b".to_string()));
+        assert_eq!(
+            yaml.get::<Vec<String>>("server.hosts"),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        assert!(yaml.exists("server.proxy"));
+        assert!(!yaml.exists("server.hosts[2]"));
+
+        let invalid = crate::namespace::yaml::Yaml::try_from(serde_json::json!({
+            "content": "server: [unclosed"
+        }))
+        .unwrap();
+        assert!(!invalid.exists(""));
+    }
+
     #[cfg(target_arch = "wasm32")]
     #[wasm_bindgen_test::wasm_bindgen_test]
     fn wasm_conversion_returns_a_structured_value() {
@@ -0,0 +1,641 @@
+//! Local overrides layered over Apollo values.
+//!
+//! Overrides force a value without publishing it to Apollo, during incidents
+//! or in local development. The client applies them to every value it serves,
+//! so [`Properties`](crate::namespace::properties::Properties) getters, JSON
+//! and YAML documents, bound values and change listeners all see the
+//! overridden value. Keys are property keys in Properties namespaces and
+//! [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901) paths in JSON and
+//! YAML namespaces, as in change events.
+//!
+//! Three sources are consulted, each overriding the ones before it:
+//!
+//! 1. **Environment**: with
+//!    [`override_env_prefix`](crate::client_config::ClientConfig::override_env_prefix),
+//!    a variable named `{prefix}{NAMESPACE}__{KEY}` overrides an existing key.
+//!    The namespace name and key are upper-cased with every other character
+//!    replaced by `_`, so `APOLLO_OVERRIDE_APPLICATION__DB_POOL_SIZE`
+//!    overrides `db.pool.size` in `application`. Variables are read once,
+//!    when the client is created.
+//! 2. **File** (native targets only): the YAML or JSON file named by
+//!    `override_file` maps namespace names to maps of keys and values. With
+//!    the `override-watch` feature the file is watched, and a file that does
+//!    not parse is ignored with a warning, keeping the previous overrides.
+//!    Without it, the file is read once, when the client is created.
+//! 3. **API**: [`crate::Client::set_override`] and its siblings.
+//!
+//! In Properties namespaces, numbers and booleans are stored as strings and
+//! `null` removes the property. In JSON and YAML namespaces the value replaces
+//! the addressed node, creating missing object members on the way; values from
+//! environment variables are parsed as JSON when possible. Listeners are
+//! notified whenever an override changes a value, and
+//! [`crate::Client::overrides`] reports the overrides in effect.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use apollo_rust_client::{Client, client_config::ClientConfig};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = ClientConfig::builder("my-app", "http://apollo-server:8080")
+//!     .override_env_prefix("APOLLO_OVERRIDE_")
+//!     .build()?;
+//! let client = Client::new(config)?;
+//!
+//! client.set_override("application", "feature.checkout", "false").await?;
+//! client.set_override("database.json", "/pool/size", 4).await?;
+//! for active in client.overrides("application").await? {
+//!     println!("{} = {} ({:?})", active.key, active.value, active.source);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{cache::Cache, change};
+#[cfg(not(target_arch = "wasm32"))]
+use log::warn;
+use log::{debug, info};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, PoisonError},
+};
+use tokio::sync::RwLock;
+
+/// Where an override comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OverrideSource {
+    /// An environment variable with the configured prefix.
+    Environment,
+    /// The watched override file.
+    File,
+    /// [`crate::Client::set_override`].
+    Api,
+}
+
+/// An override in effect for one key, as reported by
+/// [`crate::Client::overrides`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveOverride {
+    /// The property key or JSON Pointer path.
+    pub key: String,
+    /// The value served instead of Apollo's.
+    pub value: Value,
+    /// Apollo's value, or `None` if Apollo does not define the key.
+    pub remote_value: Option<Value>,
+    /// Where the override comes from.
+    pub source: OverrideSource,
+}
+
+/// Overrides of one namespace, keyed by property key or JSON Pointer path.
+type Layer = BTreeMap<String, Value>;
+
+/// The mutable override sources, keyed by namespace name.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct OverrideLayers {
+    file: BTreeMap<String, Layer>,
+    api: BTreeMap<String, Layer>,
+}
+
+impl OverrideLayers {
+    pub(crate) fn set(&mut self, namespace: &str, key: &str, value: Value) {
+        self.api
+            .entry(namespace.to_string())
+            .or_default()
+            .insert(key.to_string(), value);
+    }
+
+    pub(crate) fn remove(&mut self, namespace: &str, key: &str) -> bool {
+        let Some(layer) = self.api.get_mut(namespace) else {
+            return false;
+        };
+        let removed = layer.remove(key).is_some();
+        if layer.is_empty() {
+            self.api.remove(namespace);
+        }
+        removed
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.api.clear();
+    }
+}
+
+/// Overrides shared by a client, its clones and its coordinate handles.
+///
+/// Overrides address namespaces by name, so they apply under every app id
+/// and cluster.
+#[derive(Debug, Default)]
+pub(crate) struct Overrides {
+    /// Variables with the configured prefix, keyed by the rest of the name.
+    environment: BTreeMap<String, String>,
+    layers: std::sync::RwLock<OverrideLayers>,
+}
+
+impl Overrides {
+    /// Reads the environment variables and override file named by `config`.
+    pub(crate) fn new(config: &crate::client_config::ClientConfig) -> Self {
+        let environment = config
+            .override_env_prefix
+            .as_deref()
+            .map(read_environment)
+            .unwrap_or_default();
+        let layers = OverrideLayers::default();
+        #[cfg(not(target_arch = "wasm32"))]
+        let layers = {
+            let mut layers = layers;
+            if let Some(path) = &config.override_file {
+                match read_file(std::path::Path::new(path)) {
+                    Ok(file) => layers.file = file,
+                    Err(error) => warn!("Ignoring invalid override file {path}: {error}"),
+                }
+            }
+            layers
+        };
+        Self {
+            environment,
+            layers: std::sync::RwLock::new(layers),
+        }
+    }
+
+    /// Returns `config`, the raw cached value of `namespace`, with the
+    /// current overrides applied.
+    pub(crate) fn apply(&self, namespace: &str, config: Value) -> Value {
+        let layers = self.layers.read().unwrap_or_else(PoisonError::into_inner);
+        let resolved = self.resolve(namespace, &config, &layers);
+        if resolved.is_empty() {
+            return config;
+        }
+        apply_resolved(namespace, config, &resolved)
+    }
+
+    /// Lists the overrides that apply to `config`, the raw cached value of
+    /// `namespace`.
+    pub(crate) fn active(&self, namespace: &str, config: &Value) -> Vec<ActiveOverride> {
+        let layers = self.layers.read().unwrap_or_else(PoisonError::into_inner);
+        let resolved = self.resolve(namespace, config, &layers);
+        let document = crate::namespace::document_tree(namespace, config);
+        resolved
+            .into_iter()
+            .map(|(key, (value, source))| {
+                let remote_value = match &document {
+                    Some(document) => document.pointer(&key).cloned(),
+                    None => config.get(&key).cloned(),
+                };
+                ActiveOverride {
+                    key,
+                    value,
+                    remote_value,
+                    source,
+                }
+            })
+            .collect()
+    }
+
+    /// Changes the override layers and notifies the listeners of every
+    /// cached namespace whose value changed as a result.
+    pub(crate) async fn update<R>(
+        &self,
+        namespaces: &RwLock<HashMap<String, Arc<Cache>>>,
+        change: impl FnOnce(&mut OverrideLayers) -> R,
+    ) -> R {
+        let result = change(&mut self.layers.write().unwrap_or_else(PoisonError::into_inner));
+        let caches: Vec<_> = namespaces.read().await.values().cloned().collect();
+        info!(
+            "Local overrides changed; re-applying them to {} namespaces",
+            caches.len()
+        );
+        for cache in caches {
+            cache.republish().await;
+        }
+        result
+    }
+
+    /// Replaces the file overrides with the current contents of `path`.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "override-watch"))]
+    async fn reload_file(
+        &self,
+        path: &std::path::Path,
+        namespaces: &RwLock<HashMap<String, Arc<Cache>>>,
+    ) {
+        match read_file(path) {
+            Ok(file) => {
+                debug!("Reloading local overrides from {}", path.display());
+                self.update(namespaces, |layers| layers.file = file).await;
+            }
+            Err(error) => warn!(
+                "Ignoring invalid override file {}; keeping the previous overrides: {error}",
+                path.display()
+            ),
+        }
+    }
+
+    /// Collects the overrides of `namespace`, later sources replacing
+    /// earlier ones.
+    fn resolve(
+        &self,
+        namespace: &str,
+        config: &Value,
+        layers: &OverrideLayers,
+    ) -> BTreeMap<String, (Value, OverrideSource)> {
+        let mut resolved = BTreeMap::new();
+        let document = crate::namespace::is_document(namespace);
+        if !document && !crate::namespace::is_properties(namespace) {
+            return resolved;
+        }
+        if !self.environment.is_empty() {
+            for key in change::flatten(namespace, config).into_keys() {
+                if let Some(value) = self.environment.get(&environment_name(namespace, &key)) {
+                    let value = if document {
+                        serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.clone()))
+                    } else {
+                        Value::String(value.clone())
+                    };
+                    resolved.insert(key, (value, OverrideSource::Environment));
+                }
+            }
+        }
+        for (source, layer) in [
+            (OverrideSource::File, layers.file.get(namespace)),
+            (OverrideSource::Api, layers.api.get(namespace)),
+        ] {
+            for (key, value) in layer.into_iter().flatten() {
+                resolved.insert(key.clone(), (value.clone(), source));
+            }
+        }
+        resolved
+    }
+}
+
+/// Checks that `key` can address a value of `namespace`.
+pub(crate) fn validate(namespace: &str, key: &str) -> Result<(), String> {
+    if crate::namespace::is_document(namespace) {
+        if key.is_empty() || key.starts_with('/') {
+            Ok(())
+        } else {
+            Err("expected a JSON Pointer path such as /database/port".to_string())
+        }
+    } else if crate::namespace::is_properties(namespace) {
+        if key.is_empty() {
+            Err("property keys must not be empty".to_string())
+        } else {
+            Ok(())
+        }
+    } else {
+        Err("only Properties, JSON and YAML namespaces can be overridden".to_string())
+    }
+}
+
+fn apply_resolved(
+    namespace: &str,
+    mut config: Value,
+    resolved: &BTreeMap<String, (Value, OverrideSource)>,
+) -> Value {
+    if crate::namespace::is_document(namespace) {
+        let Some(mut document) = crate::namespace::document_tree(namespace, &config) else {
+            debug!("Not overriding namespace {namespace}: its content does not parse");
+            return config;
+        };
+        for (pointer, (value, _)) in resolved {
+            if !set_pointer(&mut document, pointer, value.clone()) {
+                debug!("Override {pointer} does not address a node of namespace {namespace}");
+            }
+        }
+        // JSON is also valid YAML, so both formats store the document as JSON.
+        if let Value::Object(map) = &mut config {
+            map.insert("content".to_string(), Value::String(document.to_string()));
+        }
+    } else if let Value::Object(map) = &mut config {
+        for (key, (value, _)) in resolved {
+            match value {
+                Value::Null => {
+                    map.remove(key);
+                }
+                Value::String(_) => {
+                    map.insert(key.clone(), value.clone());
+                }
+                value => {
+                    map.insert(key.clone(), Value::String(value.to_string()));
+                }
+            }
+        }
+    }
+    config
+}
+
+/// Replaces the node at `pointer`, creating missing object members.
+///
+/// Returns `false` if the path runs through a scalar or past the end of an
+/// array.
+fn set_pointer(document: &mut Value, pointer: &str, value: Value) -> bool {
+    let Some(path) = pointer.strip_prefix('/') else {
+        *document = value;
+        return true;
+    };
+    let tokens: Vec<String> = path
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect();
+    let Some((last, parents)) = tokens.split_last() else {
+        return false;
+    };
+    let mut target = document;
+    for token in parents {
+        target = match target {
+            Value::Object(map) => map
+                .entry(token.clone())
+                .or_insert_with(|| Value::Object(serde_json::Map::new())),
+            Value::Array(items) => match token
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| items.get_mut(index))
+            {
+                Some(item) => item,
+                None => return false,
+            },
+            _ => return false,
+        };
+    }
+    match target {
+        Value::Object(map) => {
+            map.insert(last.clone(), value);
+            true
+        }
+        Value::Array(items) => match last
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| items.get_mut(index))
+        {
+            Some(item) => {
+                *item = value;
+                true
+            }
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns the environment variable name, without prefix, that overrides `key`.
+fn environment_name(namespace: &str, key: &str) -> String {
+    let key = key.strip_prefix('/').unwrap_or(key);
+    format!("{}__{}", normalize(namespace), normalize(key))
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() {
+                character.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Reads the variables starting with `prefix`, keyed by the rest of the name.
+fn read_environment(prefix: &str) -> BTreeMap<String, String> {
+    cfg_if::cfg_if! {
+        if #[cfg(not(target_arch = "wasm32"))] {
+            std::env::vars_os()
+                .filter_map(|(name, value)| {
+                    let name = name.into_string().ok()?;
+                    let suffix = name.strip_prefix(prefix)?.to_string();
+                    Some((suffix, value.into_string().ok()?))
+                })
+                .collect()
+        } else {
+            node_environment_names()
+                .into_iter()
+                .filter_map(|name| {
+                    let suffix = name.strip_prefix(prefix)?.to_string();
+                    let value = crate::client_config::node_env(&name).ok().flatten()?;
+                    Some((suffix, value))
+                })
+                .collect()
+        }
+    }
+}
+
+/// Lists the names in `process.env`, or nothing outside Node.js.
+#[cfg(target_arch = "wasm32")]
+fn node_environment_names() -> Vec<String> {
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let Ok(process) = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("process")) else {
+        return Vec::new();
+    };
+    if process.is_null() || process.is_undefined() {
+        return Vec::new();
+    }
+    let Ok(env) = js_sys::Reflect::get(&process, &JsValue::from_str("env")) else {
+        return Vec::new();
+    };
+    let Some(env) = env.dyn_ref::<js_sys::Object>() else {
+        return Vec::new();
+    };
+    js_sys::Object::keys(env)
+        .iter()
+        .filter_map(|name| name.as_string())
+        .collect()
+}
+
+/// Parses an override file. A missing or empty file holds no overrides.
+#[cfg(not(target_arch = "wasm32"))]
+fn read_file(path: &std::path::Path) -> Result<BTreeMap<String, Layer>, String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(error) => return Err(error.to_string()),
+    };
+    if content.trim().is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    let Value::Object(namespaces) =
+        crate::namespace::yaml::to_json_value(&content).map_err(|error| error.to_string())?
+    else {
+        return Err("expected a map of namespace names".to_string());
+    };
+    let mut layers = BTreeMap::new();
+    for (namespace, keys) in namespaces {
+        let Value::Object(keys) = keys else {
+            return Err(format!("expected a map of keys for namespace {namespace}"));
+        };
+        let mut layer = Layer::new();
+        for (key, value) in keys {
+            validate(&namespace, &key)
+                .map_err(|reason| format!("key {key:?} of namespace {namespace}: {reason}"))?;
+            layer.insert(key, value);
+        }
+        layers.insert(namespace, layer);
+    }
+    Ok(layers)
+}
+
+/// How long the watcher lets an edit of the override file settle.
+#[cfg(all(not(target_arch = "wasm32"), feature = "override-watch"))]
+const RELOAD_DELAY: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Watches the override file and applies its changes (native targets only).
+///
+/// Dropping the watcher stops both the operating-system watch and the reload
+/// task.
+#[cfg(all(not(target_arch = "wasm32"), feature = "override-watch"))]
+pub(crate) struct OverrideWatcher {
+    _watcher: notify::RecommendedWatcher,
+    task: tokio::task::JoinHandle<()>,
+}
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "override-watch"))]
+impl OverrideWatcher {
+    /// Starts watching the directory containing `path`, so the file may be
+    /// created, replaced or deleted while watched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be watched, or if no Tokio
+    /// runtime is running to apply the changes.
+    pub(crate) fn spawn(
+        path: &std::path::Path,
+        overrides: Arc<Overrides>,
+        namespaces: Arc<RwLock<HashMap<String, Arc<Cache>>>>,
+    ) -> notify::Result<Self> {
+        use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+        let runtime = tokio::runtime::Handle::try_current().map_err(|_| {
+            notify::Error::generic("watching the override file requires a Tokio runtime")
+        })?;
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => std::path::PathBuf::from("."),
+        };
+        let file_name = path.file_name().map(ToOwned::to_owned);
+        let path = path.to_path_buf();
+        // Events arrive on the watcher's own thread, so they are forwarded to
+        // a task on the runtime that applies them.
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<()>();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<Event>| match event {
+                Ok(event)
+                    if !matches!(event.kind, EventKind::Access(_))
+                        && event
+                            .paths
+                            .iter()
+                            .any(|changed| changed.file_name() == file_name.as_deref()) =>
+                {
+                    let _ = sender.send(());
+                }
+                Ok(_) => {}
+                Err(error) => warn!("Override file watcher error: {error}"),
+            })?;
+        watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+
+        // Waiting briefly before reloading lets a truncate-and-write edit
+        // finish, so listeners do not see the overrides vanish in between;
+        // events arriving meanwhile are covered by the same reload.
+        let task = runtime.spawn(async move {
+            while receiver.recv().await.is_some() {
+                tokio::time::sleep(RELOAD_DELAY).await;
+                while receiver.try_recv().is_ok() {}
+                overrides.reload_file(&path, &namespaces).await;
+            }
+        });
+        Ok(Self {
+            _watcher: watcher,
+            task,
+        })
+    }
+}
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "override-watch"))]
+impl Drop for OverrideWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn overrides(environment: &[(&str, &str)]) -> Overrides {
+        Overrides {
+            environment: environment
+                .iter()
+                .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+                .collect(),
+            ..Overrides::default()
+        }
+    }
+
+    #[test]
+    fn later_sources_win_and_documents_are_patched_by_pointer() {
+        let overrides = overrides(&[
+            ("APPLICATION__DB_POOL_SIZE", "8"),
+            ("APPLICATION__DB_HOST", "env-host"),
+            ("APPLICATION__MISSING", "ignored"),
+            ("CONFIG_JSON__DB_PORT", "6543"),
+        ]);
+        {
+            let mut layers = overrides.layers.write().unwrap();
+            layers.file.insert(
+                "application".to_string(),
+                Layer::from([("db.host".to_string(), json!("file-host"))]),
+            );
+            layers.set("application", "retries", json!(3));
+            layers.set("application", "db.user", Value::Null);
+            layers.set("config.json", "/cache/enabled", json!(true));
+            layers.set("config.json", "/hosts/5", json!("unreachable"));
+        }
+
+        let properties = overrides.apply(
+            "application",
+            json!({"db.pool.size": "4", "db.host": "remote", "db.user": "app"}),
+        );
+        assert_eq!(
+            properties,
+            json!({"db.pool.size": "8", "db.host": "file-host", "retries": "3"})
+        );
+
+        let document = overrides.apply(
+            "config.json",
+            json!({"content": r#"{"db":{"port":5432},"hosts":["a"]}"#}),
+        );
+        assert_eq!(
+            crate::namespace::document_tree("config.json", &document),
+            Some(json!({"db": {"port": 6543}, "hosts": ["a"], "cache": {"enabled": true}}))
+        );
+
+        let active = overrides.active("application", &json!({"db.host": "remote"}));
+        assert_eq!(
+            active
+                .iter()
+                .map(|active| (
+                    active.key.as_str(),
+                    active.source,
+                    active.remote_value.clone()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("db.host", OverrideSource::File, Some(json!("remote"))),
+                ("db.user", OverrideSource::Api, None),
+                ("retries", OverrideSource::Api, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn keys_must_suit_the_namespace_format() {
+        assert!(validate("application", "db.host").is_ok());
+        assert!(validate("application", "").is_err());
+        assert!(validate("config.yaml", "/db/host").is_ok());
+        assert!(validate("config.yaml", "db.host").is_err());
+        assert!(validate("notes.txt", "content").is_err());
+    }
+}
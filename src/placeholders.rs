@@ -0,0 +1,410 @@
+//! Placeholder resolution in Properties values.
+//!
+//! With [`resolve_placeholders`](crate::client_config::ClientConfig::resolve_placeholders)
+//! enabled, the client expands `${key}` and `${key:default}` in the values of
+//! Properties namespaces before serving them, as the Java client does through
+//! Spring. A placeholder resolves, in order, to:
+//!
+//! 1. `key` in the namespace containing the placeholder;
+//! 2. `key` in the other loaded Properties namespaces of the same app id and
+//!    cluster, in the order they were loaded;
+//! 3. the environment variable named `key`;
+//! 4. the default, which may itself contain placeholders.
+//!
+//! Referenced values are expanded recursively, and a placeholder that
+//! resolves nowhere and has no default is left verbatim. A reference cycle
+//! makes the namespace fail to load with
+//! [`crate::cache::Error::PlaceholderCycle`] until it is fixed. When a
+//! referenced key changes, dependent namespaces are re-resolved and their
+//! listeners receive change events for the affected keys.
+
+use crate::{cache::Cache, client_config::ClientConfig};
+use serde_json::{Map, Value};
+use std::{
+    collections::HashMap,
+    sync::{Arc, PoisonError, Weak},
+};
+use tokio::sync::RwLock;
+
+/// The unresolved values of one loaded Properties namespace.
+#[derive(Debug)]
+struct Source {
+    app_id: String,
+    cluster: String,
+    namespace: String,
+    values: Arc<Map<String, Value>>,
+}
+
+/// Resolves placeholders against every Properties namespace a client has
+/// loaded.
+///
+/// Shared by a client, its clones and its coordinate handles.
+#[derive(Debug, Default)]
+pub(crate) struct Placeholders {
+    enabled: bool,
+    /// Loaded namespaces, in load order.
+    sources: std::sync::RwLock<Vec<Source>>,
+    /// The client's caches, re-resolved when a referenced namespace changes.
+    namespaces: Weak<RwLock<HashMap<String, Arc<Cache>>>>,
+}
+
+impl Placeholders {
+    pub(crate) fn new(
+        config: &ClientConfig,
+        namespaces: Weak<RwLock<HashMap<String, Arc<Cache>>>>,
+    ) -> Self {
+        Self {
+            enabled: config.resolve_placeholders.unwrap_or(false),
+            sources: std::sync::RwLock::new(Vec::new()),
+            namespaces,
+        }
+    }
+
+    /// Returns whether values of `namespace` are resolved.
+    fn applies_to(&self, namespace: &str) -> bool {
+        self.enabled && crate::namespace::is_properties(namespace)
+    }
+
+    /// Records the unresolved values of a namespace for references from
+    /// other namespaces.
+    ///
+    /// Returns `true` if the values changed, so dependents need re-resolving.
+    pub(crate) fn record(&self, config: &ClientConfig, namespace: &str, values: &Value) -> bool {
+        if !self.applies_to(namespace) {
+            return false;
+        }
+        let Value::Object(values) = values else {
+            return false;
+        };
+        let mut sources = self.sources.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(source) = sources
+            .iter_mut()
+            .find(|source| source.is(config, namespace))
+        {
+            if *source.values == *values {
+                return false;
+            }
+            source.values = Arc::new(values.clone());
+        } else {
+            sources.push(Source {
+                app_id: config.app_id.clone(),
+                cluster: config.cluster.clone(),
+                namespace: namespace.to_string(),
+                values: Arc::new(values.clone()),
+            });
+        }
+        true
+    }
+
+    /// Stops resolving references into a forgotten namespace.
+    pub(crate) fn forget(&self, config: &ClientConfig, namespace: &str) {
+        self.sources
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|source| !source.is(config, namespace));
+    }
+
+    /// Expands the placeholders in the values of `namespace`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reference chain of a placeholder cycle.
+    pub(crate) fn resolve(
+        &self,
+        config: &ClientConfig,
+        namespace: &str,
+        mut values: Value,
+    ) -> Result<Value, String> {
+        if !self.applies_to(namespace) {
+            return Ok(values);
+        }
+        let Value::Object(map) = &mut values else {
+            return Ok(values);
+        };
+        if !map
+            .values()
+            .any(|value| value.as_str().is_some_and(|value| value.contains("${")))
+        {
+            return Ok(values);
+        }
+
+        let mut layers = vec![(namespace.to_string(), Arc::new(map.clone()))];
+        layers.extend(
+            self.sources
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .iter()
+                .filter(|source| {
+                    source.app_id == config.app_id
+                        && source.cluster == config.cluster
+                        && source.namespace != namespace
+                })
+                .map(|source| (source.namespace.clone(), source.values.clone())),
+        );
+        let mut resolver = Resolver {
+            layers: &layers,
+            resolved: HashMap::new(),
+        };
+        for (key, value) in map.iter_mut() {
+            if let Value::String(text) = value
+                && text.contains("${")
+            {
+                let mut chain = vec![(namespace.to_string(), key.clone())];
+                *text = resolver.expand(namespace, text, &mut chain)?;
+            }
+        }
+        Ok(values)
+    }
+
+    /// Re-resolves the cached namespaces after values they may reference
+    /// changed.
+    pub(crate) async fn propagate(&self) {
+        let Some(namespaces) = self.namespaces.upgrade() else {
+            return;
+        };
+        let caches: Vec<_> = namespaces.read().await.values().cloned().collect();
+        for cache in caches {
+            cache.resolve_again().await;
+        }
+    }
+}
+
+impl Source {
+    fn is(&self, config: &ClientConfig, namespace: &str) -> bool {
+        self.app_id == config.app_id
+            && self.cluster == config.cluster
+            && self.namespace == namespace
+    }
+}
+
+/// Expands placeholders against namespaces in lookup order.
+struct Resolver<'a> {
+    layers: &'a [(String, Arc<Map<String, Value>>)],
+    /// The expanded values of the keys already resolved, by namespace and key,
+    /// so a key referenced many times is expanded once.
+    resolved: HashMap<(&'a str, String), String>,
+}
+
+impl<'a> Resolver<'a> {
+    /// Expands every placeholder in `text`, a value of `namespace`.
+    ///
+    /// `chain` holds the keys being expanded, to detect cycles.
+    fn expand(
+        &mut self,
+        namespace: &str,
+        text: &str,
+        chain: &mut Vec<(String, String)>,
+    ) -> Result<String, String> {
+        let mut output = String::new();
+        let mut rest = text;
+        while let Some(start) = rest.find("${") {
+            output.push_str(&rest[..start]);
+            let body_start = &rest[start + 2..];
+            let Some(end) = closing_brace(body_start) else {
+                break;
+            };
+            output.push_str(&self.replace(namespace, &body_start[..end], chain)?);
+            rest = &body_start[end + 1..];
+        }
+        output.push_str(rest);
+        Ok(output)
+    }
+
+    /// Resolves the body of one `${...}` placeholder.
+    fn replace(
+        &mut self,
+        namespace: &str,
+        body: &str,
+        chain: &mut Vec<(String, String)>,
+    ) -> Result<String, String> {
+        let (key, default) = match default_separator(body) {
+            Some(separator) => (&body[..separator], Some(&body[separator + 1..])),
+            None => (body, None),
+        };
+        let key = self.expand(namespace, key, chain)?;
+
+        if let Some((source, value)) = self.lookup(namespace, &key) {
+            if let Some(resolved) = self.resolved.get(&(source, key.clone())) {
+                return Ok(resolved.clone());
+            }
+            let link = (source.to_string(), key.clone());
+            if chain.contains(&link) {
+                chain.push(link);
+                return Err(chain
+                    .iter()
+                    .map(|(namespace, key)| format!("{namespace}/{key}"))
+                    .collect::<Vec<_>>()
+                    .join(" -> "));
+            }
+            chain.push(link);
+            let expanded = self.expand(source, &value, chain)?;
+            chain.pop();
+            self.resolved.insert((source, key), expanded.clone());
+            return Ok(expanded);
+        }
+        if let Some(value) = environment(&key) {
+            return Ok(value);
+        }
+        match default {
+            Some(default) => self.expand(namespace, default, chain),
+            None => Ok(format!("${{{body}}}")),
+        }
+    }
+
+    /// Finds `key` in `namespace`, then in the other namespaces in order.
+    fn lookup(&self, namespace: &str, key: &str) -> Option<(&'a str, String)> {
+        let own = self.layers.iter().filter(|(name, _)| name == namespace);
+        let others = self.layers.iter().filter(|(name, _)| name != namespace);
+        own.chain(others).find_map(|(name, values)| {
+            let value = match values.get(key)? {
+                Value::String(value) => value.clone(),
+                Value::Null => return None,
+                value => value.to_string(),
+            };
+            Some((name.as_str(), value))
+        })
+    }
+}
+
+/// Returns the index of the `}` closing a placeholder whose body starts
+/// `text`, skipping nested placeholders.
+fn closing_brace(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut depth = 0usize;
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'$' if bytes.get(index + 1) == Some(&b'{') => {
+                depth += 1;
+                index += 1;
+            }
+            b'}' if depth == 0 => return Some(index),
+            b'}' => depth -= 1,
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Returns the index of the `:` separating the key from the default,
+/// outside nested placeholders.
+fn default_separator(body: &str) -> Option<usize> {
+    let bytes = body.as_bytes();
+    let mut depth = 0usize;
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'$' if bytes.get(index + 1) == Some(&b'{') => {
+                depth += 1;
+                index += 1;
+            }
+            b'}' => depth = depth.saturating_sub(1),
+            b':' if depth == 0 => return Some(index),
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+fn environment(name: &str) -> Option<String> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_arch = "wasm32")] {
+            crate::client_config::node_env(name).ok().flatten()
+        } else {
+            std::env::var(name).ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn placeholders(loaded: &[(&str, Value)]) -> (ClientConfig, Placeholders) {
+        let config = ClientConfig::builder("app", "http://127.0.0.1:9")
+            .resolve_placeholders(true)
+            .build()
+            .unwrap();
+        let placeholders = Placeholders::new(&config, Weak::new());
+        for (namespace, values) in loaded {
+            assert!(placeholders.record(&config, namespace, values));
+        }
+        (config, placeholders)
+    }
+
+    #[test]
+    fn placeholders_resolve_locally_then_across_namespaces_then_to_defaults() {
+        let (config, placeholders) = placeholders(&[
+            (
+                "common",
+                json!({"db.host": "common-host", "db.port": "3306", "region": "${zone:eu}"}),
+            ),
+            ("other", json!({"db.port": "1"})),
+        ]);
+        let resolved = placeholders
+            .resolve(
+                &config,
+                "application",
+                json!({
+                    "db.port": "5432",
+                    "db.url": "jdbc://${db.host}:${db.port}/${db.name:${app.name:orders}}",
+                    "area": "${region}",
+                    "package": "${CARGO_PKG_NAME:unknown}",
+                    "unresolved": "${missing.key}",
+                    "broken": "${unterminated",
+                }),
+            )
+            .unwrap();
+        assert_eq!(
+            resolved,
+            json!({
+                "db.port": "5432",
+                "db.url": "jdbc://common-host:5432/orders",
+                "area": "eu",
+                "package": env!("CARGO_PKG_NAME"),
+                "unresolved": "${missing.key}",
+                "broken": "${unterminated",
+            })
+        );
+    }
+
+    #[test]
+    fn keys_referenced_many_times_are_expanded_once() {
+        // Expanding every reference afresh would take 2^40 steps.
+        let mut values: Map<String, Value> = (0..40)
+            .map(|level| {
+                let next = level + 1;
+                (
+                    format!("k{level}"),
+                    json!(format!("${{k{next}}}${{k{next}}}")),
+                )
+            })
+            .collect();
+        values.insert("k40".to_string(), json!(""));
+        values.insert("url".to_string(), json!("a${k0}b"));
+        let (config, placeholders) = placeholders(&[]);
+        let resolved = placeholders
+            .resolve(&config, "application", Value::Object(values))
+            .unwrap();
+        assert_eq!(resolved["url"], json!("ab"));
+        assert_eq!(resolved["k0"], json!(""));
+    }
+
+    #[test]
+    fn cycles_are_reported_with_their_reference_chain() {
+        let (config, placeholders) = placeholders(&[("common", json!({"b": "${a}"}))]);
+        let error = placeholders
+            .resolve(&config, "application", json!({"a": "x${b}"}))
+            .unwrap_err();
+        assert_eq!(error, "application/a -> common/b -> application/a");
+
+        let disabled = Placeholders::default();
+        assert_eq!(
+            disabled.resolve(&config, "application", json!({"a": "${a}"})),
+            Ok(json!({"a": "${a}"}))
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Startup readiness checks.
+//!
+//! [`crate::Client::wait_until_ready`] loads a set of namespaces concurrently
+//! and reports, per namespace, whether configuration is available. A
+//! [`ReadinessPolicy`] decides which namespaces must be ready and whether
+//! cached data that Apollo has not confirmed counts, so a service can gate its
+//! startup probe on configuration being usable.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use apollo_rust_client::{Client, ReadinessPolicy, client_config::ClientConfig};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = ClientConfig::builder("my-app", "http://apollo-server:8080").build()?;
+//! let client = Client::new(config)?;
+//!
+//! let policy = ReadinessPolicy::default().optional("feature.flags");
+//! let report = client
+//!     .wait_until_ready(&["application", "feature.flags"], Duration::from_secs(10), &policy)
+//!     .await;
+//! if !report.is_ready() {
+//!     eprintln!("configuration unavailable: {report:?}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::cache::Cache;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Decides which namespaces [`crate::Client::wait_until_ready`] waits for and
+/// what counts as ready.
+///
+/// By default every namespace is required and stale cached data is accepted.
+#[derive(Clone, Debug)]
+pub struct ReadinessPolicy {
+    optional: BTreeSet<String>,
+    accept_stale: bool,
+}
+
+impl Default for ReadinessPolicy {
+    fn default() -> Self {
+        Self {
+            optional: BTreeSet::new(),
+            accept_stale: true,
+        }
+    }
+}
+
+impl ReadinessPolicy {
+    /// Marks a namespace as optional: it is reported, but neither its failure
+    /// nor its absence at the deadline makes the client unready.
+    #[must_use]
+    pub fn optional(mut self, namespace: impl Into<String>) -> Self {
+        self.optional.insert(namespace.into());
+        self
+    }
+
+    /// Sets whether stale cached data counts as ready.
+    ///
+    /// Stale data is a persisted or expired entry that Apollo has not
+    /// confirmed in this process. When it does not count, the wait refreshes
+    /// such namespaces from Apollo and reports them ready only if the refresh
+    /// succeeds.
+    #[must_use]
+    pub fn accept_stale(mut self, accept: bool) -> Self {
+        self.accept_stale = accept;
+        self
+    }
+
+    pub(crate) fn is_required(&self, namespace: &str) -> bool {
+        !self.optional.contains(namespace)
+    }
+
+    pub(crate) fn accepts_stale(&self) -> bool {
+        self.accept_stale
+    }
+}
+
+/// Availability of one namespace when the wait ended.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(
+    tag = "status",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum NamespaceStatus {
+    /// Fetched from Apollo by this process, or loaded from a persisted entry
+    /// that is within the cache TTL.
+    Fresh,
+    /// Served from a persisted or expired entry that Apollo has not
+    /// confirmed. `refresh_error` explains why when a refresh was attempted.
+    Stale {
+        /// The error from the refresh attempted by the wait, if any.
+        refresh_error: Option<String>,
+    },
+    /// No value is available: Apollo could not be reached and nothing was
+    /// cached.
+    Failed {
+        /// The load error.
+        error: String,
+    },
+    /// Still loading when the wait ended, at the deadline or because a
+    /// required namespace failed.
+    Pending,
+}
+
+/// Readiness of one namespace.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamespaceReadiness {
+    /// The namespace name.
+    pub namespace: String,
+    /// Whether the policy requires this namespace.
+    pub required: bool,
+    /// Whether the status satisfies the policy.
+    pub ready: bool,
+    /// What was available when the wait ended.
+    pub status: NamespaceStatus,
+}
+
+/// Per-namespace outcome of [`crate::Client::wait_until_ready`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadinessReport {
+    ready: bool,
+    namespaces: Vec<NamespaceReadiness>,
+}
+
+impl ReadinessReport {
+    pub(crate) fn new(namespaces: Vec<NamespaceReadiness>) -> Self {
+        let ready = namespaces
+            .iter()
+            .all(|namespace| namespace.ready || !namespace.required);
+        Self { ready, namespaces }
+    }
+
+    /// Returns whether every required namespace is ready.
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+
+    /// Returns each namespace's readiness, in the order requested.
+    #[must_use]
+    pub fn namespaces(&self) -> &[NamespaceReadiness] {
+        &self.namespaces
+    }
+
+    /// Returns the readiness of one namespace.
+    #[must_use]
+    pub fn namespace(&self, namespace: &str) -> Option<&NamespaceReadiness> {
+        self.namespaces
+            .iter()
+            .find(|readiness| readiness.namespace == namespace)
+    }
+}
+
+impl From<ReadinessReport> for wasm_bindgen::JsValue {
+    fn from(report: ReadinessReport) -> Self {
+        match report.serialize(&serde_wasm_bindgen::Serializer::json_compatible()) {
+            Ok(value) => value,
+            Err(error) => {
+                log::error!("Unable to serialize readiness report for JavaScript: {error}");
+                wasm_bindgen::JsValue::NULL
+            }
+        }
+    }
+}
+
+impl NamespaceStatus {
+    pub(crate) fn is_ready(&self, policy: &ReadinessPolicy) -> bool {
+        match self {
+            Self::Fresh => true,
+            Self::Stale { .. } => policy.accepts_stale(),
+            Self::Failed { .. } | Self::Pending => false,
+        }
+    }
+}
+
+/// Loads a namespace and classifies what is available.
+pub(crate) async fn check(cache: &Cache, policy: &ReadinessPolicy) -> NamespaceStatus {
+    if let Err(error) = cache.get_value().await {
+        return NamespaceStatus::Failed {
+            error: error.to_string(),
+        };
+    }
+    if cache.is_current().await {
+        return NamespaceStatus::Fresh;
+    }
+    if policy.accepts_stale() {
+        return NamespaceStatus::Stale {
+            refresh_error: None,
+        };
+    }
+    match cache.refresh().await {
+        Ok(()) => NamespaceStatus::Fresh,
+        Err(error) => NamespaceStatus::Stale {
+            refresh_error: Some(error.to_string()),
+        },
+    }
+}
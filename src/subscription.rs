@@ -0,0 +1,375 @@
+//! Listener registration handles.
+//!
+//! Registering a listener returns a [`Subscription`]. The listener stays
+//! registered while the handle is alive; dropping the handle or calling
+//! [`Subscription::unsubscribe`] removes it, so short-lived components do not
+//! leak callbacks that keep firing. [`Subscription::detach`] keeps a listener
+//! registered for the lifetime of its namespace cache.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use apollo_rust_client::{Client, ListenerOptions, client_config::ClientConfig};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = ClientConfig::builder("my-app", "http://apollo-server:8080").build()?;
+//! let client = Client::new(config)?;
+//!
+//! // Receives the current value first, then every later change.
+//! let subscription = client
+//!     .add_listener_with_options(
+//!         "application",
+//!         Arc::new(|result| println!("{result:?}")),
+//!         ListenerOptions::default().replay_current(true),
+//!     )
+//!     .await;
+//!
+//! // The listener is removed when the handle is dropped or unsubscribed.
+//! subscription.unsubscribe();
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    ChangeListener, ConfigChangeEvent, Error, EventListener,
+    change::{KeyChange, key_matches},
+    namespace::get_namespace,
+};
+use serde_json::Value;
+use std::sync::{
+    Arc, PoisonError, RwLock, Weak,
+    atomic::{AtomicU64, Ordering},
+};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// What happened to a namespace, as delivered to registered notifiers.
+pub(crate) enum Notification<'a> {
+    /// The namespace has a new value; `event` lists the changed keys.
+    Changed {
+        config: &'a Value,
+        event: &'a ConfigChangeEvent,
+    },
+    /// A background refresh failed.
+    Failed(&'a str),
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        /// Internal callback shape that every public listener kind adapts to.
+        pub(crate) type Notifier = Arc<dyn Fn(&Notification<'_>)>;
+    } else {
+        /// Internal callback shape that every public listener kind adapts to.
+        pub(crate) type Notifier = Arc<dyn Fn(&Notification<'_>) + Send + Sync>;
+    }
+}
+
+/// Adapts an [`EventListener`], which receives the whole converted namespace.
+pub(crate) fn event_notifier(listener: EventListener) -> Notifier {
+    Arc::new(move |notification| match notification {
+        Notification::Changed { config, event } => {
+            listener(get_namespace(event.namespace(), (*config).clone()).map_err(Error::Namespace));
+        }
+        Notification::Failed(error) => listener(Err(Error::Refresh((*error).to_string()))),
+    })
+}
+
+/// Adapts a [`ChangeListener`], which is skipped when no key changed.
+pub(crate) fn change_notifier(listener: ChangeListener) -> Notifier {
+    Arc::new(move |notification| match notification {
+        Notification::Changed { event, .. } if !event.is_empty() => listener(Ok((*event).clone())),
+        Notification::Changed { .. } => {}
+        Notification::Failed(error) => listener(Err(Error::Refresh((*error).to_string()))),
+    })
+}
+
+/// Adapts a typed per-key callback, invoked once for each changed key that
+/// matches `key_or_prefix`. Refresh errors are not delivered.
+pub(crate) fn key_notifier<T, F>(key_or_prefix: String, callback: F) -> Notifier
+where
+    T: std::str::FromStr + 'static,
+    F: Fn(KeyChange<T>) + Send + Sync + 'static,
+{
+    Arc::new(move |notification| {
+        if let Notification::Changed { event, .. } = notification {
+            for (key, change) in event.changes() {
+                if key_matches(key, &key_or_prefix) {
+                    callback(change.parse(key));
+                }
+            }
+        }
+    })
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        /// A listener a [`ListenerRegistry`] can hold.
+        pub(crate) trait Listener: Clone + 'static {}
+        impl<L: Clone + 'static> Listener for L {}
+
+        type Registration = Weak<dyn Registry>;
+    } else {
+        /// A listener a [`ListenerRegistry`] can hold.
+        pub(crate) trait Listener: Clone + Send + Sync + 'static {}
+        impl<L: Clone + Send + Sync + 'static> Listener for L {}
+
+        type Registration = Weak<dyn Registry + Send + Sync>;
+    }
+}
+
+/// The registry operations a [`Subscription`] needs, whatever the listener
+/// type.
+trait Registry {
+    fn remove(&self, id: u64) -> bool;
+    fn contains(&self, id: u64) -> bool;
+}
+
+/// Ordered listeners, addressable by registration id.
+///
+/// Namespaces hold [`Notifier`]s; other event sources, such as feature flag
+/// exposures, hold their own listener type.
+pub(crate) struct ListenerRegistry<L = Notifier> {
+    next_id: AtomicU64,
+    listeners: RwLock<Vec<(u64, L)>>,
+}
+
+impl<L> Default for ListenerRegistry<L> {
+    fn default() -> Self {
+        Self {
+            next_id: AtomicU64::new(0),
+            listeners: RwLock::new(Vec::new()),
+        }
+    }
+}
+
+impl<L: Listener> ListenerRegistry<L> {
+    /// Appends a listener and returns the handle that removes it.
+    pub(crate) fn register(self: &Arc<Self>, listener: L) -> Subscription {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.listeners
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((id, listener));
+        Subscription {
+            registrations: vec![(Arc::downgrade(self) as Registration, id)],
+            unsubscribe_on_drop: true,
+        }
+    }
+
+    /// Returns the registered listeners in registration order.
+    ///
+    /// Callers invoke the snapshot without holding the registry lock, so a
+    /// listener may unsubscribe itself or others while being notified.
+    pub(crate) fn snapshot(&self) -> Vec<L> {
+        self.listeners
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(_, listener)| listener.clone())
+            .collect()
+    }
+
+    /// Returns whether no listener is registered.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.listeners
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty()
+    }
+
+    /// Unregisters every listener.
+    pub(crate) fn clear(&self) {
+        self.listeners
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+impl<L> Registry for ListenerRegistry<L> {
+    fn remove(&self, id: u64) -> bool {
+        let mut listeners = self
+            .listeners
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        let before = listeners.len();
+        listeners.retain(|(registered, _)| *registered != id);
+        listeners.len() != before
+    }
+
+    fn contains(&self, id: u64) -> bool {
+        self.listeners
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .any(|(registered, _)| *registered == id)
+    }
+}
+
+/// Options for registering a listener.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ListenerOptions {
+    replay_current: bool,
+}
+
+impl ListenerOptions {
+    /// Delivers the namespace's current value to the listener on registration.
+    ///
+    /// The value is loaded first if the namespace has not been read yet. The
+    /// replay is ordered with concurrent updates, so the listener never sees a
+    /// newer value before the replayed one and never misses a change. Nothing
+    /// is replayed if the initial load fails.
+    #[must_use]
+    pub fn replay_current(mut self, replay: bool) -> Self {
+        self.replay_current = replay;
+        self
+    }
+
+    pub(crate) fn replays_current(self) -> bool {
+        self.replay_current
+    }
+}
+
+/// Handle that keeps a listener registered.
+///
+/// Dropping the handle unregisters the listener, as does
+/// [`Subscription::unsubscribe`]. Use [`Subscription::detach`] to keep the
+/// listener for the lifetime of the namespace cache.
+///
+/// JavaScript handles only unregister through an explicit `unsubscribe()`
+/// call, because garbage collection timing is not observable.
+#[must_use = "dropping a Subscription unregisters its listener; call `detach` to keep it"]
+#[wasm_bindgen]
+pub struct Subscription {
+    registrations: Vec<(Registration, u64)>,
+    unsubscribe_on_drop: bool,
+}
+
+impl Subscription {
+    /// Merges handles so one handle controls listeners in several namespaces.
+    pub(crate) fn combine(subscriptions: impl IntoIterator<Item = Subscription>) -> Self {
+        let registrations = subscriptions
+            .into_iter()
+            .flat_map(|mut subscription| std::mem::take(&mut subscription.registrations))
+            .collect();
+        Self {
+            registrations,
+            unsubscribe_on_drop: true,
+        }
+    }
+
+    /// Keeps the listener registered after the handle is dropped.
+    pub fn detach(mut self) {
+        self.unsubscribe_on_drop = false;
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn into_explicit(mut self) -> Self {
+        self.unsubscribe_on_drop = false;
+        self
+    }
+}
+
+#[wasm_bindgen]
+impl Subscription {
+    /// Unregisters the listener.
+    ///
+    /// Returns `true` if the listener was still registered. A notification
+    /// that is already being delivered may still reach the listener.
+    #[allow(clippy::must_use_candidate)]
+    pub fn unsubscribe(self) -> bool {
+        let mut subscription = self;
+        let mut removed = false;
+        for (registry, id) in std::mem::take(&mut subscription.registrations) {
+            if let Some(registry) = registry.upgrade() {
+                removed |= registry.remove(id);
+            }
+        }
+        removed
+    }
+
+    /// Returns whether the listener is still registered.
+    #[must_use]
+    #[wasm_bindgen(js_name = "isActive")]
+    pub fn is_active(&self) -> bool {
+        self.registrations.iter().any(|(registry, id)| {
+            registry
+                .upgrade()
+                .is_some_and(|registry| registry.contains(*id))
+        })
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if self.unsubscribe_on_drop {
+            for (registry, id) in &self.registrations {
+                if let Some(registry) = registry.upgrade() {
+                    registry.remove(*id);
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Subscription {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("Subscription")
+            .field(
+                "ids",
+                &self
+                    .registrations
+                    .iter()
+                    .map(|(_, id)| *id)
+                    .collect::<Vec<_>>(),
+            )
+            .field("active", &self.is_active())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    fn counting_listener(count: &Arc<AtomicUsize>) -> Notifier {
+        let count = count.clone();
+        event_notifier(Arc::new(move |_| {
+            count.fetch_add(1, Ordering::AcqRel);
+        }))
+    }
+
+    #[test]
+    fn drop_and_unsubscribe_remove_only_their_listener() {
+        let registry = Arc::new(ListenerRegistry::default());
+        let count = Arc::new(AtomicUsize::new(0));
+        let first = registry.register(counting_listener(&count));
+        let second = registry.register(counting_listener(&count));
+        let third = registry.register(counting_listener(&count));
+        assert_eq!(registry.snapshot().len(), 3);
+
+        drop(first);
+        assert!(second.is_active());
+        assert!(second.unsubscribe());
+        assert_eq!(registry.snapshot().len(), 1);
+
+        third.detach();
+        assert_eq!(registry.snapshot().len(), 1);
+        for listener in registry.snapshot() {
+            listener(&Notification::Failed("test"));
+        }
+        assert_eq!(count.load(Ordering::Acquire), 1);
+    }
+
+    #[test]
+    fn handles_outliving_the_registry_are_inert() {
+        let registry = Arc::new(ListenerRegistry::default());
+        let subscription = registry.register(Arc::new(|_: &Notification<'_>| {}));
+        drop(registry);
+        assert!(!subscription.is_active());
+        assert!(!subscription.unsubscribe());
+    }
+}
@@ -1,15 +1,18 @@
-//! In-process HTTPS support shared by native tests.
+//! In-process HTTP(S) support shared by native tests and the
+//! [`test_util`](crate::test_util) fake Apollo server.
 
 use rcgen::generate_simple_self_signed;
 use rustls::{
     ServerConfig, ServerConnection, StreamOwned,
     pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer},
 };
+#[cfg(test)]
+use std::sync::OnceLock;
 use std::{
     io::{Read, Write},
     net::{SocketAddr, TcpListener, TcpStream},
     sync::{
-        Arc, Mutex, OnceLock,
+        Arc, Mutex,
         atomic::{AtomicBool, AtomicUsize, Ordering},
     },
     thread,
@@ -23,6 +26,8 @@ pub(crate) struct MockResponse {
     pub(crate) body: String,
     pub(crate) header_delay: Duration,
     pub(crate) body_delay: Duration,
+    /// Closes the connection without writing a response.
+    pub(crate) drop_connection: bool,
 }
 
 impl MockResponse {
@@ -32,9 +37,18 @@ impl MockResponse {
             body: body.into(),
             header_delay: Duration::ZERO,
             body_delay: Duration::ZERO,
+            drop_connection: false,
         }
     }
 
+    pub(crate) fn dropped() -> Self {
+        Self {
+            drop_connection: true,
+            ..Self::json(0, "")
+        }
+    }
+
+    #[cfg(test)]
     pub(crate) fn delayed_body(mut self, delay: Duration) -> Self {
         self.body_delay = delay;
         self
@@ -44,33 +58,57 @@ impl MockResponse {
 pub(crate) type ResponseHandler = dyn Fn(usize, &str) -> MockResponse + Send + Sync + 'static;
 
 /// A random-port, self-signed HTTPS server for transport-level client tests.
+///
+/// [`MockHttpsServer::bind`] can also serve plain HTTP on a chosen address.
 pub(crate) struct MockHttpsServer {
     address: SocketAddr,
+    tls: bool,
+    #[cfg_attr(not(test), allow(dead_code))]
     requests: Arc<AtomicUsize>,
+    #[cfg_attr(not(test), allow(dead_code))]
     captured: Arc<Mutex<Vec<String>>>,
     running: Arc<AtomicBool>,
     thread: Option<thread::JoinHandle<()>>,
 }
 
 impl MockHttpsServer {
+    #[cfg(test)]
     pub(crate) fn new(handler: Arc<ResponseHandler>) -> Self {
+        Self::bind(SocketAddr::from(([127, 0, 0, 1], 0)), handler, true)
+            .expect("test HTTPS server should bind to a random local port")
+    }
+
+    /// Serves `handler` on `address`, over HTTPS if `tls` is set.
+    pub(crate) fn bind(
+        address: SocketAddr,
+        handler: Arc<ResponseHandler>,
+        tls: bool,
+    ) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self::start(listener, handler, tls.then(Self::tls_config)))
+    }
+
+    fn tls_config() -> Arc<ServerConfig> {
         let certified = generate_simple_self_signed(vec!["localhost".to_string()])
             .expect("test certificate generation should succeed");
         let private_key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(
             certified.signing_key.serialize_der(),
         ));
-        let tls_config = Arc::new(
+        Arc::new(
             ServerConfig::builder()
                 .with_no_client_auth()
                 .with_single_cert(vec![certified.cert.der().clone()], private_key)
                 .expect("test TLS configuration should be valid"),
-        );
+        )
+    }
 
-        let listener = TcpListener::bind("127.0.0.1:0")
-            .expect("test HTTPS server should bind to a random local port");
-        listener
-            .set_nonblocking(true)
-            .expect("test listener should support nonblocking mode");
+    fn start(
+        listener: TcpListener,
+        handler: Arc<ResponseHandler>,
+        tls_config: Option<Arc<ServerConfig>>,
+    ) -> Self {
+        let tls = tls_config.is_some();
         let address = listener
             .local_addr()
             .expect("test listener should have a local address");
@@ -109,6 +147,7 @@ impl MockHttpsServer {
 
         Self {
             address,
+            tls,
             requests,
             captured,
             running,
@@ -117,18 +156,32 @@ impl MockHttpsServer {
     }
 
     fn respond(
-        stream: TcpStream,
-        tls_config: Arc<ServerConfig>,
+        mut stream: TcpStream,
+        tls_config: Option<Arc<ServerConfig>>,
         handler: &ResponseHandler,
         requests: &AtomicUsize,
         captured: &Mutex<Vec<String>>,
     ) {
         let _ = stream.set_nonblocking(false);
-        let Ok(connection) = ServerConnection::new(tls_config) else {
-            return;
-        };
-        let mut stream = StreamOwned::new(connection, stream);
-        let _ = stream.sock.set_read_timeout(Some(Duration::from_secs(2)));
+        let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+        match tls_config {
+            Some(tls_config) => {
+                let Ok(connection) = ServerConnection::new(tls_config) else {
+                    return;
+                };
+                let mut stream = StreamOwned::new(connection, stream);
+                Self::exchange(&mut stream, handler, requests, captured);
+            }
+            None => Self::exchange(&mut stream, handler, requests, captured),
+        }
+    }
+
+    fn exchange(
+        stream: &mut (impl Read + Write),
+        handler: &ResponseHandler,
+        requests: &AtomicUsize,
+        captured: &Mutex<Vec<String>>,
+    ) {
         let mut request = Vec::new();
         let mut buffer = [0_u8; 1024];
         loop {
@@ -151,6 +204,9 @@ impl MockHttpsServer {
         let index = requests.fetch_add(1, Ordering::AcqRel) + 1;
         let response = handler(index, &request);
         thread::sleep(response.header_delay);
+        if response.drop_connection {
+            return;
+        }
 
         let reason = if (200..300).contains(&response.status) {
             "OK"
@@ -171,13 +227,16 @@ impl MockHttpsServer {
     }
 
     pub(crate) fn url(&self) -> String {
-        format!("https://localhost:{}", self.address.port())
+        let scheme = if self.tls { "https" } else { "http" };
+        format!("{scheme}://localhost:{}", self.address.port())
     }
 
+    #[cfg(test)]
     pub(crate) fn request_count(&self) -> usize {
         self.requests.load(Ordering::Acquire)
     }
 
+    #[cfg(test)]
     pub(crate) fn request_count_for_path(&self, path: &str) -> usize {
         self.captured
             .lock()
@@ -193,6 +252,7 @@ impl MockHttpsServer {
             .count()
     }
 
+    #[cfg(test)]
     pub(crate) fn captured_requests(&self) -> Vec<String> {
         self.captured
             .lock()
@@ -200,6 +260,7 @@ impl MockHttpsServer {
             .clone()
     }
 
+    #[cfg(test)]
     pub(crate) async fn wait_for_requests(&self, expected: usize) {
         tokio::time::timeout(Duration::from_secs(2), async {
             while self.request_count() < expected {
@@ -221,11 +282,13 @@ impl Drop for MockHttpsServer {
     }
 }
 
+#[cfg(test)]
 pub(crate) fn apollo_server() -> &'static MockHttpsServer {
     static SERVER: OnceLock<MockHttpsServer> = OnceLock::new();
     SERVER.get_or_init(|| MockHttpsServer::new(Arc::new(|_, request| apollo_response(request))))
 }
 
+#[cfg(test)]
 fn apollo_response(request: &str) -> MockResponse {
     let path_and_query = request
         .lines()
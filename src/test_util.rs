@@ -0,0 +1,846 @@
+//! A fake Apollo config service for testing code built on the client.
+//!
+//! Enabled by the `test-util` feature on native targets. [`FakeApollo`] runs
+//! an in-process server on a random local port that speaks the parts of the
+//! Apollo protocol used by the client and its tooling:
+//!
+//! - `GET /configfiles/json/{appId}/{cluster}/{namespace}` serves the
+//!   published configurations;
+//! - `GET /configs/{appId}/{cluster}/{namespace}` serves them with their
+//!   release key and answers `304 Not Modified` to a matching `releaseKey`;
+//! - `GET /notifications/v2` long-polls until one of the requested namespaces
+//!   is published again.
+//!
+//! Releases can be limited to clients by IP or label with a [`GrayRule`],
+//! apps can be made to require signed requests, and scripted [`Fault`]s fail
+//! the next matching requests. Downstream crates can therefore exercise their
+//! reconfiguration and failure handling against the real
+//! [`Client`](crate::Client).
+//!
+//! # Example
+//!
+//! ```
+//! use apollo_rust_client::{
+//!     Client,
+//!     client_config::ClientConfig,
+//!     namespace::Namespace,
+//!     test_util::{FakeApollo, Fault, Release},
+//! };
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let apollo = FakeApollo::start();
+//! apollo.publish(Release::properties("app", "application", [("timeout", "30")]));
+//!
+//! let client = Client::new(ClientConfig::builder("app", apollo.url()).build()?)?;
+//! if let Namespace::Properties(properties) = client.namespace("application").await? {
+//!     assert_eq!(properties.get_int("timeout"), Some(30));
+//! }
+//!
+//! apollo.publish(Release::properties("app", "application", [("timeout", "60")]));
+//! apollo.inject(Fault::Status(503));
+//! assert!(client.refresh("application").await.is_err());
+//! client.refresh("application").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::test_support::{MockHttpsServer, MockResponse};
+use serde_json::{Map, Value, json};
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    net::SocketAddr,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// Largest clock difference accepted between a signed request's timestamp
+/// and the server, as on Apollo.
+const SIGNATURE_TOLERANCE_MILLIS: i64 = 60_000;
+
+/// An in-process fake of the Apollo config service.
+///
+/// The server stops when the value is dropped.
+pub struct FakeApollo {
+    shared: Arc<Shared>,
+    server: MockHttpsServer,
+}
+
+/// Builder for [`FakeApollo`].
+#[derive(Clone, Debug)]
+pub struct FakeApolloBuilder {
+    address: SocketAddr,
+    https: bool,
+    long_poll_timeout: Duration,
+}
+
+/// A configuration release to publish on a [`FakeApollo`].
+#[derive(Clone, Debug)]
+pub struct Release {
+    app_id: String,
+    cluster: String,
+    namespace: String,
+    configurations: Map<String, Value>,
+    gray: Option<GrayRule>,
+}
+
+/// Selects the clients that receive a gray release.
+///
+/// A client matches if the `ip` or one of the comma-separated `label`s it
+/// sends is listed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GrayRule {
+    ips: BTreeSet<String>,
+    labels: BTreeSet<String>,
+}
+
+/// A scripted failure for an upcoming request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// Answers with the given HTTP status and an error body.
+    Status(u16),
+    /// Holds the response back for the given duration before serving it.
+    Timeout(Duration),
+    /// Answers `200 OK` with a body that is not valid JSON.
+    MalformedBody,
+    /// Closes the connection without answering.
+    DropConnection,
+}
+
+/// A request received by a [`FakeApollo`].
+#[derive(Clone, Debug)]
+pub struct RecordedRequest {
+    path: String,
+    query: Vec<(String, String)>,
+    headers: Vec<(String, String)>,
+    status: Option<u16>,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    /// Signalled when a namespace is published or the server stops.
+    published: Condvar,
+    long_poll_timeout: Duration,
+}
+
+#[derive(Default)]
+struct State {
+    namespaces: HashMap<Coordinates, Entry>,
+    secrets: HashMap<String, String>,
+    /// Pending faults, each optionally limited to a namespace.
+    faults: VecDeque<(Option<String>, Fault)>,
+    requests: Vec<RecordedRequest>,
+    notification_id: i64,
+    release_id: u64,
+    closed: bool,
+}
+
+/// App id, cluster and normalized namespace name.
+type Coordinates = (String, String, String);
+
+#[derive(Default)]
+struct Entry {
+    main: Option<Published>,
+    gray: Vec<(GrayRule, Published)>,
+    notification_id: i64,
+}
+
+struct Published {
+    configurations: Map<String, Value>,
+    release_key: String,
+}
+
+enum Route {
+    ConfigFile(Coordinates),
+    Configs(Coordinates),
+    Notifications,
+}
+
+impl FakeApollo {
+    /// Returns a builder for a fake server.
+    #[must_use]
+    pub fn builder() -> FakeApolloBuilder {
+        FakeApolloBuilder {
+            address: SocketAddr::from(([127, 0, 0, 1], 0)),
+            https: false,
+            long_poll_timeout: Duration::from_mins(1),
+        }
+    }
+
+    /// Starts a plain-HTTP fake server with default settings.
+    #[must_use]
+    pub fn start() -> Self {
+        Self::builder().start()
+    }
+
+    /// Returns the base URL to use as the client's `config_server`.
+    #[must_use]
+    pub fn url(&self) -> String {
+        self.server.url()
+    }
+
+    /// Publishes a release, replacing the previous one for the same
+    /// namespace or gray rule, and notifies long-polling clients.
+    pub fn publish(&self, release: Release) {
+        let mut state = self.shared.lock();
+        state.notification_id += 1;
+        state.release_id += 1;
+        let notification_id = state.notification_id;
+        let published = Published {
+            configurations: release.configurations,
+            release_key: format!("fake-release-{}", state.release_id),
+        };
+        let entry = state
+            .namespaces
+            .entry((
+                release.app_id,
+                release.cluster,
+                normalize(&release.namespace).to_string(),
+            ))
+            .or_default();
+        entry.notification_id = notification_id;
+        match release.gray {
+            Some(rule) => {
+                entry.gray.retain(|(existing, _)| *existing != rule);
+                entry.gray.push((rule, published));
+            }
+            None => entry.main = Some(published),
+        }
+        drop(state);
+        self.shared.published.notify_all();
+    }
+
+    /// Removes every release of a namespace, so it is served as not found.
+    pub fn unpublish(&self, app_id: &str, cluster: &str, namespace: &str) {
+        self.shared.lock().namespaces.remove(&(
+            app_id.to_string(),
+            cluster.to_string(),
+            normalize(namespace).to_string(),
+        ));
+    }
+
+    /// Requires requests for `app_id` to be signed with `secret`.
+    ///
+    /// Requests with a missing, stale or wrong signature are answered with
+    /// `401 Unauthorized`.
+    pub fn require_secret(&self, app_id: impl Into<String>, secret: impl Into<String>) {
+        self.shared
+            .lock()
+            .secrets
+            .insert(app_id.into(), secret.into());
+    }
+
+    /// Fails the next request with `fault`.
+    ///
+    /// Faults are consumed in the order they were injected.
+    pub fn inject(&self, fault: Fault) {
+        self.shared.lock().faults.push_back((None, fault));
+    }
+
+    /// Fails the next request for `namespace` with `fault`.
+    pub fn inject_for(&self, namespace: &str, fault: Fault) {
+        self.shared
+            .lock()
+            .faults
+            .push_back((Some(normalize(namespace).to_string()), fault));
+    }
+
+    /// Returns the requests received so far, in arrival order.
+    #[must_use]
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.shared.lock().requests.clone()
+    }
+}
+
+impl Drop for FakeApollo {
+    fn drop(&mut self) {
+        self.shared.lock().closed = true;
+        self.shared.published.notify_all();
+    }
+}
+
+impl FakeApolloBuilder {
+    /// Serves over HTTPS with a self-signed certificate for `localhost`.
+    ///
+    /// Clients need
+    /// [`allow_insecure_https`](crate::client_config::ClientConfigBuilder::allow_insecure_https).
+    #[must_use]
+    pub fn https(mut self, https: bool) -> Self {
+        self.https = https;
+        self
+    }
+
+    /// Sets how long `/notifications/v2` holds a request without changes
+    /// before answering `304 Not Modified`. Defaults to 60 seconds.
+    #[must_use]
+    pub fn long_poll_timeout(mut self, timeout: Duration) -> Self {
+        self.long_poll_timeout = timeout;
+        self
+    }
+
+    /// Listens on `address` instead of a random port on `127.0.0.1`.
+    #[must_use]
+    pub fn bind(mut self, address: SocketAddr) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Starts the server.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the address cannot be bound; see [`Self::try_start`].
+    #[must_use]
+    pub fn start(self) -> FakeApollo {
+        self.try_start()
+            .expect("fake Apollo server should bind to its address")
+    }
+
+    /// Starts the server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address cannot be bound.
+    pub fn try_start(self) -> std::io::Result<FakeApollo> {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State::default()),
+            published: Condvar::new(),
+            long_poll_timeout: self.long_poll_timeout,
+        });
+        let handler_shared = shared.clone();
+        let handler = Arc::new(move |_, request: &str| handler_shared.handle(request));
+        let server = MockHttpsServer::bind(self.address, handler, self.https)?;
+        Ok(FakeApollo { shared, server })
+    }
+}
+
+impl Release {
+    /// Creates a release of a Properties namespace in the `default` cluster.
+    pub fn properties<K, V>(
+        app_id: impl Into<String>,
+        namespace: impl Into<String>,
+        properties: impl IntoIterator<Item = (K, V)>,
+    ) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        Self::new(
+            app_id.into(),
+            namespace.into(),
+            properties
+                .into_iter()
+                .map(|(key, value)| (key.into(), Value::String(value.into())))
+                .collect(),
+        )
+    }
+
+    /// Creates a release of a JSON, YAML, XML or text namespace in the
+    /// `default` cluster.
+    pub fn content(
+        app_id: impl Into<String>,
+        namespace: impl Into<String>,
+        content: impl Into<String>,
+    ) -> Self {
+        let mut configurations = Map::new();
+        configurations.insert("content".to_string(), Value::String(content.into()));
+        Self::new(app_id.into(), namespace.into(), configurations)
+    }
+
+    fn new(app_id: String, namespace: String, configurations: Map<String, Value>) -> Self {
+        Self {
+            app_id,
+            cluster: "default".to_string(),
+            namespace,
+            configurations,
+            gray: None,
+        }
+    }
+
+    /// Publishes to `cluster` instead of `default`.
+    #[must_use]
+    pub fn cluster(mut self, cluster: impl Into<String>) -> Self {
+        self.cluster = cluster.into();
+        self
+    }
+
+    /// Publishes as a gray release served only to clients matching `rule`.
+    #[must_use]
+    pub fn gray(mut self, rule: GrayRule) -> Self {
+        self.gray = Some(rule);
+        self
+    }
+}
+
+impl GrayRule {
+    /// Creates a rule matching no client.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also matches clients sending `ip`.
+    #[must_use]
+    pub fn ip(mut self, ip: impl Into<String>) -> Self {
+        self.ips.insert(ip.into());
+        self
+    }
+
+    /// Also matches clients sending `label`.
+    #[must_use]
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.labels.insert(label.into());
+        self
+    }
+
+    fn matches(&self, ip: Option<&str>, label: Option<&str>) -> bool {
+        ip.is_some_and(|ip| self.ips.contains(ip))
+            || label.is_some_and(|labels| {
+                labels
+                    .split(',')
+                    .any(|label| self.labels.contains(label.trim()))
+            })
+    }
+}
+
+impl RecordedRequest {
+    /// Returns the request path, without the query.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the first value of a query parameter.
+    #[must_use]
+    pub fn query(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns a header value, matching its name case-insensitively.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the status answered, or `None` if the connection was dropped.
+    #[must_use]
+    pub fn status(&self) -> Option<u16> {
+        self.status
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn handle(&self, request: &str) -> MockResponse {
+        let mut lines = request.lines();
+        let target = lines
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .unwrap_or_default();
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let mut recorded = RecordedRequest {
+            path: path.to_string(),
+            query: url::form_urlencoded::parse(query.as_bytes())
+                .into_owned()
+                .collect(),
+            headers: lines
+                .take_while(|line| !line.is_empty())
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+                .collect(),
+            status: None,
+        };
+
+        let response = self.respond(target, &recorded);
+        recorded.status = (!response.drop_connection).then_some(response.status);
+        self.lock().requests.push(recorded);
+        response
+    }
+
+    fn respond(&self, target: &str, request: &RecordedRequest) -> MockResponse {
+        let Some(route) = route(&request.path, request) else {
+            return not_found();
+        };
+        let mut state = self.lock();
+        let namespace = match &route {
+            Route::ConfigFile((_, _, namespace)) | Route::Configs((_, _, namespace)) => {
+                Some(namespace.as_str())
+            }
+            Route::Notifications => None,
+        };
+        let fault = state
+            .faults
+            .iter()
+            .position(|(scope, _)| scope.is_none() || scope.as_deref() == namespace)
+            .and_then(|index| state.faults.remove(index))
+            .map(|(_, fault)| fault);
+        let delay = match fault {
+            Some(Fault::Status(status)) => {
+                return MockResponse::json(status, r#"{"message":"injected fault"}"#);
+            }
+            Some(Fault::MalformedBody) => return MockResponse::json(200, "{not valid json"),
+            Some(Fault::DropConnection) => return MockResponse::dropped(),
+            Some(Fault::Timeout(delay)) => delay,
+            None => Duration::ZERO,
+        };
+
+        let app_id = match &route {
+            Route::ConfigFile((app_id, _, _)) | Route::Configs((app_id, _, _)) => app_id.as_str(),
+            Route::Notifications => request.query("appId").unwrap_or_default(),
+        };
+        if let Some(secret) = state.secrets.get(app_id)
+            && !signed(target, request, app_id, secret)
+        {
+            return MockResponse::json(401, r#"{"message":"unauthorized"}"#);
+        }
+
+        let mut response = match route {
+            Route::ConfigFile(coordinates) => match release_for(&state, &coordinates, request) {
+                Some(published) => MockResponse::json(
+                    200,
+                    Value::Object(published.configurations.clone()).to_string(),
+                ),
+                None => not_found(),
+            },
+            Route::Configs(coordinates) => match release_for(&state, &coordinates, request) {
+                Some(published) if request.query("releaseKey") == Some(&published.release_key) => {
+                    MockResponse::json(304, "")
+                }
+                Some(published) => MockResponse::json(
+                    200,
+                    json!({
+                        "appId": coordinates.0,
+                        "cluster": coordinates.1,
+                        "namespaceName": request.path.rsplit('/').next().map(decode),
+                        "configurations": published.configurations,
+                        "releaseKey": published.release_key,
+                    })
+                    .to_string(),
+                ),
+                None => not_found(),
+            },
+            Route::Notifications => {
+                let Some(watched) = watched(request) else {
+                    return MockResponse::json(400, r#"{"message":"invalid notifications"}"#);
+                };
+                let deadline = Instant::now() + self.long_poll_timeout;
+                loop {
+                    let changed = changes(&state, request, &watched);
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if !changed.is_empty() {
+                        break MockResponse::json(200, Value::Array(changed).to_string());
+                    }
+                    if state.closed || remaining.is_zero() {
+                        break MockResponse::json(304, "");
+                    }
+                    state = self
+                        .published
+                        .wait_timeout(state, remaining)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0;
+                }
+            }
+        };
+        response.header_delay = delay;
+        response
+    }
+}
+
+/// Maps a request path to its endpoint, ignoring any prefix of the
+/// configured server path.
+fn route(path: &str, request: &RecordedRequest) -> Option<Route> {
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(decode)
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    let coordinates = |app_id: &str, cluster: &str, namespace: &str| {
+        (
+            app_id.to_string(),
+            cluster.to_string(),
+            normalize(namespace).to_string(),
+        )
+    };
+    match segments.as_slice() {
+        [.., "configfiles", "json", app_id, cluster, namespace] => {
+            Some(Route::ConfigFile(coordinates(app_id, cluster, namespace)))
+        }
+        [.., "configs", app_id, cluster, namespace] => {
+            Some(Route::Configs(coordinates(app_id, cluster, namespace)))
+        }
+        [.., "notifications", "v2"] if request.query("appId").is_some() => {
+            Some(Route::Notifications)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the release a client receives: the first gray release it matches,
+/// otherwise the main release.
+fn release_for<'a>(
+    state: &'a State,
+    coordinates: &Coordinates,
+    request: &RecordedRequest,
+) -> Option<&'a Published> {
+    let entry = state.namespaces.get(coordinates)?;
+    entry
+        .gray
+        .iter()
+        .find(|(rule, _)| rule.matches(request.query("ip"), request.query("label")))
+        .map(|(_, published)| published)
+        .or(entry.main.as_ref())
+}
+
+/// Parses the `notifications` parameter into the watched namespace names and
+/// the notification ids the client has seen.
+fn watched(request: &RecordedRequest) -> Option<Vec<(String, i64)>> {
+    let notifications: Vec<Value> = serde_json::from_str(request.query("notifications")?).ok()?;
+    notifications
+        .iter()
+        .map(|notification| {
+            Some((
+                notification.get("namespaceName")?.as_str()?.to_string(),
+                notification
+                    .get("notificationId")
+                    .and_then(Value::as_i64)
+                    .unwrap_or(-1),
+            ))
+        })
+        .collect()
+}
+
+/// Returns the notifications for watched namespaces published after the ids
+/// the client has seen.
+fn changes(state: &State, request: &RecordedRequest, watched: &[(String, i64)]) -> Vec<Value> {
+    let app_id = request.query("appId").unwrap_or_default();
+    let cluster = request.query("cluster").unwrap_or("default");
+    watched
+        .iter()
+        .filter_map(|(namespace, seen)| {
+            let entry = state.namespaces.get(&(
+                app_id.to_string(),
+                cluster.to_string(),
+                normalize(namespace).to_string(),
+            ))?;
+            (entry.notification_id > *seen).then(
+                || json!({"namespaceName": namespace, "notificationId": entry.notification_id}),
+            )
+        })
+        .collect()
+}
+
+/// Verifies the `Timestamp` and `Authorization` headers of a request.
+fn signed(target: &str, request: &RecordedRequest, app_id: &str, secret: &str) -> bool {
+    let Some(timestamp) = request
+        .header("timestamp")
+        .and_then(|timestamp| timestamp.parse::<i64>().ok())
+    else {
+        return false;
+    };
+    if (chrono::Utc::now().timestamp_millis() - timestamp).abs() > SIGNATURE_TOLERANCE_MILLIS {
+        return false;
+    }
+    crate::cache::sign(timestamp, target, secret).is_ok_and(|signature| {
+        request.header("authorization") == Some(&format!("Apollo {app_id}:{signature}"))
+    })
+}
+
+/// Apollo serves `name.properties` as the Properties namespace `name`.
+fn normalize(namespace: &str) -> &str {
+    namespace.strip_suffix(".properties").unwrap_or(namespace)
+}
+
+/// Decodes the percent-escapes of a path segment.
+fn decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%'
+            && let Some(byte) = segment
+                .get(index + 1..index + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn not_found() -> MockResponse {
+    MockResponse::json(404, r#"{"message":"namespace not found"}"#)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Client, TempDir, client_config::ClientConfig, namespace::Namespace};
+
+    fn client(apollo: &FakeApollo, temp_dir: &TempDir, ip: Option<&str>) -> Client {
+        let mut builder = ClientConfig::builder("app", apollo.url())
+            .cache_dir(temp_dir.path().to_string_lossy())
+            .request_timeout(1);
+        if let Some(ip) = ip {
+            builder = builder.ip(ip);
+        }
+        Client::new(builder.build().unwrap()).unwrap()
+    }
+
+    async fn version(client: &Client) -> Option<String> {
+        match client.namespace("application").await.unwrap() {
+            Namespace::Properties(properties) => properties.get_string("version"),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn clients_receive_releases_gray_releases_and_scripted_faults() {
+        let apollo = FakeApollo::start();
+        apollo.publish(Release::properties(
+            "app",
+            "application",
+            [("version", "1")],
+        ));
+        apollo.publish(
+            Release::properties("app", "application", [("version", "gray")])
+                .gray(GrayRule::new().ip("10.0.0.7").label("canary")),
+        );
+        let temp_dir = TempDir::new("fake_apollo_releases");
+        let stable = client(&apollo, &temp_dir, None);
+        let canary = client(&apollo, &temp_dir, Some("10.0.0.7"));
+        assert_eq!(version(&stable).await.as_deref(), Some("1"));
+        assert_eq!(version(&canary).await.as_deref(), Some("gray"));
+
+        apollo.publish(Release::properties(
+            "app",
+            "application",
+            [("version", "2")],
+        ));
+        apollo.inject_for("other", Fault::Status(500));
+        apollo.inject(Fault::Status(503));
+        apollo.inject(Fault::MalformedBody);
+        apollo.inject(Fault::DropConnection);
+        apollo.inject(Fault::Timeout(Duration::from_millis(1500)));
+        for _ in 0..4 {
+            assert!(stable.refresh("application").await.is_err());
+        }
+        assert_eq!(version(&stable).await.as_deref(), Some("1"));
+        stable.refresh("application").await.unwrap();
+        assert_eq!(version(&stable).await.as_deref(), Some("2"));
+
+        let statuses: Vec<_> = apollo
+            .requests()
+            .iter()
+            .map(RecordedRequest::status)
+            .collect();
+        assert_eq!(
+            statuses[2..],
+            [Some(503), Some(200), None, Some(200), Some(200)]
+        );
+        apollo.unpublish("app", "default", "application");
+        assert!(stable.refresh("application").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn requests_must_carry_a_valid_signature_when_a_secret_is_required() {
+        let apollo = FakeApollo::builder().https(true).start();
+        apollo.publish(Release::content("app", "settings.json", r#"{"on":true}"#));
+        apollo.require_secret("app", "s3cret");
+        let temp_dir = TempDir::new("fake_apollo_signature");
+        let config = |secret: &str| {
+            ClientConfig::builder("app", apollo.url())
+                .cache_dir(temp_dir.path().to_string_lossy())
+                .allow_insecure_https(true)
+                .secret(secret)
+                .build()
+                .unwrap()
+        };
+
+        let wrong = Client::new(config("wrong")).unwrap();
+        assert!(wrong.namespace("settings.json").await.is_err());
+        let right = Client::new(config("s3cret")).unwrap();
+        assert!(matches!(
+            right.namespace("settings.json").await.unwrap(),
+            Namespace::Json(_)
+        ));
+
+        let requests = apollo.requests();
+        assert_eq!(requests[0].status(), Some(401));
+        assert_eq!(requests[1].status(), Some(200));
+        assert!(
+            requests[1]
+                .header("Authorization")
+                .unwrap()
+                .starts_with("Apollo app:")
+        );
+    }
+
+    #[tokio::test]
+    async fn notifications_long_poll_until_a_namespace_is_published() {
+        let apollo = FakeApollo::builder()
+            .long_poll_timeout(Duration::from_millis(200))
+            .start();
+        apollo.publish(Release::properties(
+            "app",
+            "application",
+            [("version", "1")],
+        ));
+        let http = reqwest::Client::new();
+        let get = |path: String| {
+            let request = http.get(format!("{}{path}", apollo.url()));
+            async move { request.send().await.unwrap() }
+        };
+
+        let configs = get("/configs/app/default/application".to_string()).await;
+        let body: Value = configs.json().await.unwrap();
+        assert_eq!(body["configurations"], json!({"version": "1"}));
+        let release_key = body["releaseKey"].as_str().unwrap().to_string();
+        let unchanged = get(format!(
+            "/configs/app/default/application?releaseKey={release_key}"
+        ))
+        .await;
+        assert_eq!(unchanged.status(), 304);
+
+        let poll = |id: i64| {
+            get(format!(
+                "/notifications/v2?appId=app&cluster=default&notifications={}",
+                url::form_urlencoded::byte_serialize(
+                    json!([{"namespaceName": "application", "notificationId": id}])
+                        .to_string()
+                        .as_bytes()
+                )
+                .collect::<String>()
+            ))
+        };
+        let current: Value = poll(-1).await.json().await.unwrap();
+        let id = current[0]["notificationId"].as_i64().unwrap();
+        assert_eq!(poll(id).await.status(), 304);
+
+        let waiting = poll(id);
+        let publisher = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            apollo.publish(Release::properties(
+                "app",
+                "application",
+                [("version", "2")],
+            ));
+        };
+        let (changed, ()) = tokio::join!(waiting, publisher);
+        let changed: Value = changed.json().await.unwrap();
+        assert_eq!(changed[0]["namespaceName"], "application");
+        assert!(changed[0]["notificationId"].as_i64().unwrap() > id);
+    }
+}
@@ -0,0 +1,454 @@
+//! Typed configuration structs.
+//!
+//! `#[derive(ApolloConfig)]`, enabled by the `derive` feature, implements
+//! [`ApolloConfig`] for a struct by reading each field from one key of a
+//! Properties, JSON or YAML namespace. Keys are checked when the struct is
+//! loaded, and a single [`Error`] lists every key that is missing or does not
+//! convert, instead of typos surfacing one `None` at a time.
+//!
+//! # Attributes
+//!
+//! On the struct:
+//!
+//! - `#[apollo(prefix = "db.")]` prepends a prefix to every key.
+//! - `#[apollo(rename_all = "...")]` derives keys from field names in
+//!   `"dot.case"`, `"kebab-case"`, `"camelCase"` or `"snake_case"` (the
+//!   default).
+//!
+//! On a field:
+//!
+//! - `#[apollo(key = "pool.size")]` reads another key; the prefix still
+//!   applies.
+//! - `#[apollo(default)]` uses `Default::default()` when the key is missing.
+//! - `#[apollo(default = 8)]` uses an expression when the key is missing. A
+//!   string literal is converted like a configured value, so
+//!   `default = "30"` works for numbers too.
+//!
+//! `Option` fields are optional; every other field without a default is
+//! required.
+#![cfg_attr(
+    feature = "derive",
+    doc = r#"
+# Examples
+
+```rust,no_run
+use apollo_rust_client::{ApolloConfig, Client, client_config::ClientConfig};
+
+#[derive(ApolloConfig)]
+#[apollo(prefix = "db.", rename_all = "dot.case")]
+struct Database {
+    url: String,
+    #[apollo(default = 8)]
+    pool_size: u32,
+    timeout_seconds: Option<u64>,
+}
+
+# #[tokio::main(flavor = "current_thread")]
+# async fn main() -> Result<(), Box<dyn std::error::Error>> {
+let config = ClientConfig::builder("my-app", "http://apollo-server:8080").build()?;
+let client = Client::new(config)?;
+
+// Reads db.url, db.pool.size and db.timeout.seconds.
+let database: Database = client.load_config("application").await?;
+println!("{} ({} connections)", database.url, database.pool_size);
+
+// Follows namespace changes.
+let live = client.bind_config::<Database>("application").await?;
+println!("{}", live.load().url);
+# Ok(())
+# }
+```
+"#
+)]
+
+use crate::namespace::{
+    Namespace,
+    binding::{self, Node},
+    path,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// A struct loaded from the keys of a namespace.
+///
+/// Usually derived with `#[derive(ApolloConfig)]`; see the [module
+/// documentation](self).
+pub trait ApolloConfig: Sized {
+    /// Reads the struct from the keys of `source`.
+    ///
+    /// # Errors
+    ///
+    /// Returns every key that is missing or does not convert.
+    fn from_source(source: &Source) -> Result<Self, Error>;
+
+    /// Reads the struct from a Properties, JSON or YAML namespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns every key that is missing or does not convert, or
+    /// [`Error::Unsupported`] for text namespaces.
+    fn from_namespace(namespace: &Namespace) -> Result<Self, Error> {
+        Self::from_source(&Source::from_namespace(namespace)?)
+    }
+}
+
+/// A key that could not be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeyError {
+    /// A required key is not set.
+    Missing {
+        /// The full key, prefix included.
+        key: String,
+    },
+    /// A key holds a value that does not convert to the field type.
+    Invalid {
+        /// The full key, prefix included.
+        key: String,
+        /// The configured value.
+        value: String,
+        /// Why it does not convert.
+        reason: String,
+    },
+}
+
+/// Errors loading an [`ApolloConfig`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    /// Keys are missing or do not convert, listed in field order.
+    #[error("Invalid configuration: {}", Keys(.0))]
+    Keys(Vec<KeyError>),
+
+    /// The JSON or YAML document does not parse.
+    #[error("Failed to parse the namespace: {0}")]
+    Parse(String),
+
+    /// The namespace holds text, which has no keys.
+    #[error("Text namespaces have no keys")]
+    Unsupported,
+}
+
+impl Error {
+    /// Returns the keys that could not be read.
+    #[must_use]
+    pub fn keys(&self) -> &[KeyError] {
+        match self {
+            Self::Keys(keys) => keys,
+            Self::Parse(_) | Self::Unsupported => &[],
+        }
+    }
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { key } => write!(formatter, "{key} is missing"),
+            Self::Invalid { key, value, reason } => {
+                write!(formatter, "{key} = {value:?} is invalid: {reason}")
+            }
+        }
+    }
+}
+
+/// Formats key errors as a `; `-separated list.
+struct Keys<'a>(&'a [KeyError]);
+
+impl fmt::Display for Keys<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, error) in self.0.iter().enumerate() {
+            if index > 0 {
+                formatter.write_str("; ")?;
+            }
+            write!(formatter, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The keys of a namespace.
+///
+/// A key is looked up as a whole first, as Properties keys are, then with
+/// [`path::lookup`], so `db.hosts.0`, `db.hosts[0]` and `/db/hosts/0` read
+/// `{"db": {"hosts": ["a"]}}` in a JSON or YAML namespace.
+///
+/// Keys of a Properties namespace convert by the relaxed rules of
+/// [`Properties::to_object`](crate::namespace::properties::Properties::to_object):
+/// booleans may be written `on` or `yes`, a comma-separated value fills a
+/// list, and a field read from a key with no value of its own collects the
+/// keys under it, such as `hosts[0]` and `hosts[1]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Source {
+    root: Value,
+    properties: bool,
+}
+
+impl Source {
+    /// Wraps a key-value object or a document tree.
+    #[must_use]
+    pub fn new(root: Value) -> Self {
+        Self {
+            root,
+            properties: false,
+        }
+    }
+
+    /// Returns the keys of a Properties, JSON or YAML namespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Unsupported`] for text namespaces, and
+    /// [`Error::Parse`] if a document does not parse.
+    pub fn from_namespace(namespace: &Namespace) -> Result<Self, Error> {
+        let root = match namespace {
+            Namespace::Properties(properties) => {
+                return Ok(Self {
+                    root: properties.as_value().clone(),
+                    properties: true,
+                });
+            }
+            Namespace::Json(json) => json
+                .to_object()
+                .map_err(|error| Error::Parse(error.to_string()))?,
+            Namespace::Yaml(yaml) => yaml
+                .to_object()
+                .map_err(|error| Error::Parse(error.to_string()))?,
+            Namespace::Text(_) => return Err(Error::Unsupported),
+        };
+        Ok(Self::new(root))
+    }
+
+    /// Returns the value of `key`, or `None` if it is not set or `null`.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        let value = match self.root.get(key) {
+            Some(value) => value,
+            None => path::lookup(&self.root, key)?,
+        };
+        (!value.is_null()).then_some(value)
+    }
+
+    /// Returns the value a field reads from `key`: the value of the key, or
+    /// for a Properties source, the keys nested under it.
+    fn field(&self, key: &str) -> Option<Value> {
+        if let Some(value) = self.get(key) {
+            return Some(value.clone());
+        }
+        if !self.properties {
+            return None;
+        }
+        let nested: Map<String, Value> = self
+            .root
+            .as_object()?
+            .iter()
+            .filter(|(nested, _)| {
+                nested
+                    .strip_prefix(key)
+                    .is_some_and(|rest| rest.starts_with(['.', '[']))
+            })
+            .map(|(nested, value)| (nested.clone(), value.clone()))
+            .collect();
+        (!nested.is_empty()).then_some(Value::Object(nested))
+    }
+
+    /// Converts the value a field reads from `key`.
+    fn convert<T: DeserializeOwned>(&self, key: &str, value: &Value) -> Result<T, String> {
+        if !self.properties {
+            return convert(value);
+        }
+        let converted = match value {
+            Value::String(text) => binding::from_str(text),
+            Value::Object(_) => T::deserialize(&Node::from_properties(value).into_subtree(key)),
+            value => binding::from_str(&value.to_string()),
+        };
+        converted.map_err(|error| error.to_string())
+    }
+
+    /// Starts reading fields, collecting every error until
+    /// [`Fields::finish`].
+    #[must_use]
+    pub fn fields(&self) -> Fields<'_> {
+        Fields {
+            source: self,
+            errors: Vec::new(),
+        }
+    }
+}
+
+/// Reads the fields of an [`ApolloConfig`] from a [`Source`].
+///
+/// Derived implementations read every field before returning, so the error
+/// lists all problems at once.
+#[derive(Debug)]
+pub struct Fields<'a> {
+    source: &'a Source,
+    errors: Vec<KeyError>,
+}
+
+impl Fields<'_> {
+    /// Reads a required key, recording an error if it is missing or does not
+    /// convert.
+    pub fn required<T: DeserializeOwned>(&mut self, key: &str) -> Option<T> {
+        let Some(value) = self.source.field(key) else {
+            self.errors.push(KeyError::Missing {
+                key: key.to_string(),
+            });
+            return None;
+        };
+        self.convert(key, &value)
+    }
+
+    /// Reads an optional key, recording an error if it is set but does not
+    /// convert.
+    pub fn optional<T: DeserializeOwned>(&mut self, key: &str) -> Option<T> {
+        let value = self.source.field(key)?;
+        self.convert(key, &value)
+    }
+
+    /// Reads a key, converting `default` like a configured value if it is
+    /// missing.
+    pub fn or_default_str<T: DeserializeOwned>(&mut self, key: &str, default: &str) -> Option<T> {
+        match self.source.field(key) {
+            Some(value) => self.convert(key, &value),
+            None => self.convert(key, &Value::String(default.to_string())),
+        }
+    }
+
+    /// Returns `value` if every field was read.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Keys`] with every missing or invalid key.
+    pub fn finish<T>(self, value: Option<T>) -> Result<T, Error> {
+        match value {
+            Some(value) if self.errors.is_empty() => Ok(value),
+            _ => Err(Error::Keys(self.errors)),
+        }
+    }
+
+    fn convert<T: DeserializeOwned>(&mut self, key: &str, value: &Value) -> Option<T> {
+        match self.source.convert(key, value) {
+            Ok(converted) => Some(converted),
+            Err(reason) => {
+                self.errors.push(KeyError::Invalid {
+                    key: key.to_string(),
+                    value: match value {
+                        Value::String(text) => text.clone(),
+                        value => value.to_string(),
+                    },
+                    reason,
+                });
+                None
+            }
+        }
+    }
+}
+
+/// Converts a configured value, reading strings as JSON scalars and lists
+/// when the field is not a string, and scalars as strings when it is.
+pub(crate) fn convert<T: DeserializeOwned>(value: &Value) -> Result<T, String> {
+    let error = match T::deserialize(value) {
+        Ok(converted) => return Ok(converted),
+        Err(error) => error.to_string(),
+    };
+    let relaxed = match value {
+        Value::String(text) => serde_json::from_str(text.trim()).ok(),
+        Value::Number(_) | Value::Bool(_) => T::deserialize(Value::String(value.to_string())).ok(),
+        _ => None,
+    };
+    relaxed.ok_or(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn looks_up_flat_keys_then_dotted_paths() {
+        let source = Source::new(json!({
+            "db.url": "flat",
+            "db": {"url": "nested", "hosts": ["a", "b"], "pool": null},
+        }));
+        assert_eq!(source.get("db.url"), Some(&json!("flat")));
+        assert_eq!(source.get("db.hosts.1"), Some(&json!("b")));
+        assert_eq!(source.get("db.hosts[0]"), Some(&json!("a")));
+        assert_eq!(source.get("/db/url"), Some(&json!("nested")));
+        assert_eq!(source.get("db.pool"), None);
+        assert_eq!(source.get("db.hosts.2"), None);
+    }
+
+    #[test]
+    fn converts_properties_keys_by_relaxed_rules() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        enum Mode {
+            ReadOnly,
+        }
+        let namespace = Namespace::Properties(
+            json!({
+                "cache.enabled": "on",
+                "hosts": "a, b",
+                "ports[0]": "80",
+                "ports[1]": "8080",
+                "mode": "read-only",
+                "debug": "maybe",
+            })
+            .into(),
+        );
+        let source = Source::from_namespace(&namespace).unwrap();
+        let mut fields = source.fields();
+        assert_eq!(fields.required::<bool>("cache.enabled"), Some(true));
+        assert_eq!(
+            fields.required::<Vec<String>>("hosts"),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(fields.required::<Vec<u16>>("ports"), Some(vec![80, 8080]));
+        assert_eq!(fields.required::<Mode>("mode"), Some(Mode::ReadOnly));
+        assert_eq!(fields.or_default_str::<bool>("audit", "yes"), Some(true));
+        assert_eq!(fields.required::<bool>("debug"), None);
+        assert_eq!(fields.required::<Vec<u16>>("port"), None);
+
+        let error = fields.finish(Some(())).unwrap_err();
+        assert!(
+            matches!(&error.keys()[0], KeyError::Invalid { key, value, .. } if key == "debug" && value == "maybe")
+        );
+        assert_eq!(
+            error.keys()[1],
+            KeyError::Missing {
+                key: "port".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn collects_every_missing_and_invalid_key() {
+        let source = Source::new(json!({
+            "port": "80",
+            "ratio": "high",
+            "hosts": "[\"a\",\"b\"]",
+            "name": 7,
+        }));
+        let mut fields = source.fields();
+        assert_eq!(fields.required::<u16>("port"), Some(80));
+        assert_eq!(
+            fields.optional::<Vec<String>>("hosts"),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(fields.required::<String>("name"), Some("7".to_string()));
+        assert_eq!(fields.or_default_str::<u64>("timeout", "30"), Some(30));
+        assert_eq!(fields.optional::<f64>("ratio"), None);
+        assert_eq!(fields.required::<String>("host"), None);
+
+        let error = fields.finish(Some(())).unwrap_err();
+        assert_eq!(error.keys().len(), 2);
+        assert!(
+            matches!(&error.keys()[0], KeyError::Invalid { key, value, .. } if key == "ratio" && value == "high")
+        );
+        assert_eq!(
+            error.keys()[1],
+            KeyError::Missing {
+                key: "host".to_string()
+            }
+        );
+        assert!(error.to_string().contains("host is missing"));
+    }
+}
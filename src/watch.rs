@@ -0,0 +1,95 @@
+//! Stream adapter behind [`crate::Client::watch`].
+//!
+//! A notifier publishes each update into a `tokio::sync::watch` channel that
+//! holds only the latest value and a version counter. The stream converts
+//! values lazily when the consumer polls, so a slow consumer skips straight to
+//! the newest value and learns how many it missed from the version gap.
+
+use crate::{
+    Error, Subscription,
+    namespace::{Namespace, get_namespace},
+    subscription::{Notification, Notifier},
+};
+use futures::Stream;
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// The latest notification, numbered so consumers can count skipped ones.
+#[derive(Clone, Default)]
+pub(crate) struct Update {
+    version: u64,
+    result: Option<Result<Value, String>>,
+}
+
+/// Returns a notifier that publishes into a new channel, and the channel's
+/// receiver.
+pub(crate) fn channel() -> (Notifier, watch::Receiver<Update>) {
+    let (sender, receiver) = watch::channel(Update::default());
+    let notifier: Notifier = Arc::new(move |notification| {
+        let result = match notification {
+            Notification::Changed { config, .. } => Ok((*config).clone()),
+            Notification::Failed(error) => Err((*error).to_string()),
+        };
+        sender.send_modify(|update| {
+            update.version += 1;
+            update.result = Some(result);
+        });
+    });
+    (notifier, receiver)
+}
+
+struct State {
+    namespace: String,
+    receiver: watch::Receiver<Update>,
+    seen: u64,
+    pending: Option<Result<Value, String>>,
+    _subscription: Subscription,
+}
+
+impl State {
+    fn convert(&self, result: Result<Value, String>) -> Result<Namespace, Error> {
+        match result {
+            Ok(config) => get_namespace(&self.namespace, config).map_err(Error::Namespace),
+            Err(error) => Err(Error::Refresh(error)),
+        }
+    }
+}
+
+/// Turns a channel into a stream that ends when the subscription's cache is
+/// dropped. The stream owns `subscription`, so dropping it unregisters.
+pub(crate) fn stream(
+    namespace: String,
+    receiver: watch::Receiver<Update>,
+    subscription: Subscription,
+) -> impl Stream<Item = Result<Namespace, Error>> {
+    let state = State {
+        namespace,
+        receiver,
+        seen: 0,
+        pending: None,
+        _subscription: subscription,
+    };
+    futures::stream::unfold(state, |mut state| async move {
+        if let Some(result) = state.pending.take() {
+            let item = state.convert(result);
+            return Some((item, state));
+        }
+        loop {
+            state.receiver.changed().await.ok()?;
+            let (version, result) = {
+                let update = state.receiver.borrow_and_update();
+                (update.version, update.result.clone())
+            };
+            let Some(result) = result else { continue };
+            let skipped = version - state.seen - 1;
+            state.seen = version;
+            if skipped > 0 {
+                state.pending = Some(result);
+                return Some((Err(Error::Lagged(skipped)), state));
+            }
+            let item = state.convert(result);
+            return Some((item, state));
+        }
+    })
+}
@@ -0,0 +1,88 @@
+//! Checks of the code generated by `#[derive(ApolloConfig)]`.
+
+use apollo_rust_client::{
+    namespace::{Namespace, properties::Properties},
+    typed::{ApolloConfig, KeyError, Source},
+};
+use apollo_rust_client_derive::ApolloConfig;
+use serde_json::json;
+use std::time::Duration;
+
+#[derive(ApolloConfig, Debug, PartialEq)]
+#[apollo(prefix = "db.", rename_all = "dot.case")]
+struct Database {
+    url: String,
+    #[apollo(key = "pool.max")]
+    pool_size: u32,
+    #[apollo(default = "30")]
+    timeout_seconds: u64,
+    #[apollo(default = vec!["primary".to_string()])]
+    replicas: Vec<String>,
+    #[apollo(default)]
+    read_only: bool,
+    schema: Option<String>,
+}
+
+#[derive(ApolloConfig, Debug, PartialEq)]
+#[apollo(rename_all = "camelCase")]
+struct Client {
+    retry_count: u8,
+    #[apollo(default = Duration::from_secs(5).as_millis())]
+    timeout_millis: u128,
+}
+
+#[test]
+fn reads_prefixed_renamed_and_defaulted_keys() {
+    let properties = Properties::from(json!({
+        "db.url": "postgres://db",
+        "db.pool.max": "16",
+        "db.read.only": "true",
+        "db.schema": "public",
+    }));
+    let database = Database::from_namespace(&Namespace::Properties(properties)).unwrap();
+    assert_eq!(
+        database,
+        Database {
+            url: "postgres://db".to_string(),
+            pool_size: 16,
+            timeout_seconds: 30,
+            replicas: vec!["primary".to_string()],
+            read_only: true,
+            schema: Some("public".to_string()),
+        }
+    );
+
+    let nested = Source::new(json!({"retryCount": 3}));
+    assert_eq!(
+        Client::from_source(&nested).unwrap(),
+        Client {
+            retry_count: 3,
+            timeout_millis: 5000,
+        }
+    );
+}
+
+#[test]
+fn reports_every_missing_and_invalid_key() {
+    let source = Source::new(json!({
+        "db": {"pool": {"max": "many"}, "timeout": {"seconds": -1}, "schema": 4},
+    }));
+    let error = Database::from_source(&source).unwrap_err();
+    let keys: Vec<_> = error
+        .keys()
+        .iter()
+        .map(|key| match key {
+            KeyError::Missing { key } => format!("missing {key}"),
+            KeyError::Invalid { key, .. } => format!("invalid {key}"),
+        })
+        .collect();
+    assert_eq!(
+        keys,
+        [
+            "missing db.url",
+            "invalid db.pool.max",
+            "invalid db.timeout.seconds",
+        ]
+    );
+    assert!(error.to_string().contains("db.url is missing"));
+}
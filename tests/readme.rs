@@ -14,7 +14,7 @@ async fn readme_quick_start_compiles() -> Result<(), Box<dyn std::error::Error>>
         .refresh_interval(30)
         .request_timeout(10)
         .build()?;
-    let mut client = Client::new(config)?;
+    let client = Client::new(config)?;
     client.start().await?;
 
     match client.namespace("application").await? {
@@ -30,10 +30,11 @@ async fn readme_quick_start_compiles() -> Result<(), Box<dyn std::error::Error>>
         Namespace::Text(text) => drop(text),
     }
 
-    client
+    let subscription = client
         .add_listener("application", std::sync::Arc::new(|_| {}))
         .await;
     client.refresh("application").await?;
+    subscription.unsubscribe();
     client.stop().await;
     Ok(())
 }